@@ -0,0 +1,75 @@
+//! Move generation and attack detection timings, run with
+//! `cargo bench --no-default-features --bench movegen`.
+
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use luma::{get_current_player_moves, is_check, perft, Color, Position};
+
+const PERFT_POSITIONS: [(&str, &str, u8); 3] = [
+    (
+        "start",
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        4,
+    ),
+    (
+        "kiwipete",
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        3,
+    ),
+    ("endgame", "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1", 5),
+];
+const CHECK_ITERATIONS: u32 = 20_000;
+
+fn main() {
+    for (name, fen, depth) in PERFT_POSITIONS {
+        let position = Position::from_fen(fen).unwrap();
+        let start = Instant::now();
+        let nodes = perft(black_box(&position), depth);
+        report(
+            &format!("perft {} depth {}", name, depth),
+            nodes,
+            start.elapsed(),
+        );
+    }
+
+    // Every position two plies after the start, so the kings are attacked from
+    // many different piece configurations.
+    let start = Position::new_starting_position();
+    let positions: Vec<Position> = get_current_player_moves(&start)
+        .into_iter()
+        .map(|chess_move| start.apply(chess_move))
+        .flat_map(|position| {
+            get_current_player_moves(&position)
+                .into_iter()
+                .map(move |chess_move| position.apply(chess_move))
+        })
+        .collect();
+    let start = Instant::now();
+    let mut checks = 0;
+    for _ in 0..CHECK_ITERATIONS {
+        for position in &positions {
+            if is_check(black_box(position), Color::White) {
+                checks += 1;
+            }
+        }
+    }
+    black_box(checks);
+    report(
+        "is_check",
+        CHECK_ITERATIONS as u64 * positions.len() as u64,
+        start.elapsed(),
+    );
+}
+
+fn report(name: &str, count: u64, elapsed: Duration) {
+    println!(
+        "{:<24} {:>10} in {:>8.1} ms, {:>8.2} M/s",
+        name,
+        count,
+        elapsed.as_secs_f64() * 1000.0,
+        count as f64 / elapsed.as_secs_f64() / 1_000_000.0
+    );
+}
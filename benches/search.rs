@@ -0,0 +1,80 @@
+//! Nodes searched to a fixed depth, the measure for move ordering and pruning. Run with
+//! `cargo bench --no-default-features --bench search`.
+
+use std::{
+    sync::atomic::AtomicBool,
+    time::{Duration, Instant},
+};
+
+use luma::{
+    iterative_deepening, GameHistory, Position, SearchConfig, SearchLimits, TranspositionTable,
+};
+
+const SEARCH_POSITIONS: [(&str, &str, u8); 4] = [
+    (
+        "start",
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        5,
+    ),
+    (
+        "kiwipete",
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        3,
+    ),
+    (
+        "italian",
+        "r1bqk2r/pppp1ppp/2n2n2/2b1p3/2B1P3/3P1N2/PPP2PPP/RNBQK2R w KQkq - 0 1",
+        4,
+    ),
+    ("endgame", "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1", 7),
+];
+
+fn main() {
+    for (config_name, config) in [
+        ("plain", SearchConfig::plain()),
+        ("selective", SearchConfig::default()),
+    ] {
+        let mut total_nodes = 0;
+        let mut total_elapsed = Duration::ZERO;
+        for (name, fen, depth) in SEARCH_POSITIONS {
+            let history = GameHistory::new(Position::from_fen(fen).unwrap());
+            let stop = AtomicBool::new(false);
+            let mut table = TranspositionTable::new(16);
+            let limits = SearchLimits {
+                max_depth: Some(depth),
+                config,
+                ..Default::default()
+            };
+            let start = Instant::now();
+            let info = iterative_deepening(&history, &limits, &stop, &mut table, |_| {}).unwrap();
+            let elapsed = start.elapsed();
+            report(
+                &format!("{} {} depth {}", config_name, name, depth),
+                info.nodes,
+                elapsed,
+            );
+            println!(
+                "{:<32} {:>10.1}% pawn table hits",
+                "",
+                info.pawn_hits as f64 / 10.0
+            );
+            total_nodes += info.nodes;
+            total_elapsed += elapsed;
+        }
+        report(
+            &format!("{} total", config_name),
+            total_nodes,
+            total_elapsed,
+        );
+    }
+}
+
+fn report(name: &str, nodes: u64, elapsed: Duration) {
+    println!(
+        "{:<32} {:>10} nodes in {:>8.1} ms, {:>8.2} M/s",
+        name,
+        nodes,
+        elapsed.as_secs_f64() * 1000.0,
+        nodes as f64 / elapsed.as_secs_f64() / 1_000_000.0
+    );
+}
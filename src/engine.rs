@@ -1,36 +1,60 @@
+use std::sync::atomic::AtomicBool;
+
 use crate::engine::{
     check::is_check,
     chess_moves::{get_current_player_moves, ChessMove},
     directions::squares::Square,
+    game_history::GameHistory,
     piece::Piece::{self},
     position::Position,
-    search_algorithms::get_best_move,
+    search_algorithms::{
+        iterative_deepening::iterative_deepening, limits::SearchLimits, score::Score,
+        DEFAULT_MOVE_TIME,
+    },
+    transposition_table::TranspositionTable,
 };
 
-pub fn get_next_move(position: &Position) -> MoveOrEnd {
-    match get_best_move(*position) {
-        Some(chess_move) => MoveOrEnd::Move(chess_move),
-        None => {
-            if is_check(position, position.get_player()) {
-                MoveOrEnd::GameEnd(GameEnd::Victory)
-            } else {
-                MoveOrEnd::GameEnd(GameEnd::Draw)
-            }
-        }
+/// Searches the move to play with `threads` search threads. Setting `stop` ends the search
+/// early, after at least one completed iteration.
+pub fn get_next_move(
+    history: &GameHistory,
+    table: &mut TranspositionTable,
+    threads: usize,
+    stop: &AtomicBool,
+) -> MoveOrEnd {
+    if let Some(game_end) = get_game_end(history) {
+        return MoveOrEnd::GameEnd(game_end);
+    }
+    let mut limits = SearchLimits::move_time(DEFAULT_MOVE_TIME);
+    limits.config.threads = threads;
+    let iteration = iterative_deepening(history, &limits, stop, table, |_| {});
+    match iteration
+        .and_then(|iteration| Some((iteration.best_move()?, iteration.depth, iteration.score())))
+    {
+        Some((chess_move, depth, score)) => MoveOrEnd::Move(chess_move, depth, score),
+        None => MoveOrEnd::GameEnd(GameEnd::Draw(DrawReason::Stalemate)),
     }
 }
 
-pub fn get_possible_moves(position: &Position) -> Result<Vec<ChessMove>, GameEnd> {
-    let chess_moves: Vec<ChessMove> = get_current_player_moves(position);
+pub fn get_possible_moves(history: &GameHistory) -> Result<Vec<ChessMove>, GameEnd> {
+    match get_game_end(history) {
+        Some(game_end) => Err(game_end),
+        None => Ok(get_current_player_moves(&history.get_current())),
+    }
+}
 
-    if chess_moves.is_empty() {
-        if is_check(position, position.get_player()) {
-            Err(GameEnd::Victory)
+/// Checkmate and stalemate come first, a mating move ends the game even if it
+/// reaches the fifty-move limit.
+pub fn get_game_end(history: &GameHistory) -> Option<GameEnd> {
+    let position = history.get_current();
+    if get_current_player_moves(&position).is_empty() {
+        if is_check(&position, position.get_player()) {
+            Some(GameEnd::Victory)
         } else {
-            Err(GameEnd::Draw)
+            Some(GameEnd::Draw(DrawReason::Stalemate))
         }
     } else {
-        Ok(chess_moves)
+        history.get_draw_reason().map(GameEnd::Draw)
     }
 }
 
@@ -48,21 +72,33 @@ pub fn get_check_square(position: &Position) -> Option<Square> {
 }
 #[derive(Debug)]
 pub enum MoveOrEnd {
-    Move(ChessMove),
+    /// The move to play, the depth it was searched to and its score for the side to move.
+    Move(ChessMove, u8, Score),
     GameEnd(GameEnd),
 }
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum GameEnd {
-    Draw,
+    Draw(DrawReason),
     Victory,
 }
-pub mod cache;
-mod check;
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DrawReason {
+    Stalemate,
+    ThreefoldRepetition,
+    FiftyMoveRule,
+    SeventyFiveMoveRule,
+    InsufficientMaterial,
+}
+pub mod attacks;
+pub mod check;
 pub mod chess_moves;
 pub mod directions;
-mod evaluation;
+pub mod evaluation;
+pub mod game_history;
+pub mod pgn;
 pub mod piece;
 pub mod position;
 pub mod search_algorithms;
 #[cfg(test)]
 mod tests;
+pub mod transposition_table;
@@ -0,0 +1,77 @@
+//! Precomputed attack sets. Knights, kings and pawns look their attacks up in a table
+//! per square; rooks and bishops use fancy magic bitboards, so a sliding attack costs
+//! one multiplication instead of a walk along every ray.
+
+use once_cell::sync::Lazy;
+
+use crate::engine::{
+    attacks::magics::{BISHOP_TABLE, ROOK_TABLE},
+    chess_moves::configurations::{KING_DIRECTIONS, KNIGHT_DIRECTIONS},
+    directions::{self, squares::Square, DirectionFn},
+    piece::{Color, Piece, Typ},
+    position::bitboard::Bitboard,
+};
+
+static KNIGHT_ATTACKS: Lazy<[Bitboard; 64]> = Lazy::new(|| leaper_table(&KNIGHT_DIRECTIONS));
+static KING_ATTACKS: Lazy<[Bitboard; 64]> = Lazy::new(|| leaper_table(&KING_DIRECTIONS));
+static WHITE_PAWN_ATTACKS: Lazy<[Bitboard; 64]> =
+    Lazy::new(|| leaper_table(&[directions::up_left, directions::up_right]));
+static BLACK_PAWN_ATTACKS: Lazy<[Bitboard; 64]> =
+    Lazy::new(|| leaper_table(&[directions::down_left, directions::down_right]));
+
+/// Squares attacked by `piece` standing on `square`. Sliding attacks stop at the first
+/// occupied square of each ray and include it, whichever color the blocker has.
+pub fn attacks_from(piece: Piece, square: Square, occupancy: Bitboard) -> Bitboard {
+    match piece.get_type() {
+        Typ::King => king_attacks(square),
+        Typ::Queen => queen_attacks(square, occupancy),
+        Typ::Rook => rook_attacks(square, occupancy),
+        Typ::Bishop => bishop_attacks(square, occupancy),
+        Typ::Knight => knight_attacks(square),
+        Typ::Pawn => pawn_attacks(piece.get_color(), square),
+    }
+}
+
+pub fn knight_attacks(square: Square) -> Bitboard {
+    KNIGHT_ATTACKS[square.as_index() as usize]
+}
+
+pub fn king_attacks(square: Square) -> Bitboard {
+    KING_ATTACKS[square.as_index() as usize]
+}
+
+/// The diagonal captures of a pawn of the given color, without pushes or en passant.
+pub fn pawn_attacks(color: Color, square: Square) -> Bitboard {
+    match color {
+        Color::White => WHITE_PAWN_ATTACKS[square.as_index() as usize],
+        Color::Black => BLACK_PAWN_ATTACKS[square.as_index() as usize],
+    }
+}
+
+pub fn rook_attacks(square: Square, occupancy: Bitboard) -> Bitboard {
+    ROOK_TABLE.get(square, occupancy)
+}
+
+pub fn bishop_attacks(square: Square, occupancy: Bitboard) -> Bitboard {
+    BISHOP_TABLE.get(square, occupancy)
+}
+
+pub fn queen_attacks(square: Square, occupancy: Bitboard) -> Bitboard {
+    rook_attacks(square, occupancy) | bishop_attacks(square, occupancy)
+}
+
+fn leaper_table(directions: &[DirectionFn]) -> [Bitboard; 64] {
+    std::array::from_fn(|index| {
+        let square = Square::new_unchecked(index as u32);
+        Bitboard::from_vec(
+            directions
+                .iter()
+                .filter_map(|direction| direction(square))
+                .collect(),
+        )
+    })
+}
+
+mod magics;
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,102 @@
+use crate::engine::{
+    attacks::{
+        attacks_from, bishop_attacks, king_attacks, knight_attacks, magics::slide, pawn_attacks,
+        queen_attacks, rook_attacks,
+    },
+    chess_moves::configurations::{BISHOP_DIRECTIONS, ROOK_DIRECTIONS},
+    directions::squares::*,
+    piece::{Color, Piece},
+    position::bitboard::Bitboard,
+};
+
+#[test]
+fn test_knight_attacks() {
+    assert_eq!(knight_attacks(A1), Bitboard::from_vec(vec![B3, C2]));
+    assert_eq!(knight_attacks(D4).count_ones(), 8);
+    assert_eq!(knight_attacks(H8), Bitboard::from_vec(vec![F7, G6]));
+}
+
+#[test]
+fn test_king_attacks() {
+    assert_eq!(king_attacks(A1), Bitboard::from_vec(vec![A2, B1, B2]));
+    assert_eq!(king_attacks(E4).count_ones(), 8);
+}
+
+#[test]
+fn test_pawn_attacks() {
+    assert_eq!(
+        pawn_attacks(Color::White, E4),
+        Bitboard::from_vec(vec![D5, F5])
+    );
+    assert_eq!(
+        pawn_attacks(Color::Black, E4),
+        Bitboard::from_vec(vec![D3, F3])
+    );
+    assert_eq!(pawn_attacks(Color::White, A2), Bitboard::from(B3));
+    assert_eq!(pawn_attacks(Color::Black, H7), Bitboard::from(G6));
+    assert!(pawn_attacks(Color::White, C8).is_empty());
+}
+
+#[test]
+fn test_rook_attacks_stop_at_blockers() {
+    let occupancy = Bitboard::from_vec(vec![D6, F4, D1]);
+    assert_eq!(
+        rook_attacks(D4, occupancy),
+        Bitboard::from_vec(vec![D5, D6, E4, F4, D3, D2, D1, C4, B4, A4])
+    );
+}
+
+#[test]
+fn test_bishop_attacks_stop_at_blockers() {
+    let occupancy = Bitboard::from_vec(vec![B2, F6]);
+    assert_eq!(
+        bishop_attacks(D4, occupancy),
+        Bitboard::from_vec(vec![C3, B2, E5, F6, C5, B6, A7, E3, F2, G1])
+    );
+}
+
+#[test]
+fn test_queen_attacks_on_empty_board() {
+    assert_eq!(queen_attacks(D4, Bitboard::default()).count_ones(), 27);
+    assert_eq!(queen_attacks(A1, Bitboard::default()).count_ones(), 21);
+}
+
+#[test]
+fn test_attacks_from() {
+    let occupancy = Bitboard::from_vec(vec![E2, E7]);
+    assert_eq!(
+        attacks_from(Piece::BlackRook, E4, occupancy),
+        rook_attacks(E4, occupancy)
+    );
+    assert_eq!(
+        attacks_from(Piece::WhitePawn, E4, occupancy),
+        pawn_attacks(Color::White, E4)
+    );
+    assert_eq!(
+        attacks_from(Piece::BlackKnight, E4, occupancy),
+        knight_attacks(E4)
+    );
+}
+
+#[test]
+fn test_magic_lookup_matches_ray_walk() {
+    // A fixed xorshift sequence gives varied occupancies with a reproducible failure
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    for _ in 0..200 {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        let occupancy = Bitboard::from_inner(state & (state >> 3));
+        for index in 0..64 {
+            let square = Square::new_unchecked(index);
+            assert_eq!(
+                rook_attacks(square, occupancy),
+                slide(square, occupancy, &ROOK_DIRECTIONS)
+            );
+            assert_eq!(
+                bishop_attacks(square, occupancy),
+                slide(square, occupancy, &BISHOP_DIRECTIONS)
+            );
+        }
+    }
+}
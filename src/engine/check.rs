@@ -1,12 +1,8 @@
 use crate::engine::{
-    chess_moves::{
-        configurations::{
-            DIAGONAL_DIRECTIONS, HORIZONTAL_VERTICAL_DIRECTIONS, KING_DIRECTIONS, KNIGHT_DIRECTIONS,
-        },
-        ChessMove,
-    },
-    directions::{self, squares::Square},
-    piece::{Color, Piece},
+    attacks::{bishop_attacks, king_attacks, knight_attacks, pawn_attacks, rook_attacks},
+    chess_moves::ChessMove,
+    directions::squares::Square,
+    piece::{Color, Piece, Typ},
     position::Position,
 };
 /// Check if the king of the given color is in check
@@ -15,137 +11,36 @@ pub fn is_check(position: &Position, color: Color) -> bool {
     is_under_attack(position, king_square, color)
 }
 
-pub fn filter_checks(chess_moves: Vec<ChessMove>, color: Color) -> Vec<ChessMove> {
+/// Keeps the moves after which the player to move in `position` is not in check.
+pub fn filter_checks(position: &Position, chess_moves: Vec<ChessMove>) -> Vec<ChessMove> {
+    let color = position.get_player();
+    let mut scratch = *position;
     chess_moves
         .into_iter()
-        .filter(|chess_move| !is_check(&chess_move.position, color))
+        .filter(|chess_move| {
+            let undo = scratch.make_move(*chess_move);
+            let is_legal = !is_check(&scratch, color);
+            scratch.unmake_move(*chess_move, undo);
+            is_legal
+        })
         .collect()
 }
 
 /// Check if a square is under attack by the opponent
 pub fn is_under_attack(position: &Position, square: Square, color: Color) -> bool {
     let opponent = color.get_opponent_color();
-
-    // Check horizontal/vertical attacks (rook/queen)
-    for direction in HORIZONTAL_VERTICAL_DIRECTIONS {
-        let mut current_square = square;
-
-        // Move along the direction until we hit a piece or board edge
-        while let Some(next_square) = direction(current_square) {
-            current_square = next_square;
-
-            if position.is_occupied(current_square) {
-                // Check if it's an opponent rook or queen
-                if position.is_occupied_by_piece(
-                    current_square,
-                    match opponent {
-                        Color::Black => Piece::BlackRook,
-                        Color::White => Piece::WhiteRook,
-                    },
-                ) || position.is_occupied_by_piece(
-                    current_square,
-                    match opponent {
-                        Color::Black => Piece::BlackQueen,
-                        Color::White => Piece::WhiteQueen,
-                    },
-                ) {
-                    return true;
-                }
-                // Blocked by another piece
-                break;
-            }
-        }
-    }
-
-    // Check diagonal attacks (bishop/queen)
-    for direction in DIAGONAL_DIRECTIONS {
-        let mut current_square = square;
-
-        // Move along the direction until we hit a piece or board edge
-        while let Some(next_square) = direction(current_square) {
-            current_square = next_square;
-
-            if position.is_occupied(current_square) {
-                // Check if it's an opponent bishop or queen
-                if position.is_occupied_by_piece(
-                    current_square,
-                    match opponent {
-                        Color::Black => Piece::BlackBishop,
-                        Color::White => Piece::WhiteBishop,
-                    },
-                ) || position.is_occupied_by_piece(
-                    current_square,
-                    match opponent {
-                        Color::Black => Piece::BlackQueen,
-                        Color::White => Piece::WhiteQueen,
-                    },
-                ) {
-                    return true;
-                }
-                // Blocked by another piece
-                break;
-            }
-        }
-    }
-
-    // Check knight attacks
-    for knight_direction in KNIGHT_DIRECTIONS {
-        if let Some(knight_square) = knight_direction(square) {
-            if position.is_occupied_by_piece(
-                knight_square,
-                match opponent {
-                    Color::Black => Piece::BlackKnight,
-                    Color::White => Piece::WhiteKnight,
-                },
-            ) {
-                return true;
-            }
-        }
-    }
-
-    // Check king attacks
-    for king_direction in KING_DIRECTIONS {
-        if let Some(king_square) = king_direction(square) {
-            if position.is_occupied_by_piece(
-                king_square,
-                match opponent {
-                    Color::White => Piece::WhiteKing,
-                    Color::Black => Piece::BlackKing,
-                },
-            ) {
-                return true;
-            }
-        }
-    }
-
-    // Check pawn attacks
-    if opponent == Color::White {
-        // Check for white pawns that could attack this square
-        if let Some(pawn_square) = directions::down_left(square) {
-            if position.is_occupied_by_piece(pawn_square, Piece::WhitePawn) {
-                return true;
-            }
-        }
-        if let Some(pawn_square) = directions::down_right(square) {
-            if position.is_occupied_by_piece(pawn_square, Piece::WhitePawn) {
-                return true;
-            }
-        }
-    } else {
-        // Check for black pawns that could attack this square
-        if let Some(pawn_square) = directions::up_left(square) {
-            if position.is_occupied_by_piece(pawn_square, Piece::BlackPawn) {
-                return true;
-            }
-        }
-        if let Some(pawn_square) = directions::up_right(square) {
-            if position.is_occupied_by_piece(pawn_square, Piece::BlackPawn) {
-                return true;
-            }
-        }
-    }
-
-    false
+    let occupancy = position.get_all();
+    let pieces = |typ: Typ| position.get_squares(Piece::new(opponent, typ));
+
+    // Attacks are symmetric: a piece on the square reaches exactly the squares from
+    // which the same piece attacks it, except for pawns, which look the other way.
+    let rooks_and_queens = pieces(Typ::Rook) | pieces(Typ::Queen);
+    let bishops_and_queens = pieces(Typ::Bishop) | pieces(Typ::Queen);
+    !(rook_attacks(square, occupancy) & rooks_and_queens).is_empty()
+        || !(bishop_attacks(square, occupancy) & bishops_and_queens).is_empty()
+        || !(knight_attacks(square) & pieces(Typ::Knight)).is_empty()
+        || !(king_attacks(square) & pieces(Typ::King)).is_empty()
+        || !(pawn_attacks(color, square) & pieces(Typ::Pawn)).is_empty()
 }
 
 #[cfg(test)]
@@ -1,14 +1,18 @@
+use std::fmt;
+
 use crate::engine::check::filter_checks;
 use crate::engine::chess_moves::configurations::*;
 use crate::engine::chess_moves::configurations::{MovesFn, BLACK_MOVE_CONFIG, WHITE_MOVE_CONFIG};
 use crate::engine::directions::squares::Square;
-use crate::engine::piece::{Color, Piece};
-use crate::engine::position::{CastlingType, Position};
+use crate::engine::piece::{Color, Piece, PIECES};
+use crate::engine::position::{CastlingType, Position, CASTLING_TYPES};
 
 pub(crate) mod castling;
 mod common;
 pub(crate) mod configurations;
+pub mod notation;
 pub mod pawn;
+pub mod perft;
 
 pub fn get_current_player_moves(position: &Position) -> Vec<ChessMove> {
     match position.get_player() {
@@ -17,23 +21,15 @@ pub fn get_current_player_moves(position: &Position) -> Vec<ChessMove> {
     }
 }
 
-pub fn get_black_mobility(position: &Position) -> Vec<ChessMove> {
-    get_moves(position, BLACK_MOVE_CONFIG, true)
-}
-
-pub fn get_white_mobility(position: &Position) -> Vec<ChessMove> {
-    get_moves(position, WHITE_MOVE_CONFIG, true)
-}
-
 pub fn get_white_moves(position: &Position) -> Vec<ChessMove> {
-    get_moves(position, WHITE_MOVE_CONFIG, false)
+    get_moves(position, WHITE_MOVE_CONFIG)
 }
 
 pub fn get_black_moves(position: &Position) -> Vec<ChessMove> {
-    get_moves(position, BLACK_MOVE_CONFIG, false)
+    get_moves(position, BLACK_MOVE_CONFIG)
 }
 
-fn get_moves(position: &Position, config: Config, ignore_checks: bool) -> Vec<ChessMove> {
+fn get_moves(position: &Position, config: Config) -> Vec<ChessMove> {
     let mut new_chess_moves: Vec<ChessMove> = Vec::new();
     new_chess_moves.extend(get_new_positions(position, config.bishop, config.bishop_fn));
     new_chess_moves.extend(get_new_positions(position, config.king, config.king_fn));
@@ -42,13 +38,9 @@ fn get_moves(position: &Position, config: Config, ignore_checks: bool) -> Vec<Ch
     new_chess_moves.extend(get_new_positions(position, config.knight, config.knight_fn));
     new_chess_moves.extend(get_new_positions(position, config.pawn, config.pawn_fn));
     new_chess_moves.extend((config.castling_move_fn)(position));
-
-    if ignore_checks {
-        new_chess_moves
-    } else {
-        filter_checks(new_chess_moves, position.get_player())
-    }
+    filter_checks(position, new_chess_moves)
 }
+
 fn get_new_positions(position: &Position, piece: Piece, get_moves_fn: MovesFn) -> Vec<ChessMove> {
     let mut new_chess_moves: Vec<ChessMove> = Vec::new();
     for square in position.get_squares(piece).iter() {
@@ -56,15 +48,97 @@ fn get_new_positions(position: &Position, piece: Piece, get_moves_fn: MovesFn) -
     }
     new_chess_moves
 }
-#[derive(Clone, Copy, Eq, Hash, PartialEq, Debug)]
-pub struct ChessMove {
-    pub position: Position,
-    pub move_type: MoveType,
-    pub piece: Piece,
-    pub from: Square,
-    pub to: Square,
-    pub capture: Option<Piece>,
-    pub pormotion: Option<Piece>,
+const SQUARE_BITS: u32 = 0x3f;
+const PIECE_BITS: u32 = 0xf;
+// Stands for "no piece" in the capture and promotion fields.
+const NO_PIECE: u32 = 0xf;
+const TO_SHIFT: u32 = 6;
+const PIECE_SHIFT: u32 = 12;
+const CAPTURE_SHIFT: u32 = 16;
+const PROMOTION_SHIFT: u32 = 20;
+const MOVE_TYPE_SHIFT: u32 = 24;
+
+/// A move packed into 28 bits: from and to square, the moving, captured and promoted
+/// piece and the move type. It does not contain the position after the move, use
+/// `Position::make_move` or `Position::apply` to get there.
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub struct ChessMove(u32);
+
+impl ChessMove {
+    pub fn new(
+        move_type: MoveType,
+        piece: Piece,
+        from: Square,
+        to: Square,
+        capture: Option<Piece>,
+        promotion: Option<Piece>,
+    ) -> ChessMove {
+        let piece_code = |piece: Option<Piece>| piece.map_or(NO_PIECE, |piece| piece as u32);
+        ChessMove(
+            from.as_index()
+                | to.as_index() << TO_SHIFT
+                | (piece as u32) << PIECE_SHIFT
+                | piece_code(capture) << CAPTURE_SHIFT
+                | piece_code(promotion) << PROMOTION_SHIFT
+                | move_type.as_code() << MOVE_TYPE_SHIFT,
+        )
+    }
+
+    pub fn get_from(self) -> Square {
+        Square::new_unchecked(self.0 & SQUARE_BITS)
+    }
+
+    pub fn get_to(self) -> Square {
+        Square::new_unchecked(self.0 >> TO_SHIFT & SQUARE_BITS)
+    }
+
+    pub fn get_piece(self) -> Piece {
+        PIECES[(self.0 >> PIECE_SHIFT & PIECE_BITS) as usize]
+    }
+
+    /// The captured piece, for en passant the pawn beside the to square.
+    pub fn get_capture(self) -> Option<Piece> {
+        self.get_optional_piece(CAPTURE_SHIFT)
+    }
+
+    pub fn get_promotion(self) -> Option<Piece> {
+        self.get_optional_piece(PROMOTION_SHIFT)
+    }
+
+    pub fn get_move_type(self) -> MoveType {
+        MoveType::from_code(self.0 >> MOVE_TYPE_SHIFT)
+    }
+
+    /// The packed move, for tables that store moves as plain bits. Never zero, as no move
+    /// goes from a square to itself.
+    pub(crate) fn to_bits(self) -> u32 {
+        self.0
+    }
+
+    /// Unpacks bits returned by `to_bits`.
+    pub(crate) fn from_bits(bits: u32) -> ChessMove {
+        ChessMove(bits)
+    }
+
+    fn get_optional_piece(self, shift: u32) -> Option<Piece> {
+        match self.0 >> shift & PIECE_BITS {
+            NO_PIECE => None,
+            code => Some(PIECES[code as usize]),
+        }
+    }
+}
+
+impl fmt::Debug for ChessMove {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChessMove")
+            .field("move_type", &self.get_move_type())
+            .field("piece", &self.get_piece())
+            .field("from", &self.get_from())
+            .field("to", &self.get_to())
+            .field("capture", &self.get_capture())
+            .field("promotion", &self.get_promotion())
+            .finish()
+    }
 }
 
 #[derive(Clone, Copy, Eq, Hash, PartialEq, Debug)]
@@ -77,5 +151,31 @@ pub enum MoveType {
     Castling { castling_type: CastlingType },
 }
 
+impl MoveType {
+    fn as_code(self) -> u32 {
+        match self {
+            MoveType::Quiet => 0,
+            MoveType::Capture => 1,
+            MoveType::Promotion => 2,
+            MoveType::PromotionCapture => 3,
+            MoveType::EnPassant => 4,
+            MoveType::Castling { castling_type } => 5 + castling_type.as_index() as u32,
+        }
+    }
+
+    fn from_code(code: u32) -> MoveType {
+        match code {
+            0 => MoveType::Quiet,
+            1 => MoveType::Capture,
+            2 => MoveType::Promotion,
+            3 => MoveType::PromotionCapture,
+            4 => MoveType::EnPassant,
+            _ => MoveType::Castling {
+                castling_type: CASTLING_TYPES[code as usize - 5],
+            },
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests;
@@ -49,7 +49,8 @@ pub fn get_castling_move(
         return None;
     }
 
-    if !is_save_passage(position, castling.empty_path_squares, castling.color) {
+    // On the queenside the rook passes b1/b8, which may be attacked.
+    if !is_save_passage(position, castling.king_path_squares, castling.color) {
         return None;
     }
 
@@ -57,44 +58,42 @@ pub fn get_castling_move(
         return None;
     }
 
-    let new_position = position
-        .remove_piece(castling.king_from)
-        .remove_piece(castling.rook_from)
-        .put_piece(castling.king, castling.king_to)
-        .put_piece(castling.rook, castling.rook_to)
-        .toggle_player()
-        .reset_en_passant()
-        .disallow_castling_for_color(castling.color);
+    // The king path includes the to square, so the king cannot end up in check.
+    Some(ChessMove::new(
+        MoveType::Castling {
+            castling_type: castling.castling_type,
+        },
+        castling.king,
+        castling.king_from,
+        castling.king_to,
+        None,
+        None,
+    ))
+}
 
-    if !is_check(&new_position, castling.color) {
-        let chess_move: ChessMove = ChessMove {
-            move_type: MoveType::Castling {
-                castling_type: castling.castling_type,
-            },
-            piece: castling.king,
-            from: castling.king_from,
-            to: castling.king_to,
-            capture: None,
-            pormotion: None,
-            position: new_position,
-        };
-        return Some(chess_move);
+pub fn get_castling_configuration(castling_type: CastlingType) -> CastlingConfiguration {
+    match castling_type {
+        CastlingType::WhiteKingside => WHITE_KINGSIDE,
+        CastlingType::WhiteQueenside => WHITE_QUEENSIDE,
+        CastlingType::BlackKingside => BLACK_KINGSIDE,
+        CastlingType::BlackQueenside => BLACK_QUEENSIDE,
     }
-    None
 }
 
-pub fn remove_castling_rights_if_necessary(position: Position, from: Square) -> Position {
+/// Removes the castling rights bound to `square`. Called with the from square of
+/// every move and with the to square of captures, so a captured rook loses its rights too.
+pub fn remove_castling_rights_if_necessary(position: Position, square: Square) -> Position {
     let mut new_position = position;
-    if from == WHITE_QUEENSIDE.king_from || from == WHITE_QUEENSIDE.rook_from {
+    if square == WHITE_QUEENSIDE.king_from || square == WHITE_QUEENSIDE.rook_from {
         new_position = new_position.remove_castling_right(CastlingType::WhiteQueenside);
     }
-    if from == WHITE_KINGSIDE.king_from || from == WHITE_KINGSIDE.rook_from {
+    if square == WHITE_KINGSIDE.king_from || square == WHITE_KINGSIDE.rook_from {
         new_position = new_position.remove_castling_right(CastlingType::WhiteKingside);
     }
-    if from == BLACK_QUEENSIDE.king_from || from == BLACK_QUEENSIDE.rook_from {
+    if square == BLACK_QUEENSIDE.king_from || square == BLACK_QUEENSIDE.rook_from {
         new_position = new_position.remove_castling_right(CastlingType::BlackQueenside);
     }
-    if from == BLACK_KINGSIDE.king_from || from == BLACK_KINGSIDE.rook_from {
+    if square == BLACK_KINGSIDE.king_from || square == BLACK_KINGSIDE.rook_from {
         new_position = new_position.remove_castling_right(CastlingType::BlackKingside);
     }
     new_position
@@ -118,6 +117,6 @@ fn is_empty_path(position: &Position, sqares: &[Square]) -> bool {
     true
 }
 
-mod configurations;
+pub(crate) mod configurations;
 #[cfg(test)]
 mod tests;
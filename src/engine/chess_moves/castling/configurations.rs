@@ -12,6 +12,7 @@ pub struct CastlingConfiguration {
     pub(crate) rook_from: Square,
     pub(crate) rook_to: Square,
     pub(crate) empty_path_squares: &'static [Square],
+    pub(crate) king_path_squares: &'static [Square],
     pub(crate) castling_type: CastlingType,
 }
 
@@ -23,6 +24,7 @@ pub const WHITE_KINGSIDE: CastlingConfiguration = CastlingConfiguration {
     rook_from: H1,
     rook_to: F1,
     empty_path_squares: &[F1, G1],
+    king_path_squares: &[F1, G1],
     king: Piece::WhiteKing,
     rook: Piece::WhiteRook,
     castling_type: CastlingType::WhiteKingside,
@@ -36,6 +38,7 @@ pub const WHITE_QUEENSIDE: CastlingConfiguration = CastlingConfiguration {
     rook_from: A1,
     rook_to: D1,
     empty_path_squares: &[B1, C1, D1],
+    king_path_squares: &[C1, D1],
     king: Piece::WhiteKing,
     rook: Piece::WhiteRook,
     castling_type: CastlingType::WhiteQueenside,
@@ -49,6 +52,7 @@ pub const BLACK_KINGSIDE: CastlingConfiguration = CastlingConfiguration {
     rook_from: H8,
     rook_to: F8,
     empty_path_squares: &[F8, G8],
+    king_path_squares: &[F8, G8],
     king: Piece::BlackKing,
     rook: Piece::BlackRook,
     castling_type: CastlingType::BlackKingside,
@@ -62,6 +66,7 @@ pub const BLACK_QUEENSIDE: CastlingConfiguration = CastlingConfiguration {
     rook_from: A8,
     rook_to: D8,
     empty_path_squares: &[B8, C8, D8],
+    king_path_squares: &[C8, D8],
     king: Piece::BlackKing,
     rook: Piece::BlackRook,
     castling_type: CastlingType::BlackQueenside,
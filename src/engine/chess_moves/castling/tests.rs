@@ -20,8 +20,12 @@ fn test_white_kingside_castling_allowed() {
 
     // Should have normal king moves plus castling
     let castling_move = moves.iter().find(|c| {
-        c.position.is_occupied_by_piece(G1, Piece::WhiteKing)
-            && c.position.is_occupied_by_piece(F1, Piece::WhiteRook)
+        position
+            .apply(**c)
+            .is_occupied_by_piece(G1, Piece::WhiteKing)
+            && position
+                .apply(**c)
+                .is_occupied_by_piece(F1, Piece::WhiteRook)
     });
     assert!(
         castling_move.is_some(),
@@ -38,8 +42,12 @@ fn test_white_queenside_castling_allowed() {
     let moves = get_white_castling_moves(&position);
 
     let castling_move = moves.iter().find(|c| {
-        c.position.is_occupied_by_piece(C1, Piece::WhiteKing)
-            && c.position.is_occupied_by_piece(D1, Piece::WhiteRook)
+        position
+            .apply(**c)
+            .is_occupied_by_piece(C1, Piece::WhiteKing)
+            && position
+                .apply(**c)
+                .is_occupied_by_piece(D1, Piece::WhiteRook)
     });
     assert!(
         castling_move.is_some(),
@@ -57,8 +65,12 @@ fn test_white_castling_blocked_by_pieces() {
 
     let moves = get_white_castling_moves(&position);
     let castling_move = moves.iter().find(|c| {
-        c.position.is_occupied_by_piece(G1, Piece::WhiteKing)
-            && c.position.is_occupied_by_piece(F1, Piece::WhiteRook)
+        position
+            .apply(**c)
+            .is_occupied_by_piece(G1, Piece::WhiteKing)
+            && position
+                .apply(**c)
+                .is_occupied_by_piece(F1, Piece::WhiteRook)
     });
     assert!(
         castling_move.is_none(),
@@ -73,8 +85,12 @@ fn test_white_castling_blocked_by_pieces() {
 
     let moves = get_white_castling_moves(&position);
     let castling_move = moves.iter().find(|c| {
-        c.position.is_occupied_by_piece(C1, Piece::WhiteKing)
-            && c.position.is_occupied_by_piece(D1, Piece::WhiteRook)
+        position
+            .apply(**c)
+            .is_occupied_by_piece(C1, Piece::WhiteKing)
+            && position
+                .apply(**c)
+                .is_occupied_by_piece(D1, Piece::WhiteRook)
     });
     assert!(
         castling_move.is_none(),
@@ -91,8 +107,12 @@ fn test_white_castling_not_allowed_when_in_check() {
 
     let moves = get_white_castling_moves(&position);
     let castling_move = moves.iter().find(|c| {
-        c.position.is_occupied_by_piece(G1, Piece::WhiteKing)
-            && c.position.is_occupied_by_piece(F1, Piece::WhiteRook)
+        position
+            .apply(**c)
+            .is_occupied_by_piece(G1, Piece::WhiteKing)
+            && position
+                .apply(**c)
+                .is_occupied_by_piece(F1, Piece::WhiteRook)
     });
     assert!(
         castling_move.is_none(),
@@ -110,8 +130,12 @@ fn test_white_castling_not_allowed_through_check() {
 
     let moves = get_white_castling_moves(&position);
     let castling_move = moves.iter().find(|c| {
-        c.position.is_occupied_by_piece(G1, Piece::WhiteKing)
-            && c.position.is_occupied_by_piece(F1, Piece::WhiteRook)
+        position
+            .apply(**c)
+            .is_occupied_by_piece(G1, Piece::WhiteKing)
+            && position
+                .apply(**c)
+                .is_occupied_by_piece(F1, Piece::WhiteRook)
     });
     assert!(
         castling_move.is_none(),
@@ -126,8 +150,12 @@ fn test_white_castling_not_allowed_through_check() {
 
     let moves = get_white_castling_moves(&position);
     let castling_move = moves.iter().find(|c| {
-        c.position.is_occupied_by_piece(C1, Piece::WhiteKing)
-            && c.position.is_occupied_by_piece(D1, Piece::WhiteRook)
+        position
+            .apply(**c)
+            .is_occupied_by_piece(C1, Piece::WhiteKing)
+            && position
+                .apply(**c)
+                .is_occupied_by_piece(D1, Piece::WhiteRook)
     });
     assert!(
         castling_move.is_none(),
@@ -146,8 +174,12 @@ fn test_black_kingside_castling_allowed() {
     let moves = get_black_castling_moves(&position);
 
     let castling_move = moves.iter().find(|c| {
-        c.position.is_occupied_by_piece(G8, Piece::BlackKing)
-            && c.position.is_occupied_by_piece(F8, Piece::BlackRook)
+        position
+            .apply(**c)
+            .is_occupied_by_piece(G8, Piece::BlackKing)
+            && position
+                .apply(**c)
+                .is_occupied_by_piece(F8, Piece::BlackRook)
     });
     assert!(
         castling_move.is_some(),
@@ -163,8 +195,12 @@ fn test_black_queenside_castling_allowed() {
     let moves = get_black_castling_moves(&position);
 
     let castling_move = moves.iter().find(|c| {
-        c.position.is_occupied_by_piece(C8, Piece::BlackKing)
-            && c.position.is_occupied_by_piece(D8, Piece::BlackRook)
+        position
+            .apply(**c)
+            .is_occupied_by_piece(C8, Piece::BlackKing)
+            && position
+                .apply(**c)
+                .is_occupied_by_piece(D8, Piece::BlackRook)
     });
     assert!(
         castling_move.is_some(),
@@ -182,8 +218,12 @@ fn test_black_castling_blocked_by_pieces() {
 
     let moves = get_black_castling_moves(&position);
     let castling_move = moves.iter().find(|c| {
-        c.position.is_occupied_by_piece(G8, Piece::BlackKing)
-            && c.position.is_occupied_by_piece(F8, Piece::BlackRook)
+        position
+            .apply(**c)
+            .is_occupied_by_piece(G8, Piece::BlackKing)
+            && position
+                .apply(**c)
+                .is_occupied_by_piece(F8, Piece::BlackRook)
     });
     assert!(
         castling_move.is_none(),
@@ -198,8 +238,12 @@ fn test_black_castling_blocked_by_pieces() {
 
     let moves = get_black_castling_moves(&position);
     let castling_move = moves.iter().find(|c| {
-        c.position.is_occupied_by_piece(C8, Piece::BlackKing)
-            && c.position.is_occupied_by_piece(D8, Piece::BlackRook)
+        position
+            .apply(**c)
+            .is_occupied_by_piece(C8, Piece::BlackKing)
+            && position
+                .apply(**c)
+                .is_occupied_by_piece(D8, Piece::BlackRook)
     });
     assert!(
         castling_move.is_none(),
@@ -216,8 +260,12 @@ fn test_black_castling_not_allowed_when_in_check() {
 
     let moves = get_black_castling_moves(&position);
     let castling_move = moves.iter().find(|c| {
-        c.position.is_occupied_by_piece(G8, Piece::BlackKing)
-            && c.position.is_occupied_by_piece(F8, Piece::BlackRook)
+        position
+            .apply(**c)
+            .is_occupied_by_piece(G8, Piece::BlackKing)
+            && position
+                .apply(**c)
+                .is_occupied_by_piece(F8, Piece::BlackRook)
     });
     assert!(
         castling_move.is_none(),
@@ -235,8 +283,12 @@ fn test_black_castling_not_allowed_through_check() {
 
     let moves = get_black_castling_moves(&position);
     let castling_move = moves.iter().find(|c| {
-        c.position.is_occupied_by_piece(G8, Piece::BlackKing)
-            && c.position.is_occupied_by_piece(F8, Piece::BlackRook)
+        position
+            .apply(**c)
+            .is_occupied_by_piece(G8, Piece::BlackKing)
+            && position
+                .apply(**c)
+                .is_occupied_by_piece(F8, Piece::BlackRook)
     });
     assert!(
         castling_move.is_none(),
@@ -251,8 +303,12 @@ fn test_black_castling_not_allowed_through_check() {
 
     let moves = get_black_castling_moves(&position);
     let castling_move = moves.iter().find(|c| {
-        c.position.is_occupied_by_piece(C8, Piece::BlackKing)
-            && c.position.is_occupied_by_piece(D8, Piece::BlackRook)
+        position
+            .apply(**c)
+            .is_occupied_by_piece(C8, Piece::BlackKing)
+            && position
+                .apply(**c)
+                .is_occupied_by_piece(D8, Piece::BlackRook)
     });
     assert!(
         castling_move.is_none(),
@@ -272,12 +328,20 @@ fn test_castling_rights_false() {
 
     let moves = get_white_castling_moves(&position);
     let kingside_castling = moves.iter().find(|c| {
-        c.position.is_occupied_by_piece(G1, Piece::WhiteKing)
-            && c.position.is_occupied_by_piece(F1, Piece::WhiteRook)
+        position
+            .apply(**c)
+            .is_occupied_by_piece(G1, Piece::WhiteKing)
+            && position
+                .apply(**c)
+                .is_occupied_by_piece(F1, Piece::WhiteRook)
     });
     let queenside_castling = moves.iter().find(|c| {
-        c.position.is_occupied_by_piece(C1, Piece::WhiteKing)
-            && c.position.is_occupied_by_piece(D1, Piece::WhiteRook)
+        position
+            .apply(**c)
+            .is_occupied_by_piece(C1, Piece::WhiteKing)
+            && position
+                .apply(**c)
+                .is_occupied_by_piece(D1, Piece::WhiteRook)
     });
 
     assert!(
@@ -300,12 +364,20 @@ fn test_both_castlings_available() {
     let moves = get_white_castling_moves(&position);
 
     let kingside_castling = moves.iter().find(|c| {
-        c.position.is_occupied_by_piece(G1, Piece::WhiteKing)
-            && c.position.is_occupied_by_piece(F1, Piece::WhiteRook)
+        position
+            .apply(**c)
+            .is_occupied_by_piece(G1, Piece::WhiteKing)
+            && position
+                .apply(**c)
+                .is_occupied_by_piece(F1, Piece::WhiteRook)
     });
     let queenside_castling = moves.iter().find(|c| {
-        c.position.is_occupied_by_piece(C1, Piece::WhiteKing)
-            && c.position.is_occupied_by_piece(D1, Piece::WhiteRook)
+        position
+            .apply(**c)
+            .is_occupied_by_piece(C1, Piece::WhiteKing)
+            && position
+                .apply(**c)
+                .is_occupied_by_piece(D1, Piece::WhiteRook)
     });
 
     assert!(
@@ -325,13 +397,11 @@ fn test_white_kingside_castling() {
         .put_piece(Piece::WhiteRook, H1);
     let c = get_castling_move(&position, WHITE_KINGSIDE);
     assert!(c.is_some());
-    assert!(c
-        .unwrap()
-        .position
+    assert!(position
+        .apply(c.unwrap())
         .is_occupied_by_piece(G1, Piece::WhiteKing));
-    assert!(c
-        .unwrap()
-        .position
+    assert!(position
+        .apply(c.unwrap())
         .is_occupied_by_piece(F1, Piece::WhiteRook));
 }
 
@@ -342,13 +412,11 @@ fn test_white_queenside_castling() {
         .put_piece(Piece::WhiteRook, A1);
     let c = get_castling_move(&position, WHITE_QUEENSIDE);
     assert!(c.is_some());
-    assert!(c
-        .unwrap()
-        .position
+    assert!(position
+        .apply(c.unwrap())
         .is_occupied_by_piece(C1, Piece::WhiteKing));
-    assert!(c
-        .unwrap()
-        .position
+    assert!(position
+        .apply(c.unwrap())
         .is_occupied_by_piece(D1, Piece::WhiteRook));
 }
 
@@ -359,13 +427,11 @@ fn test_black_kingside_castling() {
         .put_piece(Piece::BlackRook, H8);
     let c = get_castling_move(&position, BLACK_KINGSIDE);
     assert!(c.is_some());
-    assert!(c
-        .unwrap()
-        .position
+    assert!(position
+        .apply(c.unwrap())
         .is_occupied_by_piece(G8, Piece::BlackKing));
-    assert!(c
-        .unwrap()
-        .position
+    assert!(position
+        .apply(c.unwrap())
         .is_occupied_by_piece(F8, Piece::BlackRook));
 }
 
@@ -376,13 +442,11 @@ fn test_black_queenside_castling() {
         .put_piece(Piece::BlackRook, A8);
     let c = get_castling_move(&position, BLACK_QUEENSIDE);
     assert!(c.is_some());
-    assert!(c
-        .unwrap()
-        .position
+    assert!(position
+        .apply(c.unwrap())
         .is_occupied_by_piece(C8, Piece::BlackKing));
-    assert!(c
-        .unwrap()
-        .position
+    assert!(position
+        .apply(c.unwrap())
         .is_occupied_by_piece(D8, Piece::BlackRook));
 }
 
@@ -399,17 +463,29 @@ fn test_castling_methods_toggle_player() {
     // White to move
 
     let white_kingside = get_castling_move(&position, WHITE_KINGSIDE);
-    assert_eq!(white_kingside.unwrap().position.get_player(), Color::Black);
+    assert_eq!(
+        position.apply(white_kingside.unwrap()).get_player(),
+        Color::Black
+    );
 
     let white_queenside = get_castling_move(&position, WHITE_QUEENSIDE);
-    assert_eq!(white_queenside.unwrap().position.get_player(), Color::Black);
+    assert_eq!(
+        position.apply(white_queenside.unwrap()).get_player(),
+        Color::Black
+    );
 
     let position_black = position.toggle_player(); // Black to move
     let black_kingside = get_castling_move(&position_black, BLACK_KINGSIDE);
-    assert_eq!(black_kingside.unwrap().position.get_player(), Color::White);
+    assert_eq!(
+        position_black.apply(black_kingside.unwrap()).get_player(),
+        Color::White
+    );
 
     let black_queenside = get_castling_move(&position_black, BLACK_QUEENSIDE);
-    assert_eq!(black_queenside.unwrap().position.get_player(), Color::White);
+    assert_eq!(
+        position_black.apply(black_queenside.unwrap()).get_player(),
+        Color::White
+    );
 }
 
 #[test]
@@ -424,16 +500,28 @@ fn test_castling_methods_reset_en_passant() {
         .set_en_passant(E4);
 
     let white_kingside = get_castling_move(&position, WHITE_KINGSIDE);
-    assert_eq!(white_kingside.unwrap().position.get_en_passant(), None);
+    assert_eq!(
+        position.apply(white_kingside.unwrap()).get_en_passant(),
+        None
+    );
 
     let white_queenside = get_castling_move(&position, WHITE_QUEENSIDE);
-    assert_eq!(white_queenside.unwrap().position.get_en_passant(), None);
+    assert_eq!(
+        position.apply(white_queenside.unwrap()).get_en_passant(),
+        None
+    );
 
     let black_kingside = get_castling_move(&position, BLACK_KINGSIDE);
-    assert_eq!(black_kingside.unwrap().position.get_en_passant(), None);
+    assert_eq!(
+        position.apply(black_kingside.unwrap()).get_en_passant(),
+        None
+    );
 
     let black_queenside = get_castling_move(&position, BLACK_QUEENSIDE);
-    assert_eq!(black_queenside.unwrap().position.get_en_passant(), None);
+    assert_eq!(
+        position.apply(black_queenside.unwrap()).get_en_passant(),
+        None
+    );
 }
 
 #[test]
@@ -447,42 +535,34 @@ fn test_castling_methods_disable_castling_rights() {
         .put_piece(Piece::BlackKing, E8);
 
     let white_kingside = get_castling_move(&position, WHITE_KINGSIDE);
-    assert!(!white_kingside
-        .unwrap()
-        .position
+    assert!(!position
+        .apply(white_kingside.unwrap())
         .get_castling_right(CastlingType::WhiteKingside));
-    assert!(!white_kingside
-        .unwrap()
-        .position
+    assert!(!position
+        .apply(white_kingside.unwrap())
         .get_castling_right(CastlingType::WhiteQueenside));
 
     let white_queenside = get_castling_move(&position, WHITE_QUEENSIDE);
-    assert!(!white_queenside
-        .unwrap()
-        .position
+    assert!(!position
+        .apply(white_queenside.unwrap())
         .get_castling_right(CastlingType::WhiteKingside));
-    assert!(!white_queenside
-        .unwrap()
-        .position
+    assert!(!position
+        .apply(white_queenside.unwrap())
         .get_castling_right(CastlingType::WhiteQueenside));
 
     let black_kingside = get_castling_move(&position, BLACK_KINGSIDE);
-    assert!(!black_kingside
-        .unwrap()
-        .position
+    assert!(!position
+        .apply(black_kingside.unwrap())
         .get_castling_right(CastlingType::BlackKingside));
-    assert!(!black_kingside
-        .unwrap()
-        .position
+    assert!(!position
+        .apply(black_kingside.unwrap())
         .get_castling_right(CastlingType::BlackQueenside));
 
     let black_queenside = get_castling_move(&position, BLACK_QUEENSIDE);
-    assert!(!black_queenside
-        .unwrap()
-        .position
+    assert!(!position
+        .apply(black_queenside.unwrap())
         .get_castling_right(CastlingType::BlackKingside));
-    assert!(!black_queenside
-        .unwrap()
-        .position
+    assert!(!position
+        .apply(black_queenside.unwrap())
         .get_castling_right(CastlingType::BlackQueenside));
 }
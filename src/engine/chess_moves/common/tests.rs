@@ -1,14 +1,12 @@
-use std::u32;
-
 use crate::engine::{
     chess_moves::{
         common::{
-            generate_path_with_limit, get_moves_for_king_at_square, get_moves_for_rook_at_square,
-            slide,
+            get_moves_for_king_at_square, get_moves_for_queen_at_square,
+            get_moves_for_rook_at_square,
         },
         ChessMove,
     },
-    directions::{self, squares::*},
+    directions::squares::*,
     piece::*,
     position::Position,
 };
@@ -20,16 +18,19 @@ fn test_slide1() {
         .put_piece(Piece::WhiteQueen, G4)
         .put_piece(Piece::WhitePawn, G7);
 
-    let path = generate_path_with_limit(G4, directions::up, u32::MAX);
-    let positions = slide(&position, G4, path, Piece::WhiteQueen);
-    assert!(positions
-        .iter()
-        .any(|p| p.position.is_occupied_by_piece(G5, Piece::WhiteQueen)
-            && p.position.is_occupied_by_piece(G7, Piece::WhitePawn)));
-    assert!(positions
-        .iter()
-        .any(|p| p.position.is_occupied_by_piece(G6, Piece::WhiteQueen)
-            && p.position.is_occupied_by_piece(G7, Piece::WhitePawn)));
+    let positions = get_moves_for_queen_at_square(&position, Piece::WhiteQueen, G4);
+    assert!(positions.iter().any(|p| position
+        .apply(*p)
+        .is_occupied_by_piece(G5, Piece::WhiteQueen)
+        && position
+            .apply(*p)
+            .is_occupied_by_piece(G7, Piece::WhitePawn)));
+    assert!(positions.iter().any(|p| position
+        .apply(*p)
+        .is_occupied_by_piece(G6, Piece::WhiteQueen)
+        && position
+            .apply(*p)
+            .is_occupied_by_piece(G7, Piece::WhitePawn)));
 }
 
 #[test]
@@ -94,13 +95,10 @@ fn test_get_piece_moves_rook_with_obstacles() {
     assert!(!contains_move(&positions, Piece::WhiteRook, G4)); // Can't go beyond captured piece
 }
 
-fn contains_move(chess_moves: &Vec<ChessMove>, piece: Piece, field: Square) -> bool {
-    for chess_move in chess_moves {
-        if chess_move.position.is_occupied_by_piece(field, piece) {
-            return true;
-        }
-    }
-    false
+fn contains_move(chess_moves: &[ChessMove], piece: Piece, field: Square) -> bool {
+    chess_moves
+        .iter()
+        .any(|chess_move| chess_move.get_piece() == piece && chess_move.get_to() == field)
 }
 
 #[test]
@@ -109,41 +107,43 @@ fn test_slide2() {
     position = position
         .put_piece(Piece::WhiteQueen, G4)
         .put_piece(Piece::BlackPawn, G7);
-    let path = generate_path_with_limit(G4, directions::up, u32::MAX);
-
-    let positions = slide(&position, G4, path, Piece::WhiteQueen);
-
-    assert!(positions
-        .iter()
-        .any(|p| p.position.is_occupied_by_piece(G5, Piece::WhiteQueen)
-            && p.position.is_occupied_by_piece(G7, Piece::BlackPawn)));
-    assert!(positions
-        .iter()
-        .any(|p| p.position.is_occupied_by_piece(G6, Piece::WhiteQueen)
-            && p.position.is_occupied_by_piece(G7, Piece::BlackPawn)));
-    assert!(positions
-        .iter()
-        .any(|p| p.position.is_occupied_by_piece(G7, Piece::WhiteQueen)
-            && !p.position.is_occupied_by_piece(G7, Piece::BlackPawn)));
+    let positions = get_moves_for_queen_at_square(&position, Piece::WhiteQueen, G4);
+
+    assert!(positions.iter().any(|p| position
+        .apply(*p)
+        .is_occupied_by_piece(G5, Piece::WhiteQueen)
+        && position
+            .apply(*p)
+            .is_occupied_by_piece(G7, Piece::BlackPawn)));
+    assert!(positions.iter().any(|p| position
+        .apply(*p)
+        .is_occupied_by_piece(G6, Piece::WhiteQueen)
+        && position
+            .apply(*p)
+            .is_occupied_by_piece(G7, Piece::BlackPawn)));
+    assert!(positions.iter().any(|p| position
+        .apply(*p)
+        .is_occupied_by_piece(G7, Piece::WhiteQueen)
+        && !position
+            .apply(*p)
+            .is_occupied_by_piece(G7, Piece::BlackPawn)));
 }
 
 #[test]
 fn test_slide3() {
     let mut position: Position = Position::default();
     position = position.put_piece(Piece::WhiteQueen, G4);
-    let path = generate_path_with_limit(G4, directions::up, u32::MAX);
-
-    let positions = slide(&position, G4, path, Piece::WhiteQueen);
-    assert!(positions
-        .iter()
-        .any(|p| p.position.is_occupied_by_piece(G5, Piece::WhiteQueen)));
-    assert!(positions
-        .iter()
-        .any(|p| p.position.is_occupied_by_piece(G6, Piece::WhiteQueen)));
-    assert!(positions
-        .iter()
-        .any(|p| p.position.is_occupied_by_piece(G7, Piece::WhiteQueen)));
-    assert!(positions
-        .iter()
-        .any(|p| p.position.is_occupied_by_piece(G8, Piece::WhiteQueen)));
+    let positions = get_moves_for_queen_at_square(&position, Piece::WhiteQueen, G4);
+    assert!(positions.iter().any(|p| position
+        .apply(*p)
+        .is_occupied_by_piece(G5, Piece::WhiteQueen)));
+    assert!(positions.iter().any(|p| position
+        .apply(*p)
+        .is_occupied_by_piece(G6, Piece::WhiteQueen)));
+    assert!(positions.iter().any(|p| position
+        .apply(*p)
+        .is_occupied_by_piece(G7, Piece::WhiteQueen)));
+    assert!(positions.iter().any(|p| position
+        .apply(*p)
+        .is_occupied_by_piece(G8, Piece::WhiteQueen)));
 }
@@ -14,32 +14,18 @@ use crate::engine::{
     position::Position,
 };
 
-pub const ROOK_MAX_DISTANCE: u32 = u32::MAX;
 pub const ROOK_DIRECTIONS: [DirectionFn; 4] = [
     directions::up,
     directions::down,
     directions::left,
     directions::right,
 ];
-pub const QUEEN_MAX_DISTANCE: u32 = u32::MAX;
-pub const QUEEN_DIRECTIONS: [DirectionFn; 8] = [
-    directions::up,
-    directions::down,
-    directions::left,
-    directions::right,
-    directions::up_left,
-    directions::up_right,
-    directions::down_left,
-    directions::down_right,
-];
-pub const BISHOP_MAX_DISTANCE: u32 = u32::MAX;
 pub const BISHOP_DIRECTIONS: [DirectionFn; 4] = [
     directions::up_left,
     directions::up_right,
     directions::down_left,
     directions::down_right,
 ];
-pub const KING_MAX_DISTANCE: u32 = 1;
 pub const KING_DIRECTIONS: [DirectionFn; 8] = [
     directions::left,
     directions::right,
@@ -50,7 +36,6 @@ pub const KING_DIRECTIONS: [DirectionFn; 8] = [
     directions::down_left,
     directions::down_right,
 ];
-pub const KNIGHT_MAX_DISTANCE: u32 = 1;
 pub const KNIGHT_DIRECTIONS: [DirectionFn; 8] = [
     directions::right_right_down,
     directions::right_right_up,
@@ -111,17 +96,3 @@ pub const BLACK_MOVE_CONFIG: Config = Config {
     castling_move_fn: get_black_castling_moves,
     pawn_fn: get_pawn_moves,
 };
-
-pub const HORIZONTAL_VERTICAL_DIRECTIONS: [DirectionFn; 4] = [
-    directions::up,
-    directions::down,
-    directions::left,
-    directions::right,
-];
-
-pub const DIAGONAL_DIRECTIONS: [DirectionFn; 4] = [
-    directions::up_left,
-    directions::up_right,
-    directions::down_left,
-    directions::down_right,
-];
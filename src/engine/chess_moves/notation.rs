@@ -0,0 +1,270 @@
+use std::fmt;
+
+use crate::engine::{
+    check::is_check,
+    chess_moves::{get_current_player_moves, ChessMove, MoveType},
+    directions::{self, squares::Square},
+    piece::{Piece, Typ},
+    position::{CastlingType, Position},
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MoveParseError {
+    /// The text is not a move in the expected notation.
+    Invalid(String),
+    Illegal(String),
+    Ambiguous(String),
+}
+
+impl fmt::Display for MoveParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoveParseError::Invalid(text) => write!(f, "'{}' is not a move", text),
+            MoveParseError::Illegal(text) => write!(f, "'{}' is not a legal move", text),
+            MoveParseError::Ambiguous(text) => {
+                write!(f, "'{}' matches several legal moves", text)
+            }
+        }
+    }
+}
+
+impl std::error::Error for MoveParseError {}
+
+impl ChessMove {
+    /// Formats the move, which must be legal in `position`, in Standard Algebraic Notation.
+    pub fn to_san(self, position: &Position) -> String {
+        let mut san = match self.get_move_type() {
+            MoveType::Castling { castling_type } => match castling_type {
+                CastlingType::WhiteKingside | CastlingType::BlackKingside => String::from("O-O"),
+                CastlingType::WhiteQueenside | CastlingType::BlackQueenside => {
+                    String::from("O-O-O")
+                }
+            },
+            _ => piece_move_to_san(position, &self),
+        };
+        let opponent = self.get_piece().get_color().get_opponent_color();
+        let after = position.apply(self);
+        if is_check(&after, opponent) {
+            if get_current_player_moves(&after).is_empty() {
+                san.push('#');
+            } else {
+                san.push('+');
+            }
+        }
+        san
+    }
+
+    /// Formats the move in long algebraic notation as used by UCI, e.g. "e7e8q".
+    pub fn to_uci(self) -> String {
+        match self.get_promotion() {
+            Some(piece) => format!(
+                "{}{}{}",
+                self.get_from(),
+                self.get_to(),
+                piece.as_char().to_ascii_lowercase()
+            ),
+            None => format!("{}{}", self.get_from(), self.get_to()),
+        }
+    }
+
+    /// Finds the legal move that `san` describes. Check and annotation suffixes are optional,
+    /// castling may be written with zeros and superfluous disambiguation is accepted.
+    pub fn parse_san(position: &Position, san: &str) -> Result<ChessMove, MoveParseError> {
+        let text = san.trim_end_matches(['+', '#', '!', '?']);
+        let moves = get_current_player_moves(position);
+        let candidates: Vec<ChessMove> = match text {
+            "O-O" | "0-0" => moves
+                .into_iter()
+                .filter(|chess_move| is_castling(chess_move, true))
+                .collect(),
+            "O-O-O" | "0-0-0" => moves
+                .into_iter()
+                .filter(|chess_move| is_castling(chess_move, false))
+                .collect(),
+            _ => {
+                let pattern =
+                    parse_pattern(text).ok_or(MoveParseError::Invalid(san.to_string()))?;
+                moves
+                    .into_iter()
+                    .filter(|chess_move| pattern.matches(chess_move))
+                    .collect()
+            }
+        };
+        match candidates.len() {
+            0 => Err(MoveParseError::Illegal(san.to_string())),
+            1 => Ok(candidates[0]),
+            _ => Err(MoveParseError::Ambiguous(san.to_string())),
+        }
+    }
+
+    /// Finds the legal move that `uci` describes in long algebraic notation.
+    pub fn parse_uci(position: &Position, uci: &str) -> Result<ChessMove, MoveParseError> {
+        let is_valid = matches!(uci.len(), 4 | 5)
+            && uci.is_ascii()
+            && Square::from_algebraic(&uci[0..2]).is_some()
+            && Square::from_algebraic(&uci[2..4]).is_some()
+            && uci[4..].chars().all(|c| matches!(c, 'q' | 'r' | 'b' | 'n'));
+        if !is_valid {
+            return Err(MoveParseError::Invalid(uci.to_string()));
+        }
+        get_current_player_moves(position)
+            .into_iter()
+            .find(|chess_move| chess_move.to_uci() == uci)
+            .ok_or(MoveParseError::Illegal(uci.to_string()))
+    }
+}
+
+fn piece_move_to_san(position: &Position, chess_move: &ChessMove) -> String {
+    let mut san = String::new();
+    let is_capture =
+        chess_move.get_capture().is_some() || chess_move.get_move_type() == MoveType::EnPassant;
+    if chess_move.get_piece().get_type() == Typ::Pawn {
+        if is_capture {
+            san.push(file_char(chess_move.get_from()));
+        }
+    } else {
+        san.push(piece_char(chess_move.get_piece()));
+        san.push_str(&disambiguation(position, chess_move));
+    }
+    if is_capture {
+        san.push('x');
+    }
+    san.push_str(&chess_move.get_to().to_string());
+    if let Some(piece) = chess_move.get_promotion() {
+        san.push('=');
+        san.push(piece_char(piece));
+    }
+    san
+}
+
+/// The file, the rank or the whole from square, whatever tells the move apart from
+/// moves of the same kind of piece to the same square.
+fn disambiguation(position: &Position, chess_move: &ChessMove) -> String {
+    let others: Vec<ChessMove> = get_current_player_moves(position)
+        .into_iter()
+        .filter(|other| {
+            other.get_piece() == chess_move.get_piece()
+                && other.get_to() == chess_move.get_to()
+                && other.get_from() != chess_move.get_from()
+        })
+        .collect();
+    if others.is_empty() {
+        String::new()
+    } else if others
+        .iter()
+        .all(|other| file_char(other.get_from()) != file_char(chess_move.get_from()))
+    {
+        file_char(chess_move.get_from()).to_string()
+    } else if others
+        .iter()
+        .all(|other| rank_char(other.get_from()) != rank_char(chess_move.get_from()))
+    {
+        rank_char(chess_move.get_from()).to_string()
+    } else {
+        chess_move.get_from().to_string()
+    }
+}
+
+struct SanPattern {
+    typ: Typ,
+    file: Option<char>,
+    rank: Option<char>,
+    to: Square,
+    promotion: Option<Typ>,
+}
+
+impl SanPattern {
+    fn matches(&self, chess_move: &ChessMove) -> bool {
+        chess_move.get_piece().get_type() == self.typ
+            && !matches!(chess_move.get_move_type(), MoveType::Castling { .. })
+            && chess_move.get_to() == self.to
+            && chess_move.get_promotion().map(|piece| piece.get_type()) == self.promotion
+            && self
+                .file
+                .is_none_or(|file| file == file_char(chess_move.get_from()))
+            && self
+                .rank
+                .is_none_or(|rank| rank == rank_char(chess_move.get_from()))
+    }
+}
+
+fn parse_pattern(text: &str) -> Option<SanPattern> {
+    let mut chars: Vec<char> = text.chars().filter(|c| *c != 'x' && *c != '-').collect();
+    let mut promotion = None;
+    if let Some(last) = chars.last() {
+        if let Some(typ) = typ_from_char(*last) {
+            promotion = Some(typ);
+            chars.pop();
+            if chars.last() == Some(&'=') {
+                chars.pop();
+            }
+        }
+    }
+    let typ = match chars.first().and_then(|c| typ_from_char(*c)) {
+        Some(typ) => {
+            chars.remove(0);
+            typ
+        }
+        None => Typ::Pawn,
+    };
+    if chars.len() < 2 {
+        return None;
+    }
+    let to_text: String = chars.split_off(chars.len() - 2).into_iter().collect();
+    let to = Square::from_algebraic(&to_text)?;
+    let mut file = None;
+    let mut rank = None;
+    for c in chars {
+        match c {
+            'a'..='h' if file.is_none() => file = Some(c),
+            '1'..='8' if rank.is_none() => rank = Some(c),
+            _ => return None,
+        }
+    }
+    Some(SanPattern {
+        typ,
+        file,
+        rank,
+        to,
+        promotion,
+    })
+}
+
+fn is_castling(chess_move: &ChessMove, kingside: bool) -> bool {
+    match chess_move.get_move_type() {
+        MoveType::Castling { castling_type } => {
+            kingside
+                == matches!(
+                    castling_type,
+                    CastlingType::WhiteKingside | CastlingType::BlackKingside
+                )
+        }
+        _ => false,
+    }
+}
+
+fn piece_char(piece: Piece) -> char {
+    piece.as_char().to_ascii_uppercase()
+}
+
+fn typ_from_char(c: char) -> Option<Typ> {
+    match c {
+        'K' => Some(Typ::King),
+        'Q' => Some(Typ::Queen),
+        'R' => Some(Typ::Rook),
+        'B' => Some(Typ::Bishop),
+        'N' => Some(Typ::Knight),
+        _ => None,
+    }
+}
+
+fn file_char(square: Square) -> char {
+    (b'a' + directions::get_column(square) as u8 - 1) as char
+}
+
+fn rank_char(square: Square) -> char {
+    (b'1' + (square.as_index() / 8) as u8) as char
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,127 @@
+use crate::engine::{
+    chess_moves::{get_current_player_moves, notation::MoveParseError, ChessMove},
+    position::Position,
+};
+
+fn sans(fen: &str) -> Vec<String> {
+    let position = Position::from_fen(fen).unwrap();
+    let mut sans: Vec<String> = get_current_player_moves(&position)
+        .iter()
+        .map(|chess_move| chess_move.to_san(&position))
+        .collect();
+    sans.sort();
+    sans
+}
+
+fn assert_san(fen: &str, san: &str) {
+    let position = Position::from_fen(fen).unwrap();
+    let chess_move = ChessMove::parse_san(&position, san).unwrap();
+    assert_eq!(chess_move.to_san(&position), san);
+}
+
+#[test]
+fn test_san_of_all_moves_round_trips() {
+    let fens = [
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+        "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+    ];
+    for fen in fens {
+        for san in sans(fen) {
+            assert_san(fen, &san);
+        }
+    }
+}
+
+#[test]
+fn test_san_formatting() {
+    let kiwipete = sans("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1");
+    for san in [
+        "O-O", "O-O-O", "Nxf7", "Qxf6", "dxe6", "Bxa6", "Rb1", "gxh3", "Nb1",
+    ] {
+        assert!(kiwipete.contains(&String::from(san)), "{}", san);
+    }
+    let promotions = sans("rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8");
+    for san in ["dxc8=Q", "dxc8=N", "Bxf7", "Kxf2"] {
+        assert!(promotions.contains(&String::from(san)), "{}", san);
+    }
+    assert!(sans("8/8/8/8/8/8/k7/2K4R w - - 0 1").contains(&String::from("Rh2+")));
+    assert!(sans("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").contains(&String::from("Ra8#")));
+    assert!(sans("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1").contains(&String::from("exd6")));
+}
+
+#[test]
+fn test_san_disambiguation() {
+    // Knights on b1 and f3 both reach d2, rooks on a1 and a5 both reach a3,
+    // and queens on e4, h4 and h1 all reach e1, the one on h4 needs file and rank.
+    let sans = sans("2k5/8/8/R7/4Q2Q/1K3N2/8/RN5Q w - - 0 1");
+    for san in ["Nbd2", "Nfd2", "R1a3", "R5a3", "Qee1", "Qh4e1", "Q1e1"] {
+        assert!(sans.contains(&String::from(san)), "{} in {:?}", san, sans);
+    }
+}
+
+#[test]
+fn test_parse_san_variants() {
+    let position = Position::new_starting_position();
+    let knight = ChessMove::parse_san(&position, "Nf3").unwrap();
+    assert_eq!(ChessMove::parse_san(&position, "Ngf3"), Ok(knight));
+    assert_eq!(ChessMove::parse_san(&position, "Ng1-f3!?"), Ok(knight));
+    let castling = Position::from_fen("4k3/8/8/8/8/8/8/4K2R w K - 0 1").unwrap();
+    assert_eq!(
+        ChessMove::parse_san(&castling, "0-0+"),
+        ChessMove::parse_san(&castling, "O-O")
+    );
+    let promotion = Position::from_fen("4k3/1P6/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    assert_eq!(
+        ChessMove::parse_san(&promotion, "b8Q"),
+        ChessMove::parse_san(&promotion, "b8=Q+")
+    );
+    assert_eq!(
+        ChessMove::parse_san(&position, "e5"),
+        Err(MoveParseError::Illegal(String::from("e5")))
+    );
+    assert_eq!(
+        ChessMove::parse_san(&position, "Zz9"),
+        Err(MoveParseError::Invalid(String::from("Zz9")))
+    );
+    let two_knights = Position::from_fen("4k3/8/8/8/8/5N2/8/1N2K3 w - - 0 1").unwrap();
+    assert_eq!(
+        ChessMove::parse_san(&two_knights, "Nd2"),
+        Err(MoveParseError::Ambiguous(String::from("Nd2")))
+    );
+}
+
+#[test]
+fn test_uci_round_trip() {
+    let position =
+        Position::from_fen("r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1")
+            .unwrap();
+    for chess_move in get_current_player_moves(&position) {
+        assert_eq!(
+            ChessMove::parse_uci(&position, &chess_move.to_uci()),
+            Ok(chess_move)
+        );
+    }
+    let position =
+        Position::from_fen("rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8").unwrap();
+    let promotion = ChessMove::parse_uci(&position, "d7c8n").unwrap();
+    assert_eq!(promotion.to_uci(), "d7c8n");
+    assert_eq!(promotion.to_san(&position), "dxc8=N");
+    let castling = ChessMove::parse_uci(&position, "e1g1").unwrap();
+    assert_eq!(castling.to_san(&position), "O-O");
+}
+
+#[test]
+fn test_parse_uci_errors() {
+    let position = Position::new_starting_position();
+    for text in ["", "e2", "e2e4x", "e2e9", "i2i4", "e7e8k", "e2e4qq"] {
+        assert_eq!(
+            ChessMove::parse_uci(&position, text),
+            Err(MoveParseError::Invalid(String::from(text)))
+        );
+    }
+    assert_eq!(
+        ChessMove::parse_uci(&position, "e2e5"),
+        Err(MoveParseError::Illegal(String::from("e2e5")))
+    );
+}
@@ -1,4 +1,5 @@
 use crate::engine::{
+    check::is_under_attack,
     chess_moves::{common::progess, ChessMove, MoveType},
     directions::{self, squares::Square, DirectionFn, RowFn},
     piece::{Color, Piece, Typ},
@@ -361,48 +362,28 @@ pub fn en_passant(
     to: Square,
     capture: Square,
 ) -> ChessMove {
-    let new_position = position
-        .remove_piece(from)
-        .remove_piece(capture)
-        .remove_piece(to)
-        .put_piece(piece, to)
-        .toggle_player()
-        .reset_en_passant();
-
-    ChessMove {
-        move_type: MoveType::EnPassant,
+    ChessMove::new(
+        MoveType::EnPassant,
         piece,
         from,
         to,
-        capture: position.get_piece_at(capture),
-        pormotion: None,
-        position: new_position,
-    }
+        position.get_piece_at(capture),
+        None,
+    )
 }
 
 pub fn promote(position: &Position, from: Square, to: Square, new_piece: Piece) -> ChessMove {
-    let tuple = match position.get_piece_at(to) {
-        Some(piece) => (MoveType::PromotionCapture, Some(piece)),
-        None => (MoveType::Promotion, None),
-    };
-    let new_position = position
-        .remove_piece(from)
-        .remove_piece(to)
-        .put_piece(new_piece, to)
-        .toggle_player()
-        .reset_en_passant();
-
-    ChessMove {
-        move_type: tuple.0,
-        piece: match new_piece.get_color() {
-            Color::Black => Piece::BlackPawn,
-            Color::White => Piece::WhitePawn,
-        },
-        from,
-        to,
-        capture: tuple.1,
-        pormotion: Some(new_piece),
-        position: new_position,
+    let pawn = Piece::new(new_piece.get_color(), Typ::Pawn);
+    match position.get_piece_at(to) {
+        Some(capture) => ChessMove::new(
+            MoveType::PromotionCapture,
+            pawn,
+            from,
+            to,
+            Some(capture),
+            Some(new_piece),
+        ),
+        None => ChessMove::new(MoveType::Promotion, pawn, from, to, None, Some(new_piece)),
     }
 }
 
@@ -418,6 +399,38 @@ pub fn set_en_passant_if_necessary(
     position
 }
 
+/// Whether the pawn on `square`, which has just moved two rows forward, can be taken en
+/// passant without leaving the capturing side's king in check.
+pub fn can_capture_en_passant(position: &Position, square: Square) -> bool {
+    let Some(pawn) = position.get_piece_at(square) else {
+        return false;
+    };
+    let color = pawn.get_color().get_opponent_color();
+    let capturing_pawn = Piece::new(color, Typ::Pawn);
+    let to = match color {
+        Color::White => directions::up(square),
+        Color::Black => directions::down(square),
+    };
+    let Some(to) = to.filter(|_| pawn.get_type() == Typ::Pawn) else {
+        return false;
+    };
+    let king = position
+        .get_squares(Piece::new(color, Typ::King))
+        .iter()
+        .next();
+    [directions::left(square), directions::right(square)]
+        .into_iter()
+        .flatten()
+        .filter(|from| position.is_occupied_by_piece(*from, capturing_pawn))
+        .any(|from| {
+            let after = position
+                .remove_piece(square)
+                .remove_piece(from)
+                .put_piece(capturing_pawn, to);
+            king.is_none_or(|king| !is_under_attack(&after, king, color))
+        })
+}
+
 pub fn is_pawn_two_rows_forward(piece: Piece, from: Square, to: Square) -> bool {
     if !(piece.get_type() == Typ::Pawn) {
         return false;
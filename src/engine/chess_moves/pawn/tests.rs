@@ -10,11 +10,11 @@ mod test_white_forward {
         let result = get_move_white_forward(&position, E2);
 
         assert!(result.is_some());
-        let new_position = result.unwrap();
-        assert!(new_position
-            .position
+        let chess_move = result.unwrap();
+        assert!(position
+            .apply(chess_move)
             .is_occupied_by_piece(E3, Piece::WhitePawn));
-        assert!(!new_position.position.is_occupied(E2));
+        assert!(!position.apply(chess_move).is_occupied(E2));
     }
 
     #[test]
@@ -65,11 +65,11 @@ mod test_black_forward {
         let result = get_move_black_forward(&position, E7);
 
         assert!(result.is_some());
-        let new_position = result.unwrap();
-        assert!(new_position
-            .position
+        let chess_move = result.unwrap();
+        assert!(position
+            .apply(chess_move)
             .is_occupied_by_piece(E6, Piece::BlackPawn));
-        assert!(!new_position.position.is_occupied(E7));
+        assert!(!position.apply(chess_move).is_occupied(E7));
     }
 
     #[test]
@@ -120,12 +120,12 @@ mod test_white_two_forward {
         let result = get_move_white_two_forward(&position, E2);
 
         assert!(result.is_some());
-        let new_position = result.unwrap();
-        assert!(new_position
-            .position
+        let chess_move = result.unwrap();
+        assert!(position
+            .apply(chess_move)
             .is_occupied_by_piece(E4, Piece::WhitePawn));
-        assert!(!new_position.position.is_occupied(E2));
-        assert!(!new_position.position.is_occupied(E3));
+        assert!(!position.apply(chess_move).is_occupied(E2));
+        assert!(!position.apply(chess_move).is_occupied(E3));
     }
 
     #[test]
@@ -182,12 +182,12 @@ mod test_black_two_forward {
         let result = get_move_black_two_forward(&position, E7);
 
         assert!(result.is_some());
-        let new_position = result.unwrap();
-        assert!(new_position
-            .position
+        let chess_move = result.unwrap();
+        assert!(position
+            .apply(chess_move)
             .is_occupied_by_piece(E5, Piece::BlackPawn));
-        assert!(!new_position.position.is_occupied(E7));
-        assert!(!new_position.position.is_occupied(E6));
+        assert!(!position.apply(chess_move).is_occupied(E7));
+        assert!(!position.apply(chess_move).is_occupied(E6));
     }
 
     #[test]
@@ -236,13 +236,13 @@ mod test_white_captures {
         let result = get_move_white_left_capture(&position, E4);
 
         assert!(result.is_some());
-        let new_position = result.unwrap();
-        assert!(new_position
-            .position
+        let chess_move = result.unwrap();
+        assert!(position
+            .apply(chess_move)
             .is_occupied_by_piece(D5, Piece::WhitePawn));
-        assert!(!new_position.position.is_occupied(E4));
-        assert!(!new_position
-            .position
+        assert!(!position.apply(chess_move).is_occupied(E4));
+        assert!(!position
+            .apply(chess_move)
             .is_occupied_by_piece(D5, Piece::BlackPawn));
     }
 
@@ -255,11 +255,11 @@ mod test_white_captures {
         let result = get_move_white_right_capture(&position, E4);
 
         assert!(result.is_some());
-        let new_position = result.unwrap();
-        assert!(new_position
-            .position
+        let chess_move = result.unwrap();
+        assert!(position
+            .apply(chess_move)
             .is_occupied_by_piece(F5, Piece::WhitePawn));
-        assert!(!new_position.position.is_occupied(E4));
+        assert!(!position.apply(chess_move).is_occupied(E4));
     }
 
     #[test]
@@ -340,11 +340,11 @@ mod test_black_captures {
         let result = get_move_black_left_capture(&position, E5);
 
         assert!(result.is_some());
-        let new_position = result.unwrap();
-        assert!(new_position
-            .position
+        let chess_move = result.unwrap();
+        assert!(position
+            .apply(chess_move)
             .is_occupied_by_piece(D4, Piece::BlackPawn));
-        assert!(!new_position.position.is_occupied(E5));
+        assert!(!position.apply(chess_move).is_occupied(E5));
     }
 
     #[test]
@@ -356,11 +356,11 @@ mod test_black_captures {
         let result = get_move_black_right_capture(&position, E5);
 
         assert!(result.is_some());
-        let new_position = result.unwrap();
-        assert!(new_position
-            .position
+        let chess_move = result.unwrap();
+        assert!(position
+            .apply(chess_move)
             .is_occupied_by_piece(F4, Piece::BlackPawn));
-        assert!(!new_position.position.is_occupied(E5));
+        assert!(!position.apply(chess_move).is_occupied(E5));
     }
 
     #[test]
@@ -392,12 +392,12 @@ mod test_white_en_passant {
         let result = get_move_white_left_en_passant(&position, E5);
 
         assert!(result.is_some());
-        let new_position = result.unwrap();
-        assert!(new_position
-            .position
+        let chess_move = result.unwrap();
+        assert!(position
+            .apply(chess_move)
             .is_occupied_by_piece(D6, Piece::WhitePawn));
-        assert!(!new_position.position.is_occupied(E5));
-        assert!(!new_position.position.is_occupied(D5)); // Captured pawn removed
+        assert!(!position.apply(chess_move).is_occupied(E5));
+        assert!(!position.apply(chess_move).is_occupied(D5)); // Captured pawn removed
     }
 
     #[test]
@@ -410,12 +410,12 @@ mod test_white_en_passant {
         let result = get_move_white_right_en_passant(&position, E5);
 
         assert!(result.is_some());
-        let new_position = result.unwrap();
-        assert!(new_position
-            .position
+        let chess_move = result.unwrap();
+        assert!(position
+            .apply(chess_move)
             .is_occupied_by_piece(F6, Piece::WhitePawn));
-        assert!(!new_position.position.is_occupied(E5));
-        assert!(!new_position.position.is_occupied(F5)); // Captured pawn removed
+        assert!(!position.apply(chess_move).is_occupied(E5));
+        assert!(!position.apply(chess_move).is_occupied(F5)); // Captured pawn removed
     }
 
     #[test]
@@ -480,12 +480,12 @@ mod test_black_en_passant {
         let result = get_move_black_left_en_passant(&position, E4);
 
         assert!(result.is_some());
-        let new_position = result.unwrap();
-        assert!(new_position
-            .position
+        let chess_move = result.unwrap();
+        assert!(position
+            .apply(chess_move)
             .is_occupied_by_piece(D3, Piece::BlackPawn));
-        assert!(!new_position.position.is_occupied(E4));
-        assert!(!new_position.position.is_occupied(D4)); // Captured pawn removed
+        assert!(!position.apply(chess_move).is_occupied(E4));
+        assert!(!position.apply(chess_move).is_occupied(D4)); // Captured pawn removed
     }
 
     #[test]
@@ -498,12 +498,12 @@ mod test_black_en_passant {
         let result = get_move_black_right_en_passant(&position, E4);
 
         assert!(result.is_some());
-        let new_position = result.unwrap();
-        assert!(new_position
-            .position
+        let chess_move = result.unwrap();
+        assert!(position
+            .apply(chess_move)
             .is_occupied_by_piece(F3, Piece::BlackPawn));
-        assert!(!new_position.position.is_occupied(E4));
-        assert!(!new_position.position.is_occupied(F4)); // Captured pawn removed
+        assert!(!position.apply(chess_move).is_occupied(E4));
+        assert!(!position.apply(chess_move).is_occupied(F4)); // Captured pawn removed
     }
 }
 
@@ -520,18 +520,26 @@ mod test_white_promotions {
         assert_eq!(results.len(), 4);
 
         // Check each promotion piece
-        let has_queen = results
-            .iter()
-            .any(|p| p.position.is_occupied_by_piece(E8, Piece::WhiteQueen));
-        let has_rook = results
-            .iter()
-            .any(|p| p.position.is_occupied_by_piece(E8, Piece::WhiteRook));
-        let has_bishop = results
-            .iter()
-            .any(|p| p.position.is_occupied_by_piece(E8, Piece::WhiteBishop));
-        let has_knight = results
-            .iter()
-            .any(|p| p.position.is_occupied_by_piece(E8, Piece::WhiteKnight));
+        let has_queen = results.iter().any(|p| {
+            position
+                .apply(*p)
+                .is_occupied_by_piece(E8, Piece::WhiteQueen)
+        });
+        let has_rook = results.iter().any(|p| {
+            position
+                .apply(*p)
+                .is_occupied_by_piece(E8, Piece::WhiteRook)
+        });
+        let has_bishop = results.iter().any(|p| {
+            position
+                .apply(*p)
+                .is_occupied_by_piece(E8, Piece::WhiteBishop)
+        });
+        let has_knight = results.iter().any(|p| {
+            position
+                .apply(*p)
+                .is_occupied_by_piece(E8, Piece::WhiteKnight)
+        });
 
         assert!(has_queen);
         assert!(has_rook);
@@ -539,8 +547,8 @@ mod test_white_promotions {
         assert!(has_knight);
 
         // Original pawn should be gone in all cases
-        for position in &results {
-            assert!(!position.position.is_occupied(E7));
+        for chess_move in &results {
+            assert!(!position.apply(*chess_move).is_occupied(E7));
         }
     }
 
@@ -576,25 +584,33 @@ mod test_white_promotions {
         assert_eq!(results.len(), 4);
 
         // All should capture on D8
-        for position in &results {
-            assert!(!position.position.is_occupied(E7));
+        for chess_move in &results {
+            assert!(!position.apply(*chess_move).is_occupied(E7));
             assert!(!position
-                .position
+                .apply(*chess_move)
                 .is_occupied_by_piece(D8, Piece::BlackKnight));
         }
 
-        let has_queen = results
-            .iter()
-            .any(|p| p.position.is_occupied_by_piece(D8, Piece::WhiteQueen));
-        let has_rook = results
-            .iter()
-            .any(|p| p.position.is_occupied_by_piece(D8, Piece::WhiteRook));
-        let has_bishop = results
-            .iter()
-            .any(|p| p.position.is_occupied_by_piece(D8, Piece::WhiteBishop));
-        let has_knight = results
-            .iter()
-            .any(|p| p.position.is_occupied_by_piece(D8, Piece::WhiteKnight));
+        let has_queen = results.iter().any(|p| {
+            position
+                .apply(*p)
+                .is_occupied_by_piece(D8, Piece::WhiteQueen)
+        });
+        let has_rook = results.iter().any(|p| {
+            position
+                .apply(*p)
+                .is_occupied_by_piece(D8, Piece::WhiteRook)
+        });
+        let has_bishop = results.iter().any(|p| {
+            position
+                .apply(*p)
+                .is_occupied_by_piece(D8, Piece::WhiteBishop)
+        });
+        let has_knight = results.iter().any(|p| {
+            position
+                .apply(*p)
+                .is_occupied_by_piece(D8, Piece::WhiteKnight)
+        });
 
         assert!(has_queen);
         assert!(has_rook);
@@ -612,10 +628,10 @@ mod test_white_promotions {
 
         assert_eq!(results.len(), 4);
 
-        for position in &results {
-            assert!(!position.position.is_occupied(E7));
+        for chess_move in &results {
+            assert!(!position.apply(*chess_move).is_occupied(E7));
             assert!(!position
-                .position
+                .apply(*chess_move)
                 .is_occupied_by_piece(F8, Piece::BlackKnight));
         }
     }
@@ -658,26 +674,34 @@ mod test_black_promotions {
 
         assert_eq!(results.len(), 4);
 
-        let has_queen = results
-            .iter()
-            .any(|p| p.position.is_occupied_by_piece(E1, Piece::BlackQueen));
-        let has_rook = results
-            .iter()
-            .any(|p| p.position.is_occupied_by_piece(E1, Piece::BlackRook));
-        let has_bishop = results
-            .iter()
-            .any(|p| p.position.is_occupied_by_piece(E1, Piece::BlackBishop));
-        let has_knight = results
-            .iter()
-            .any(|p| p.position.is_occupied_by_piece(E1, Piece::BlackKnight));
+        let has_queen = results.iter().any(|p| {
+            position
+                .apply(*p)
+                .is_occupied_by_piece(E1, Piece::BlackQueen)
+        });
+        let has_rook = results.iter().any(|p| {
+            position
+                .apply(*p)
+                .is_occupied_by_piece(E1, Piece::BlackRook)
+        });
+        let has_bishop = results.iter().any(|p| {
+            position
+                .apply(*p)
+                .is_occupied_by_piece(E1, Piece::BlackBishop)
+        });
+        let has_knight = results.iter().any(|p| {
+            position
+                .apply(*p)
+                .is_occupied_by_piece(E1, Piece::BlackKnight)
+        });
 
         assert!(has_queen);
         assert!(has_rook);
         assert!(has_bishop);
         assert!(has_knight);
 
-        for position in &results {
-            assert!(!position.position.is_occupied(E2));
+        for chess_move in &results {
+            assert!(!position.apply(*chess_move).is_occupied(E2));
         }
     }
 
@@ -691,10 +715,10 @@ mod test_black_promotions {
 
         assert_eq!(results.len(), 4);
 
-        for position in &results {
-            assert!(!position.position.is_occupied(E2));
+        for chess_move in &results {
+            assert!(!position.apply(*chess_move).is_occupied(E2));
             assert!(!position
-                .position
+                .apply(*chess_move)
                 .is_occupied_by_piece(D1, Piece::WhiteKnight));
         }
     }
@@ -709,10 +733,10 @@ mod test_black_promotions {
 
         assert_eq!(results.len(), 4);
 
-        for position in &results {
-            assert!(!position.position.is_occupied(E2));
+        for chess_move in &results {
+            assert!(!position.apply(*chess_move).is_occupied(E2));
             assert!(!position
-                .position
+                .apply(*chess_move)
                 .is_occupied_by_piece(F1, Piece::WhiteKnight));
         }
     }
@@ -733,12 +757,16 @@ mod test_aggregate_functions {
         // Should have forward and two-forward moves
         assert_eq!(moves.len(), 2);
 
-        let has_e3 = moves
-            .iter()
-            .any(|p| p.position.is_occupied_by_piece(E3, Piece::WhitePawn));
-        let has_e4 = moves
-            .iter()
-            .any(|p| p.position.is_occupied_by_piece(E4, Piece::WhitePawn));
+        let has_e3 = moves.iter().any(|p| {
+            position
+                .apply(*p)
+                .is_occupied_by_piece(E3, Piece::WhitePawn)
+        });
+        let has_e4 = moves.iter().any(|p| {
+            position
+                .apply(*p)
+                .is_occupied_by_piece(E4, Piece::WhitePawn)
+        });
 
         assert!(has_e3);
         assert!(has_e4);
@@ -756,15 +784,21 @@ mod test_aggregate_functions {
         // Forward, left capture, right capture
         assert_eq!(moves.len(), 3);
 
-        let has_forward = moves
-            .iter()
-            .any(|p| p.position.is_occupied_by_piece(E5, Piece::WhitePawn));
-        let has_left_capture = moves
-            .iter()
-            .any(|p| p.position.is_occupied_by_piece(D5, Piece::WhitePawn));
-        let has_right_capture = moves
-            .iter()
-            .any(|p| p.position.is_occupied_by_piece(F5, Piece::WhitePawn));
+        let has_forward = moves.iter().any(|p| {
+            position
+                .apply(*p)
+                .is_occupied_by_piece(E5, Piece::WhitePawn)
+        });
+        let has_left_capture = moves.iter().any(|p| {
+            position
+                .apply(*p)
+                .is_occupied_by_piece(D5, Piece::WhitePawn)
+        });
+        let has_right_capture = moves.iter().any(|p| {
+            position
+                .apply(*p)
+                .is_occupied_by_piece(F5, Piece::WhitePawn)
+        });
 
         assert!(has_forward);
         assert!(has_left_capture);
@@ -795,10 +829,13 @@ mod test_aggregate_functions {
         assert_eq!(moves.len(), 1);
 
         let has_en_passant = moves.iter().any(|p| {
-            p.position.is_occupied_by_piece(D6, Piece::WhitePawn) && !p.position.is_occupied(D5)
+            position
+                .apply(*p)
+                .is_occupied_by_piece(D6, Piece::WhitePawn)
+                && !position.apply(*p).is_occupied(D5)
         });
 
-        assert_eq!(false, has_en_passant);
+        assert!(!has_en_passant);
     }
 
     #[test]
@@ -814,9 +851,11 @@ mod test_aggregate_functions {
         assert_eq!(moves.len(), 2);
 
         let has_en_passant = moves.iter().any(|p| {
-            p.position.print_board();
-            return (p.position.is_occupied_by_piece(D6, Piece::WhitePawn)
-                && !p.position.is_occupied(D5));
+            position.apply(*p).print_board();
+            position
+                .apply(*p)
+                .is_occupied_by_piece(D6, Piece::WhitePawn)
+                && !position.apply(*p).is_occupied(D5)
         });
 
         assert!(has_en_passant);
@@ -831,12 +870,16 @@ mod test_aggregate_functions {
         // Should have forward and two-forward moves
         assert_eq!(moves.len(), 2);
 
-        let has_e6 = moves
-            .iter()
-            .any(|p| p.position.is_occupied_by_piece(E6, Piece::BlackPawn));
-        let has_e5 = moves
-            .iter()
-            .any(|p| p.position.is_occupied_by_piece(E5, Piece::BlackPawn));
+        let has_e6 = moves.iter().any(|p| {
+            position
+                .apply(*p)
+                .is_occupied_by_piece(E6, Piece::BlackPawn)
+        });
+        let has_e5 = moves.iter().any(|p| {
+            position
+                .apply(*p)
+                .is_occupied_by_piece(E5, Piece::BlackPawn)
+        });
 
         assert!(has_e6);
         assert!(has_e5);
@@ -869,12 +912,16 @@ mod test_public_interface {
         // Should have forward and two-forward moves
         assert_eq!(moves.len(), 2);
 
-        let has_e3 = moves
-            .iter()
-            .any(|p| p.position.is_occupied_by_piece(E3, Piece::WhitePawn));
-        let has_e4 = moves
-            .iter()
-            .any(|p| p.position.is_occupied_by_piece(E4, Piece::WhitePawn));
+        let has_e3 = moves.iter().any(|p| {
+            position
+                .apply(*p)
+                .is_occupied_by_piece(E3, Piece::WhitePawn)
+        });
+        let has_e4 = moves.iter().any(|p| {
+            position
+                .apply(*p)
+                .is_occupied_by_piece(E4, Piece::WhitePawn)
+        });
 
         assert!(has_e3);
         assert!(has_e4);
@@ -889,12 +936,16 @@ mod test_public_interface {
         // Should have forward and two-forward moves
         assert_eq!(moves.len(), 2);
 
-        let has_e6 = moves
-            .iter()
-            .any(|p| p.position.is_occupied_by_piece(E6, Piece::BlackPawn));
-        let has_e5 = moves
-            .iter()
-            .any(|p| p.position.is_occupied_by_piece(E5, Piece::BlackPawn));
+        let has_e6 = moves.iter().any(|p| {
+            position
+                .apply(*p)
+                .is_occupied_by_piece(E6, Piece::BlackPawn)
+        });
+        let has_e5 = moves.iter().any(|p| {
+            position
+                .apply(*p)
+                .is_occupied_by_piece(E5, Piece::BlackPawn)
+        });
 
         assert!(has_e6);
         assert!(has_e5);
@@ -918,7 +969,10 @@ mod test_public_interface {
         assert_eq!(moves.len(), 1);
 
         let has_en_passant = moves.iter().any(|p| {
-            p.position.is_occupied_by_piece(D6, Piece::WhitePawn) && !p.position.is_occupied(D5)
+            position
+                .apply(*p)
+                .is_occupied_by_piece(D6, Piece::WhitePawn)
+                && !position.apply(*p).is_occupied(D5)
         });
 
         assert!(has_en_passant);
@@ -0,0 +1,44 @@
+use crate::engine::{
+    chess_moves::{get_current_player_moves, ChessMove},
+    position::Position,
+};
+
+/// Counts the leaf nodes of the legal move tree down to `depth`.
+pub fn perft(position: &Position, depth: u8) -> u64 {
+    let mut position = *position;
+    count_nodes(&mut position, depth)
+}
+
+fn count_nodes(position: &mut Position, depth: u8) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+    let chess_moves = get_current_player_moves(position);
+    if depth == 1 {
+        return chess_moves.len() as u64;
+    }
+    let mut nodes = 0;
+    for chess_move in chess_moves {
+        let undo = position.make_move(chess_move);
+        nodes += count_nodes(position, depth - 1);
+        position.unmake_move(chess_move, undo);
+    }
+    nodes
+}
+
+/// Splits the perft count by root move, which narrows a mismatch down to a single move.
+pub fn divide(position: &Position, depth: u8) -> Vec<(ChessMove, u64)> {
+    if depth == 0 {
+        return Vec::new();
+    }
+    get_current_player_moves(position)
+        .into_iter()
+        .map(|chess_move| {
+            let nodes = perft(&position.apply(chess_move), depth - 1);
+            (chess_move, nodes)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests;
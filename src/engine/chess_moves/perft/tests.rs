@@ -0,0 +1,94 @@
+use crate::engine::{
+    chess_moves::perft::{divide, perft},
+    position::{fen::STARTING_POSITION_FEN, Position},
+};
+
+const KIWIPETE_FEN: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+const POSITION_3_FEN: &str = "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1";
+const POSITION_4_FEN: &str = "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1";
+const POSITION_4_MIRRORED_FEN: &str =
+    "r2q1rk1/pP1p2pp/Q4n2/bbp1p3/Np6/1B3NBn/pPPP1PPP/R3K2R b KQ - 0 1";
+const POSITION_5_FEN: &str = "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8";
+const POSITION_6_FEN: &str =
+    "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10";
+
+fn assert_perft(fen: &str, expected: &[u64]) {
+    let position = Position::from_fen(fen).unwrap();
+    for (index, nodes) in expected.iter().enumerate() {
+        let depth = index as u8 + 1;
+        assert_eq!(perft(&position, depth), *nodes, "{} depth {}", fen, depth);
+    }
+}
+
+#[test]
+fn test_perft_starting_position() {
+    assert_perft(STARTING_POSITION_FEN, &[20, 400, 8902, 197281]);
+}
+
+#[test]
+fn test_perft_kiwipete() {
+    assert_perft(KIWIPETE_FEN, &[48, 2039, 97862]);
+}
+
+#[test]
+fn test_perft_position_3() {
+    assert_perft(POSITION_3_FEN, &[14, 191, 2812, 43238]);
+}
+
+#[test]
+fn test_perft_position_4() {
+    assert_perft(POSITION_4_FEN, &[6, 264, 9467]);
+    assert_perft(POSITION_4_MIRRORED_FEN, &[6, 264, 9467]);
+}
+
+#[test]
+fn test_perft_position_5() {
+    assert_perft(POSITION_5_FEN, &[44, 1486, 62379]);
+}
+
+#[test]
+fn test_perft_position_6() {
+    assert_perft(POSITION_6_FEN, &[46, 2079, 89890]);
+}
+
+#[test]
+fn test_perft_edge_cases() {
+    // en passant capture that would expose the own king
+    assert_perft("3k4/3p4/8/K1P4r/8/8/8/8 b - - 0 1", &[18, 92, 1670, 10138]);
+    assert_perft(
+        "8/8/4k3/8/2p5/8/B2P2K1/8 w - - 0 1",
+        &[13, 102, 1266, 10276],
+    );
+    // en passant capture that gives check
+    assert_perft(
+        "8/8/1k6/2b5/2pP4/8/5K2/8 b - d3 0 1",
+        &[15, 126, 1928, 13931],
+    );
+    // castling rights lost by a captured rook
+    assert_perft("5k2/8/8/8/8/8/8/4K2R w K - 0 1", &[15, 66, 1198, 6399]);
+    assert_perft("3k4/8/8/8/8/8/8/R3K3 w Q - 0 1", &[16, 71, 1286, 7418]);
+    assert_perft(
+        "r3k2r/1b4bq/8/8/8/8/7B/R3K2R w KQkq - 0 1",
+        &[26, 1141, 27826],
+    );
+    assert_perft(
+        "r3k2r/8/3Q4/8/8/5q2/8/R3K2R b KQkq - 0 1",
+        &[44, 1494, 50509],
+    );
+    // promotions
+    assert_perft("2K2r2/4P3/8/8/8/8/8/3k4 w - - 0 1", &[11, 133, 1442, 19174]);
+    assert_perft("4k3/1P6/8/8/8/8/K7/8 w - - 0 1", &[9, 40, 472, 2661]);
+    assert_perft("8/P1k5/K7/8/8/8/8/8 w - - 0 1", &[6, 27, 273, 1329]);
+    // checkmate and stalemate
+    assert_perft("K1k5/8/P7/8/8/8/8/8 w - - 0 1", &[2, 6, 13, 63]);
+    assert_perft("8/k1P5/8/1K6/8/8/8/8 w - - 0 1", &[10, 25, 268, 926]);
+    assert_perft("8/8/2k5/5q2/5n2/8/5K2/8 b - - 0 1", &[37, 183, 6559, 23527]);
+}
+
+#[test]
+fn test_divide_sums_up_to_perft() {
+    let position = Position::from_fen(KIWIPETE_FEN).unwrap();
+    let divided = divide(&position, 2);
+    assert_eq!(divided.len(), 48);
+    assert_eq!(divided.iter().map(|(_, nodes)| nodes).sum::<u64>(), 2039);
+}
@@ -7,7 +7,11 @@ use crate::engine::{
             get_moves_for_rook_at_square, progess,
         },
         get_black_moves, get_white_moves,
-        pawn::{en_passant, is_pawn_two_rows_forward, promote, set_en_passant_if_necessary},
+        pawn::{
+            can_capture_en_passant, en_passant, is_pawn_two_rows_forward, promote,
+            set_en_passant_if_necessary,
+        },
+        ChessMove, MoveType,
     },
     directions::squares::*,
     piece::*,
@@ -16,24 +20,24 @@ use crate::engine::{
 #[test]
 fn test_progress_white_king() {
     let position = Position::default();
-    let new_position = progess(&position, Piece::WhiteKing, E1, E2);
-    assert!(!new_position
-        .position
+    let chess_move = progess(&position, Piece::WhiteKing, E1, E2);
+    assert!(!position
+        .apply(chess_move)
         .is_occupied_by_piece(E1, Piece::WhiteKing));
-    assert!(new_position
-        .position
+    assert!(position
+        .apply(chess_move)
         .is_occupied_by_piece(E2, Piece::WhiteKing));
 }
 
 #[test]
 fn test_promotion() {
     let position = Position::default().put_piece(Piece::WhitePawn, A7);
-    let new_position = promote(&position, A7, A8, Piece::WhiteQueen);
-    assert!(new_position
-        .position
+    let chess_move = promote(&position, A7, A8, Piece::WhiteQueen);
+    assert!(position
+        .apply(chess_move)
         .is_occupied_by_piece(A8, Piece::WhiteQueen));
-    assert!(!new_position
-        .position
+    assert!(!position
+        .apply(chess_move)
         .is_occupied_by_piece(A7, Piece::WhitePawn));
 }
 #[test]
@@ -42,15 +46,15 @@ fn test_en_passant() {
         .put_piece(Piece::WhitePawn, D4)
         .put_piece(Piece::BlackPawn, E4);
 
-    let new_position = en_passant(&position, Piece::BlackPawn, E4, D3, D4);
-    assert!(!new_position
-        .position
+    let chess_move = en_passant(&position, Piece::BlackPawn, E4, D3, D4);
+    assert!(!position
+        .apply(chess_move)
         .is_occupied_by_piece(D4, Piece::WhitePawn));
-    assert!(!new_position
-        .position
+    assert!(!position
+        .apply(chess_move)
         .is_occupied_by_piece(E4, Piece::BlackPawn));
-    assert!(new_position
-        .position
+    assert!(position
+        .apply(chess_move)
         .is_occupied_by_piece(D3, Piece::BlackPawn));
 }
 
@@ -206,39 +210,39 @@ fn test_disallow_castling_if_necessary_other_piece_move() {
 #[test]
 fn test_progess_toggles_player() {
     let position = Position::default(); // White to move
-    let new_position = progess(&position, Piece::WhitePawn, E2, E3);
-    assert_eq!(new_position.position.get_player(), Color::Black);
+    let chess_move = progess(&position, Piece::WhitePawn, E2, E3);
+    assert_eq!(position.apply(chess_move).get_player(), Color::Black);
 }
 
 #[test]
 fn test_progess_sets_en_passant_for_pawn_two_squares() {
     let position = Position::default();
-    let new_position = progess(&position, Piece::WhitePawn, E2, E4);
-    assert_eq!(new_position.position.get_en_passant(), Some(E4));
+    let chess_move = progess(&position, Piece::WhitePawn, E2, E4);
+    assert_eq!(position.apply(chess_move).get_en_passant(), Some(E4));
 }
 
 #[test]
 fn test_progess_disallows_castling_for_king_move() {
     let position = Position::default();
-    let new_position = progess(&position, Piece::WhiteKing, E1, E2);
-    assert!(!new_position
-        .position
+    let chess_move = progess(&position, Piece::WhiteKing, E1, E2);
+    assert!(!position
+        .apply(chess_move)
         .get_castling_right(CastlingType::WhiteKingside));
-    assert!(!new_position
-        .position
+    assert!(!position
+        .apply(chess_move)
         .get_castling_right(CastlingType::WhiteQueenside));
 }
 
 #[test]
 fn test_progess_disallows_castling_for_rook_move() {
     let position = Position::default();
-    let new_position = progess(&position, Piece::WhiteRook, H1, H2);
-    assert!(!new_position
-        .position
+    let chess_move = progess(&position, Piece::WhiteRook, H1, H2);
+    assert!(!position
+        .apply(chess_move)
         .get_castling_right(CastlingType::WhiteKingside));
     // Queenside should still be allowed
-    assert!(new_position
-        .position
+    assert!(position
+        .apply(chess_move)
         .get_castling_right(CastlingType::WhiteQueenside));
 }
 
@@ -318,33 +322,33 @@ fn test_bishop_white_moves() {
     let mut right_down = false;
     let mut not_valid = false;
 
-    for position in positions {
+    for chess_move in positions {
         if position
-            .position
+            .apply(chess_move)
             .is_occupied_by_piece(F5, Piece::WhiteBishop)
         {
             left_up = true;
         }
         if position
-            .position
+            .apply(chess_move)
             .is_occupied_by_piece(F3, Piece::WhiteBishop)
         {
             left_down = true;
         }
         if position
-            .position
+            .apply(chess_move)
             .is_occupied_by_piece(H5, Piece::WhiteBishop)
         {
             right_up = true;
         }
         if position
-            .position
+            .apply(chess_move)
             .is_occupied_by_piece(H3, Piece::WhiteBishop)
         {
             right_down = true;
         }
         if position
-            .position
+            .apply(chess_move)
             .is_occupied_by_piece(B2, Piece::WhiteBishop)
         {
             not_valid = true;
@@ -360,7 +364,7 @@ fn test_bishop_white_moves() {
 #[test]
 fn test_king_black_moves() {
     let position: Position = Position::new_starting_position();
-    assert!(get_moves_for_king_at_square(&position, Piece::BlackKing, D8).len() == 0);
+    assert!(get_moves_for_king_at_square(&position, Piece::BlackKing, D8).is_empty());
     assert!(get_moves_for_king_at_square(&position, Piece::BlackKing, D3).len() == 8);
     assert!(get_moves_for_king_at_square(&position, Piece::BlackKing, F6).len() == 5);
     assert!(get_moves_for_king_at_square(&position, Piece::BlackKing, H6).len() == 3);
@@ -396,51 +400,51 @@ fn test_knight_moves() {
     let mut found_not_c5 = true;
 
     println!("{:?}", positions.len());
-    for position in positions {
+    for chess_move in positions {
         if position
-            .position
+            .apply(chess_move)
             .is_occupied_by_piece(C3, Piece::WhiteKnight)
         {
             found_c3 = true;
         }
         if position
-            .position
+            .apply(chess_move)
             .is_occupied_by_piece(D6, Piece::WhiteKnight)
         {
             found_d6 = true;
         }
         if position
-            .position
+            .apply(chess_move)
             .is_occupied_by_piece(F6, Piece::WhiteKnight)
         {
             found_f6 = true;
         }
         if position
-            .position
+            .apply(chess_move)
             .is_occupied_by_piece(D2, Piece::WhiteKnight)
         {
             found_d2 = true;
         }
         if position
-            .position
+            .apply(chess_move)
             .is_occupied_by_piece(F2, Piece::WhiteKnight)
         {
             found_f2 = true;
         }
         if position
-            .position
+            .apply(chess_move)
             .is_occupied_by_piece(G3, Piece::WhiteKnight)
         {
             found_g3 = true;
         }
         if position
-            .position
+            .apply(chess_move)
             .is_occupied_by_piece(G5, Piece::WhiteKnight)
         {
             found_g5 = true;
         }
         if position
-            .position
+            .apply(chess_move)
             .is_occupied_by_piece(C5, Piece::WhiteKnight)
         {
             found_not_c5 = false;
@@ -458,7 +462,8 @@ fn test_knight_moves() {
 }
 #[test]
 fn test_queen_white_moves() {
-    let positions = get_moves_for_queen_at_square(&Position::default(), Piece::WhiteQueen, G4);
+    let position = Position::default();
+    let positions = get_moves_for_queen_at_square(&position, Piece::WhiteQueen, G4);
     assert!(positions.len() == 23);
 
     let mut found_up = false;
@@ -471,57 +476,57 @@ fn test_queen_white_moves() {
     let mut found_right_up = false;
     let mut found_right_down = false;
 
-    for position in positions {
+    for chess_move in positions {
         if position
-            .position
+            .apply(chess_move)
             .is_occupied_by_piece(G3, Piece::WhiteQueen)
         {
             found_down = true;
         }
         if position
-            .position
+            .apply(chess_move)
             .is_occupied_by_piece(G8, Piece::WhiteQueen)
         {
             found_up = true;
         }
         if position
-            .position
+            .apply(chess_move)
             .is_occupied_by_piece(A4, Piece::WhiteQueen)
         {
             found_left = true;
         }
         if position
-            .position
+            .apply(chess_move)
             .is_occupied_by_piece(H4, Piece::WhiteQueen)
         {
             found_right = true;
         }
         if position
-            .position
+            .apply(chess_move)
             .is_occupied_by_piece(B2, Piece::WhiteQueen)
         {
             found_not = false;
         }
         if position
-            .position
+            .apply(chess_move)
             .is_occupied_by_piece(F5, Piece::WhiteQueen)
         {
             found_left_up = true;
         }
         if position
-            .position
+            .apply(chess_move)
             .is_occupied_by_piece(F3, Piece::WhiteQueen)
         {
             found_left_down = true;
         }
         if position
-            .position
+            .apply(chess_move)
             .is_occupied_by_piece(H5, Piece::WhiteQueen)
         {
             found_right_up = true;
         }
         if position
-            .position
+            .apply(chess_move)
             .is_occupied_by_piece(H3, Piece::WhiteQueen)
         {
             found_right_down = true;
@@ -548,7 +553,8 @@ fn test_queen_white_moves() {
 }
 #[test]
 fn test_rook_white_moves() {
-    let positions = get_moves_for_rook_at_square(&Position::default(), Piece::BlackRook, G4);
+    let position = Position::default();
+    let positions = get_moves_for_rook_at_square(&position, Piece::BlackRook, G4);
 
     assert!(positions.len() == 14);
     let mut found_up = false;
@@ -557,20 +563,35 @@ fn test_rook_white_moves() {
     let mut found_right = false;
     let mut found_not = true;
 
-    for position in positions {
-        if position.position.is_occupied_by_piece(G3, Piece::BlackRook) {
+    for chess_move in positions {
+        if position
+            .apply(chess_move)
+            .is_occupied_by_piece(G3, Piece::BlackRook)
+        {
             found_down = true;
         }
-        if position.position.is_occupied_by_piece(G8, Piece::BlackRook) {
+        if position
+            .apply(chess_move)
+            .is_occupied_by_piece(G8, Piece::BlackRook)
+        {
             found_up = true;
         }
-        if position.position.is_occupied_by_piece(A4, Piece::BlackRook) {
+        if position
+            .apply(chess_move)
+            .is_occupied_by_piece(A4, Piece::BlackRook)
+        {
             found_left = true;
         }
-        if position.position.is_occupied_by_piece(H4, Piece::BlackRook) {
+        if position
+            .apply(chess_move)
+            .is_occupied_by_piece(H4, Piece::BlackRook)
+        {
             found_right = true;
         }
-        if position.position.is_occupied_by_piece(B2, Piece::BlackRook) {
+        if position
+            .apply(chess_move)
+            .is_occupied_by_piece(B2, Piece::BlackRook)
+        {
             found_not = false;
         }
     }
@@ -585,3 +606,42 @@ fn test_rook_white_moves() {
     // not
     assert!(found_not);
 }
+
+#[test]
+fn test_chess_move_encoding() {
+    assert_eq!(std::mem::size_of::<ChessMove>(), 4);
+    let castling = MoveType::Castling {
+        castling_type: CastlingType::BlackQueenside,
+    };
+    let chess_move = ChessMove::new(castling, Piece::BlackKing, E8, C8, None, None);
+    assert_eq!(chess_move.get_move_type(), castling);
+    assert_eq!(chess_move.get_piece(), Piece::BlackKing);
+    assert_eq!((chess_move.get_from(), chess_move.get_to()), (E8, C8));
+    assert_eq!(chess_move.get_capture(), None);
+
+    let promotion = ChessMove::new(
+        MoveType::PromotionCapture,
+        Piece::WhitePawn,
+        G7,
+        H8,
+        Some(Piece::BlackRook),
+        Some(Piece::WhiteKnight),
+    );
+    assert_eq!(promotion.get_move_type(), MoveType::PromotionCapture);
+    assert_eq!((promotion.get_from(), promotion.get_to()), (G7, H8));
+    assert_eq!(promotion.get_capture(), Some(Piece::BlackRook));
+    assert_eq!(promotion.get_promotion(), Some(Piece::WhiteKnight));
+}
+
+#[test]
+fn test_can_capture_en_passant() {
+    let position = Position::from_fen("4k3/8/8/8/3pP3/8/8/4K3 b - e3 0 1").unwrap();
+    assert!(can_capture_en_passant(&position, E4));
+    assert_eq!(position.get_capturable_en_passant(), Some(E4));
+    let no_neighbour = Position::from_fen("4k3/8/8/8/p3P3/8/8/4K3 b - e3 0 1").unwrap();
+    assert!(!can_capture_en_passant(&no_neighbour, E4));
+    assert_eq!(no_neighbour.get_capturable_en_passant(), None);
+    // Taking would expose the black king on the fourth rank to the rook.
+    let pinned = Position::from_fen("8/8/8/8/k2pP2R/8/8/4K3 b - e3 0 1").unwrap();
+    assert!(!can_capture_en_passant(&pinned, E4));
+}
@@ -1,4 +1,4 @@
-use std::cmp::Ordering;
+use std::{cmp::Ordering, fmt};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Square(u32);
@@ -17,6 +17,28 @@ impl Square {
     pub const fn as_index(self) -> u32 {
         self.0
     }
+
+    /// Parses a square from algebraic notation, e.g. "e4".
+    pub fn from_algebraic(name: &str) -> Option<Self> {
+        let mut chars = name.chars();
+        let (Some(file), Some(rank), None) = (chars.next(), chars.next(), chars.next()) else {
+            return None;
+        };
+        if !('a'..='h').contains(&file) || !('1'..='8').contains(&rank) {
+            return None;
+        }
+        let column = file as u32 - 'a' as u32;
+        let row = rank as u32 - '1' as u32;
+        Square::new(row * 8 + column)
+    }
+}
+
+impl fmt::Display for Square {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let file = (b'a' + (self.0 % 8) as u8) as char;
+        let rank = (b'1' + (self.0 / 8) as u8) as char;
+        write!(f, "{}{}", file, rank)
+    }
 }
 
 impl PartialOrd for Square {
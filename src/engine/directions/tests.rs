@@ -110,3 +110,15 @@ fn test_row_2() {
 fn test_row_7() {
     assert!(is_in_row_7(E7));
 }
+
+#[test]
+fn test_square_algebraic_notation() {
+    assert_eq!(Square::from_algebraic("a1"), Some(A1));
+    assert_eq!(Square::from_algebraic("e4"), Some(E4));
+    assert_eq!(Square::from_algebraic("h8"), Some(H8));
+    assert_eq!(Square::from_algebraic("i1"), None);
+    assert_eq!(Square::from_algebraic("a9"), None);
+    assert_eq!(Square::from_algebraic("a10"), None);
+    assert_eq!(E4.to_string(), "e4");
+    assert_eq!(H8.to_string(), "h8");
+}
@@ -1,55 +1,94 @@
-use crate::engine::position::Position;
-#[derive(Debug, Default, Clone, Copy, PartialEq)]
+use crate::engine::{
+    evaluation::{
+        params::{EvalParams, DEFAULT_PARAMS},
+        pawn_table::{PawnEntry, PawnTable},
+        phase::{get_phase, Tapered},
+    },
+    piece::Color,
+    position::Position,
+};
+/// The terms of one side's evaluation in centipawns.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub struct Score {
-    pub material: f32,
-    pub squares: f32,
-    pub mobility: f32,
-    pub pawn_structure: f32,
+    pub material: Tapered,
+    pub squares: Tapered,
+    pub mobility: Tapered,
+    pub pawn_structure: Tapered,
+    pub king_safety: Tapered,
 }
 
-#[derive(Debug, Default, Clone, Copy, PartialEq)]
+impl Score {
+    pub fn get_total(&self) -> Tapered {
+        self.material + self.squares + self.mobility + self.pawn_structure + self.king_safety
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub struct Evaluation {
     pub black_score: Score,
     pub white_score: Score,
-    pub score: f32,
-    pub hits: u32,
+    /// From `phase::MIDDLEGAME_PHASE` with all pieces on the board down to 0 when only
+    /// kings and pawns are left.
+    pub phase: i32,
+    /// In centipawns from white's point of view, the middlegame and endgame values
+    /// blended by the phase.
+    pub score: i32,
 }
 impl Evaluation {
+    /// Evaluates with the compiled-in parameters.
     pub fn new(position: &Position) -> Evaluation {
+        Evaluation::with_params(position, &DEFAULT_PARAMS)
+    }
+
+    pub fn with_params(position: &Position, params: &EvalParams) -> Evaluation {
+        Evaluation::with_pawns(position, params, PawnEntry::new(position, params))
+    }
+
+    /// Like `with_params`, but takes the pawn structure from `pawn_table` if it is cached
+    /// there.
+    pub fn with_pawn_table(
+        position: &Position,
+        params: &EvalParams,
+        pawn_table: &mut PawnTable,
+    ) -> Evaluation {
+        Evaluation::with_pawns(position, params, pawn_table.probe(position, params))
+    }
+
+    fn with_pawns(position: &Position, params: &EvalParams, pawns: PawnEntry) -> Evaluation {
         let white_score: Score = Score {
-            material: material::count_white(position),
-            squares: squares::count_white(position),
-            mobility: mobility::count_white(position),
-            pawn_structure: pawn_structures::count_white(position),
+            material: material::count_white(position, params),
+            squares: squares::count_white(position, params),
+            mobility: mobility::count(position, params, Color::White, pawns.black.attacks),
+            pawn_structure: pawn_structures::count(position, params, Color::White, pawns.white),
+            king_safety: king_safety::count_white(position, params),
         };
 
         let black_score: Score = Score {
-            material: material::count_black(position),
-            squares: squares::count_black(position),
-            mobility: mobility::count_black(position),
-            pawn_structure: pawn_structures::count_black(position),
+            material: material::count_black(position, params),
+            squares: squares::count_black(position, params),
+            mobility: mobility::count(position, params, Color::Black, pawns.white.attacks),
+            pawn_structure: pawn_structures::count(position, params, Color::Black, pawns.black),
+            king_safety: king_safety::count_black(position, params),
         };
 
-        let score = white_score.material
-            + white_score.mobility
-            + white_score.squares
-            + white_score.pawn_structure
-            - (black_score.material
-                + black_score.mobility
-                + black_score.squares
-                + black_score.pawn_structure);
+        let phase = get_phase(position);
+        let score = (white_score.get_total() - black_score.get_total()).blend(phase);
 
         Evaluation {
             black_score,
             white_score,
+            phase,
             score,
-            hits: 0,
         }
     }
 }
-mod material;
+mod king_safety;
+pub mod material;
 mod mobility;
+pub mod params;
 mod pawn_structures;
+pub mod pawn_table;
+pub mod phase;
 mod squares;
 #[cfg(test)]
 mod tests;
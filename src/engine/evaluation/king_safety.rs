@@ -0,0 +1,107 @@
+use crate::engine::{
+    attacks::{attacks_from, king_attacks},
+    directions::squares::Square,
+    evaluation::{
+        params::EvalParams,
+        pawn_structures::{get_file, get_relative_rank},
+        phase::Tapered,
+    },
+    piece::{Color, Piece, Typ},
+    position::{bitboard::Bitboard, Position},
+};
+
+// Indexed by how many ranks the nearest pawn of the file stands in front of the king,
+// zero if there is none within reach.
+pub(super) const SHIELD_SCORE: [i32; 3] = [-25, 0, -10];
+pub(super) const STORM_SCORE: [i32; 4] = [0, -5, -30, -15];
+pub(super) const OPEN_FILE_SCORE: i32 = -25;
+pub(super) const HALF_OPEN_FILE_SCORE: i32 = -15;
+
+// A single attacker rarely mates, so the attack weights only count in full when several
+// pieces take part. Indexed by the number of attackers, in percent.
+pub(super) const ATTACK_SCALE: [i32; 8] = [0, 0, 50, 75, 88, 94, 97, 99];
+pub(super) const KNIGHT_ATTACK_WEIGHT: i32 = 20;
+pub(super) const BISHOP_ATTACK_WEIGHT: i32 = 20;
+pub(super) const ROOK_ATTACK_WEIGHT: i32 = 40;
+pub(super) const QUEEN_ATTACK_WEIGHT: i32 = 80;
+
+pub fn count_black(position: &Position, params: &EvalParams) -> Tapered {
+    count(position, params, Color::Black)
+}
+
+pub fn count_white(position: &Position, params: &EvalParams) -> Tapered {
+    count(position, params, Color::White)
+}
+
+/// Without queens and rooks mate threats are rare, so king safety only has a
+/// middlegame value.
+fn count(position: &Position, params: &EvalParams, color: Color) -> Tapered {
+    let king = position.get_king_square(color);
+    Tapered::new(
+        get_pawn_cover(position, params, color, king) + get_attacks(position, params, color, king),
+        0,
+    )
+}
+
+/// The pawn shield and pawn storms on the king's file and its neighbours, and whether
+/// they are open.
+pub fn get_pawn_cover(position: &Position, params: &EvalParams, color: Color, king: Square) -> i32 {
+    let own_pawns = position.get_squares(Piece::new(color, Typ::Pawn));
+    let opponent_pawns = position.get_squares(Piece::new(color.get_opponent_color(), Typ::Pawn));
+    let king_file = king.as_index() % 8;
+    let king_rank = get_relative_rank(color, king);
+    let mut score = 0;
+    for file in king_file.saturating_sub(1)..=(king_file + 1).min(7) {
+        let shield = get_nearest_distance(own_pawns, color, file, king_rank);
+        score += params.pawn_shield[shield.filter(|distance| *distance < 3).unwrap_or(0)];
+        let storm = get_nearest_distance(opponent_pawns, color, file, king_rank);
+        score += params.pawn_storm[storm.filter(|distance| *distance < 4).unwrap_or(0)];
+        if !has_pawn_on_file(own_pawns, file) {
+            score += if has_pawn_on_file(opponent_pawns, file) {
+                params.half_open_file
+            } else {
+                params.open_file
+            };
+        }
+    }
+    score
+}
+
+/// The opponent's pieces attacking the squares around the king, weighted by piece and
+/// scaled up with the number of attackers.
+pub fn get_attacks(position: &Position, params: &EvalParams, color: Color, king: Square) -> i32 {
+    let zone = king_attacks(king) | Bitboard::from(king);
+    let occupancy = position.get_all();
+    let opponent = color.get_opponent_color();
+    let mut attackers = 0;
+    let mut weight = 0;
+    for (typ, piece_weight) in [
+        (Typ::Knight, params.knight_attack),
+        (Typ::Bishop, params.bishop_attack),
+        (Typ::Rook, params.rook_attack),
+        (Typ::Queen, params.queen_attack),
+    ] {
+        let piece = Piece::new(opponent, typ);
+        for square in position.get_squares(piece).iter() {
+            if !(attacks_from(piece, square, occupancy) & zone).is_empty() {
+                attackers += 1;
+                weight += piece_weight;
+            }
+        }
+    }
+    -weight * params.attack_scale[attackers.min(params.attack_scale.len() - 1)] / 100
+}
+
+/// How many ranks the nearest of `pawns` on `file` stands in front of `king_rank`.
+fn get_nearest_distance(pawns: Bitboard, color: Color, file: u32, king_rank: u32) -> Option<usize> {
+    (pawns & get_file(file))
+        .iter()
+        .map(|square| get_relative_rank(color, square))
+        .filter(|rank| *rank > king_rank)
+        .map(|rank| (rank - king_rank) as usize)
+        .min()
+}
+
+fn has_pawn_on_file(pawns: Bitboard, file: u32) -> bool {
+    !(pawns & get_file(file)).is_empty()
+}
@@ -1,28 +1,54 @@
-use crate::engine::{piece::*, position::Position};
+use crate::engine::{
+    evaluation::{params::EvalParams, phase::Tapered},
+    piece::*,
+    position::Position,
+};
 
-const KING_SCORE: u32 = 900;
-const QUEEN_SCORE: u32 = 90;
-const ROOK_SCORE: u32 = 50;
-const BISHOP_SCORE: u32 = 30;
-const KNIGHT_SCORE: u32 = 30;
-const PAWN_SCORE: u32 = 10;
+// Both kings are always on the board, so the king has no material value.
+const QUEEN_SCORE: Tapered = Tapered::new(900, 940);
+const ROOK_SCORE: Tapered = Tapered::new(500, 540);
+const BISHOP_SCORE: Tapered = Tapered::new(300, 320);
+const KNIGHT_SCORE: Tapered = Tapered::new(300, 290);
+const PAWN_SCORE: Tapered = Tapered::new(100, 120);
 
-pub fn count_black(position: &Position) -> f32 {
-    let black_score = KING_SCORE * position.count_pieces(Piece::BlackKing)
-        + QUEEN_SCORE * position.count_pieces(Piece::BlackQueen)
-        + ROOK_SCORE * position.count_pieces(Piece::BlackRook)
-        + BISHOP_SCORE * position.count_pieces(Piece::BlackBishop)
-        + KNIGHT_SCORE * position.count_pieces(Piece::BlackKnight)
-        + PAWN_SCORE * position.count_pieces(Piece::BlackPawn);
-    black_score as f32
+/// The default values in the order of `params::TYPES`.
+pub(super) const DEFAULT_VALUES: [Tapered; 5] = [
+    PAWN_SCORE,
+    KNIGHT_SCORE,
+    BISHOP_SCORE,
+    ROOK_SCORE,
+    QUEEN_SCORE,
+];
+
+fn get_score(typ: Typ) -> Tapered {
+    match typ {
+        Typ::King => Tapered::default(),
+        Typ::Queen => QUEEN_SCORE,
+        Typ::Rook => ROOK_SCORE,
+        Typ::Bishop => BISHOP_SCORE,
+        Typ::Knight => KNIGHT_SCORE,
+        Typ::Pawn => PAWN_SCORE,
+    }
+}
+
+/// The default middlegame material value of a piece in centipawns, zero for the king.
+/// Move ordering uses it, so it doesn't change with tuned parameters.
+pub fn get_value(piece: Piece) -> i32 {
+    get_score(piece.get_type()).middlegame
+}
+
+pub fn count_black(position: &Position, params: &EvalParams) -> Tapered {
+    count(position, params, Color::Black)
+}
+
+pub fn count_white(position: &Position, params: &EvalParams) -> Tapered {
+    count(position, params, Color::White)
 }
 
-pub fn count_white(position: &Position) -> f32 {
-    let white_score = KING_SCORE * position.count_pieces(Piece::WhiteKing)
-        + QUEEN_SCORE * position.count_pieces(Piece::WhiteQueen)
-        + ROOK_SCORE * position.count_pieces(Piece::WhiteRook)
-        + BISHOP_SCORE * position.count_pieces(Piece::WhiteBishop)
-        + KNIGHT_SCORE * position.count_pieces(Piece::WhiteKnight)
-        + PAWN_SCORE * position.count_pieces(Piece::WhitePawn);
-    white_score as f32
+fn count(position: &Position, params: &EvalParams, color: Color) -> Tapered {
+    let mut score = Tapered::default();
+    for typ in [Typ::Queen, Typ::Rook, Typ::Bishop, Typ::Knight, Typ::Pawn] {
+        score += params.get_material(typ) * position.count_pieces(Piece::new(color, typ)) as i32;
+    }
+    score
 }
@@ -1,35 +1,63 @@
 use crate::engine::{
-    chess_moves::{get_black_mobility, get_white_mobility, ChessMove},
-    piece::{self},
-    position::Position,
+    attacks::attacks_from,
+    evaluation::{params::EvalParams, phase::Tapered},
+    piece::{Color, Piece, Typ},
+    position::{bitboard::Bitboard, Position},
 };
 
-const QUEEN_FACTOR: f32 = 0.25; // ~2.25 Punkte pro Feld
-const ROOK_FACTOR: f32 = 0.20; // ~1.0 Punkte pro Feld
-const BISHOP_FACTOR: f32 = 0.10; // ~0.3 Punkte pro Feld
-const KNIGHT_FACTOR: f32 = 0.15; // ~0.45 Punkte pro Feld
-const KING_FACTOR: f32 = 0.05; // König-Aktivität ist später wichtig
-const PAWN_FACTOR: f32 = 0.0;
+// The value of a piece by the number of safe squares it attacks. Squares held by own
+// pieces or attacked by opposing pawns don't count.
+pub(super) const KNIGHT_MOBILITY: [Tapered; 9] = linear_table(Tapered::new(15, 10));
+pub(super) const BISHOP_MOBILITY: [Tapered; 14] = linear_table(Tapered::new(10, 15));
+pub(super) const ROOK_MOBILITY: [Tapered; 15] = linear_table(Tapered::new(20, 30));
+pub(super) const QUEEN_MOBILITY: [Tapered; 28] = linear_table(Tapered::new(25, 20));
+// An active king only matters once the danger of mate is gone.
+pub(super) const KING_MOBILITY: [Tapered; 9] = linear_table(Tapered::new(5, 20));
 
-pub fn count_black(position: &Position) -> f32 {
-    get_score(get_black_mobility(position))
+/// A table rising by `factor` tenths of a centipawn per square, rounded to centipawns.
+const fn linear_table<const N: usize>(factor: Tapered) -> [Tapered; N] {
+    let mut table = [Tapered::new(0, 0); N];
+    let mut squares = 0;
+    while squares < N {
+        let count = squares as i32;
+        table[squares] = Tapered::new(
+            (count * factor.middlegame + 5) / 10,
+            (count * factor.endgame + 5) / 10,
+        );
+        squares += 1;
+    }
+    table
 }
-pub fn count_white(position: &Position) -> f32 {
-    get_score(get_white_mobility(position))
+
+fn get_table(params: &EvalParams, typ: Typ) -> &[Tapered] {
+    match typ {
+        Typ::Knight => &params.knight_mobility,
+        Typ::Bishop => &params.bishop_mobility,
+        Typ::Rook => &params.rook_mobility,
+        Typ::Queen => &params.queen_mobility,
+        Typ::King => &params.king_mobility,
+        Typ::Pawn => &[],
+    }
 }
 
-fn get_score(chess_moves: Vec<ChessMove>) -> f32 {
-    let mut score: f32 = 0.0;
-    for chess_move in chess_moves {
-        let factor = match chess_move.piece.get_type() {
-            piece::Typ::King => KING_FACTOR,
-            piece::Typ::Queen => QUEEN_FACTOR,
-            piece::Typ::Rook => ROOK_FACTOR,
-            piece::Typ::Pawn => PAWN_FACTOR,
-            piece::Typ::Knight => KNIGHT_FACTOR,
-            piece::Typ::Bishop => BISHOP_FACTOR,
-        };
-        score += factor;
+/// The mobility of `color`'s pieces, `opponent_pawn_attacks` are the squares the
+/// opposing pawns attack.
+pub fn count(
+    position: &Position,
+    params: &EvalParams,
+    color: Color,
+    opponent_pawn_attacks: Bitboard,
+) -> Tapered {
+    let occupancy = position.get_all();
+    let safe = !(position.get_squares_of_color(color) | opponent_pawn_attacks);
+    let mut score = Tapered::default();
+    for typ in [Typ::Knight, Typ::Bishop, Typ::Rook, Typ::Queen, Typ::King] {
+        let piece = Piece::new(color, typ);
+        let table = get_table(params, typ);
+        for square in position.get_squares(piece).iter() {
+            let squares = (attacks_from(piece, square, occupancy) & safe).count_ones() as usize;
+            score += table[squares.min(table.len() - 1)];
+        }
     }
     score
 }
@@ -0,0 +1,327 @@
+use std::{fmt, fs, io, path::Path};
+
+use once_cell::sync::Lazy;
+
+use crate::engine::{
+    directions::squares::Square,
+    evaluation::{king_safety, material, mobility, pawn_structures, phase::Tapered, squares},
+    piece::Typ,
+};
+
+/// A parameter's name, only built when it's needed. Tuning walks the parameters far more
+/// often than files are read or written.
+type Name<'a> = &'a dyn Fn() -> String;
+type Visit<'a> = &'a mut dyn FnMut(Name, &mut i32);
+
+/// The compiled-in parameters, used unless others are loaded.
+pub static DEFAULT_PARAMS: Lazy<EvalParams> = Lazy::new(EvalParams::default);
+
+/// The piece types in the order of the per type tables, with their names in files.
+pub const TYPES: [(Typ, &str); 6] = [
+    (Typ::Pawn, "pawn"),
+    (Typ::Knight, "knight"),
+    (Typ::Bishop, "bishop"),
+    (Typ::Rook, "rook"),
+    (Typ::Queen, "queen"),
+    (Typ::King, "king"),
+];
+
+/// Every weight of the evaluation. The defaults are the constants of the evaluation
+/// modules. In a file, each line holds a parameter's name and value, for example
+/// `material.pawn.endgame 120`. Parameters missing from a file keep their default.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EvalParams {
+    /// Indexed like `TYPES`, the king has no material value.
+    pub material: [Tapered; 5],
+    /// Indexed like `TYPES`, from white's side of the board with a1 first.
+    pub squares: [[Tapered; 64]; 6],
+    pub knight_mobility: [Tapered; 9],
+    pub bishop_mobility: [Tapered; 14],
+    pub rook_mobility: [Tapered; 15],
+    pub queen_mobility: [Tapered; 28],
+    pub king_mobility: [Tapered; 9],
+    pub doubled_pawn: Tapered,
+    pub isolated_pawn: Tapered,
+    pub backward_pawn: Tapered,
+    /// The tables below are indexed by the rank counted from the pawn's own side.
+    pub passed_pawn: [Tapered; 8],
+    pub candidate_pawn: [Tapered; 8],
+    pub connected_pawn: [Tapered; 8],
+    pub free_path: [i32; 8],
+    pub king_distance_weight: [i32; 8],
+    pub opponent_king_distance: i32,
+    pub own_king_distance: i32,
+    pub pawn_shield: [i32; 3],
+    pub pawn_storm: [i32; 4],
+    pub open_file: i32,
+    pub half_open_file: i32,
+    pub attack_scale: [i32; 8],
+    pub knight_attack: i32,
+    pub bishop_attack: i32,
+    pub rook_attack: i32,
+    pub queen_attack: i32,
+}
+
+#[derive(Debug)]
+pub enum ParamsError {
+    Io(io::Error),
+    InvalidLine(usize),
+    UnknownParameter { line: usize, name: String },
+}
+
+impl fmt::Display for ParamsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParamsError::Io(error) => write!(f, "{}", error),
+            ParamsError::InvalidLine(line) => {
+                write!(
+                    f,
+                    "line {} is not a parameter name followed by a number",
+                    line
+                )
+            }
+            ParamsError::UnknownParameter { line, name } => {
+                write!(f, "unknown parameter '{}' on line {}", name, line)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParamsError {}
+
+impl From<io::Error> for ParamsError {
+    fn from(error: io::Error) -> Self {
+        ParamsError::Io(error)
+    }
+}
+
+impl Default for EvalParams {
+    fn default() -> Self {
+        EvalParams {
+            material: material::DEFAULT_VALUES,
+            squares: TYPES.map(|(typ, _)| squares::get_default_table(typ)),
+            knight_mobility: mobility::KNIGHT_MOBILITY,
+            bishop_mobility: mobility::BISHOP_MOBILITY,
+            rook_mobility: mobility::ROOK_MOBILITY,
+            queen_mobility: mobility::QUEEN_MOBILITY,
+            king_mobility: mobility::KING_MOBILITY,
+            doubled_pawn: pawn_structures::DOUBLED_PAWN_SCORE,
+            isolated_pawn: pawn_structures::ISOLATED_PAWN_SCORE,
+            backward_pawn: pawn_structures::BACKWARD_PAWN_SCORE,
+            passed_pawn: pawn_structures::PASSED_PAWN_SCORE,
+            candidate_pawn: pawn_structures::CANDIDATE_PAWN_SCORE,
+            connected_pawn: pawn_structures::CONNECTED_PAWN_SCORE,
+            free_path: pawn_structures::FREE_PATH_SCORE,
+            king_distance_weight: pawn_structures::KING_DISTANCE_WEIGHT,
+            opponent_king_distance: pawn_structures::OPPONENT_KING_DISTANCE_SCORE,
+            own_king_distance: pawn_structures::OWN_KING_DISTANCE_SCORE,
+            pawn_shield: king_safety::SHIELD_SCORE,
+            pawn_storm: king_safety::STORM_SCORE,
+            open_file: king_safety::OPEN_FILE_SCORE,
+            half_open_file: king_safety::HALF_OPEN_FILE_SCORE,
+            attack_scale: king_safety::ATTACK_SCALE,
+            knight_attack: king_safety::KNIGHT_ATTACK_WEIGHT,
+            bishop_attack: king_safety::BISHOP_ATTACK_WEIGHT,
+            rook_attack: king_safety::ROOK_ATTACK_WEIGHT,
+            queen_attack: king_safety::QUEEN_ATTACK_WEIGHT,
+        }
+    }
+}
+
+impl EvalParams {
+    pub fn load(path: &Path) -> Result<EvalParams, ParamsError> {
+        EvalParams::parse(&fs::read_to_string(path)?)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    /// Reads parameters in the file format. Empty lines and lines starting with `#` are
+    /// skipped.
+    pub fn parse(text: &str) -> Result<EvalParams, ParamsError> {
+        let mut values = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut fields = line.split_whitespace();
+            match (
+                fields.next(),
+                fields.next().map(str::parse::<i32>),
+                fields.next(),
+            ) {
+                (Some(name), Some(Ok(value)), None) => values.push((index + 1, name, value)),
+                _ => return Err(ParamsError::InvalidLine(index + 1)),
+            }
+        }
+        let mut params = EvalParams::default();
+        let mut found = vec![false; values.len()];
+        params.visit(&mut |name, value| {
+            let name = name();
+            for (index, (_, value_name, new_value)) in values.iter().enumerate() {
+                if *value_name == name {
+                    *value = *new_value;
+                    found[index] = true;
+                }
+            }
+        });
+        match values.iter().zip(found).find(|(_, found)| !found) {
+            Some(((line, name, _), _)) => Err(ParamsError::UnknownParameter {
+                line: *line,
+                name: name.to_string(),
+            }),
+            None => Ok(params),
+        }
+    }
+
+    /// All parameters in file order, for tuning.
+    pub fn get_values(&self) -> Vec<i32> {
+        let mut values = Vec::new();
+        self.clone().visit(&mut |_, value| values.push(*value));
+        values
+    }
+
+    /// Sets all parameters from values in the order of `get_values`.
+    pub fn set_values(&mut self, values: &[i32]) {
+        let mut index = 0;
+        self.visit(&mut |_, value| {
+            if let Some(new_value) = values.get(index) {
+                *value = *new_value;
+            }
+            index += 1;
+        });
+    }
+
+    /// The parameters of the piece type's table.
+    pub fn get_squares(&self, typ: Typ) -> &[Tapered; 64] {
+        &self.squares[get_type_index(typ)]
+    }
+
+    /// The material value of a piece type, zero for the king.
+    pub fn get_material(&self, typ: Typ) -> Tapered {
+        match typ {
+            Typ::King => Tapered::default(),
+            typ => self.material[get_type_index(typ)],
+        }
+    }
+
+    /// Calls `visit` with the name and value of every parameter, in file order.
+    fn visit(&mut self, visit: Visit) {
+        for (index, (_, name)) in TYPES[..5].iter().enumerate() {
+            visit_tapered(
+                &|| format!("material.{}", name),
+                &mut self.material[index],
+                visit,
+            );
+        }
+        for (index, (_, name)) in TYPES.iter().enumerate() {
+            for (square, value) in self.squares[index].iter_mut().enumerate() {
+                let square = Square::new_unchecked(square as u32);
+                visit_tapered(&|| format!("squares.{}.{}", name, square), value, visit);
+            }
+        }
+        visit_tapered_table("mobility.knight", &mut self.knight_mobility, visit);
+        visit_tapered_table("mobility.bishop", &mut self.bishop_mobility, visit);
+        visit_tapered_table("mobility.rook", &mut self.rook_mobility, visit);
+        visit_tapered_table("mobility.queen", &mut self.queen_mobility, visit);
+        visit_tapered_table("mobility.king", &mut self.king_mobility, visit);
+        visit_tapered(
+            &|| "pawns.doubled".to_string(),
+            &mut self.doubled_pawn,
+            visit,
+        );
+        visit_tapered(
+            &|| "pawns.isolated".to_string(),
+            &mut self.isolated_pawn,
+            visit,
+        );
+        visit_tapered(
+            &|| "pawns.backward".to_string(),
+            &mut self.backward_pawn,
+            visit,
+        );
+        visit_tapered_table("pawns.passed", &mut self.passed_pawn, visit);
+        visit_tapered_table("pawns.candidate", &mut self.candidate_pawn, visit);
+        visit_tapered_table("pawns.connected", &mut self.connected_pawn, visit);
+        visit_table("pawns.free_path", &mut self.free_path, visit);
+        visit_table(
+            "pawns.king_distance_weight",
+            &mut self.king_distance_weight,
+            visit,
+        );
+        visit(
+            &|| "pawns.opponent_king_distance".to_string(),
+            &mut self.opponent_king_distance,
+        );
+        visit(
+            &|| "pawns.own_king_distance".to_string(),
+            &mut self.own_king_distance,
+        );
+        visit_table("king_safety.shield", &mut self.pawn_shield, visit);
+        visit_table("king_safety.storm", &mut self.pawn_storm, visit);
+        visit(&|| "king_safety.open_file".to_string(), &mut self.open_file);
+        visit(
+            &|| "king_safety.half_open_file".to_string(),
+            &mut self.half_open_file,
+        );
+        visit_table("king_safety.attack_scale", &mut self.attack_scale, visit);
+        visit(
+            &|| "king_safety.knight_attack".to_string(),
+            &mut self.knight_attack,
+        );
+        visit(
+            &|| "king_safety.bishop_attack".to_string(),
+            &mut self.bishop_attack,
+        );
+        visit(
+            &|| "king_safety.rook_attack".to_string(),
+            &mut self.rook_attack,
+        );
+        visit(
+            &|| "king_safety.queen_attack".to_string(),
+            &mut self.queen_attack,
+        );
+    }
+}
+
+/// The file format, one parameter per line.
+impl fmt::Display for EvalParams {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut result = Ok(());
+        self.clone().visit(&mut |name, value| {
+            if result.is_ok() {
+                result = writeln!(f, "{} {}", name(), value);
+            }
+        });
+        result
+    }
+}
+
+fn get_type_index(typ: Typ) -> usize {
+    TYPES
+        .iter()
+        .position(|(table_typ, _)| *table_typ == typ)
+        .unwrap_or_default()
+}
+
+fn visit_tapered(name: Name, value: &mut Tapered, visit: Visit) {
+    visit(&|| format!("{}.middlegame", name()), &mut value.middlegame);
+    visit(&|| format!("{}.endgame", name()), &mut value.endgame);
+}
+
+fn visit_tapered_table(name: &str, table: &mut [Tapered], visit: Visit) {
+    for (index, value) in table.iter_mut().enumerate() {
+        visit_tapered(&|| format!("{}.{}", name, index), value, visit);
+    }
+}
+
+fn visit_table(name: &str, table: &mut [i32], visit: Visit) {
+    for (index, value) in table.iter_mut().enumerate() {
+        visit(&|| format!("{}.{}", name, index), value);
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,75 @@
+use crate::engine::{
+    evaluation::{
+        params::{EvalParams, ParamsError, DEFAULT_PARAMS},
+        phase::Tapered,
+        Evaluation,
+    },
+    piece::Typ,
+    position::Position,
+};
+
+#[test]
+fn test_text_round_trip() {
+    let mut params = EvalParams::default();
+    params.material[0] = Tapered::new(90, 130);
+    params.squares[1][27] = Tapered::new(-7, 3);
+    params.attack_scale[7] = 100;
+    assert_eq!(EvalParams::parse(&params.to_string()).unwrap(), params);
+}
+
+#[test]
+fn test_parse_keeps_missing_defaults() {
+    let text = "# tuned\nmaterial.knight.middlegame 325\n\nsquares.knight.e4.endgame 12\n";
+    let params = EvalParams::parse(text).unwrap();
+    assert_eq!(params.get_material(Typ::Knight), Tapered::new(325, 290));
+    assert_eq!(params.get_squares(Typ::Knight)[28].endgame, 12);
+    assert_eq!(
+        params.get_material(Typ::Queen),
+        DEFAULT_PARAMS.get_material(Typ::Queen)
+    );
+}
+
+#[test]
+fn test_parse_errors() {
+    assert!(matches!(
+        EvalParams::parse("material.pawn.middlegame 100\nmaterial.pawn.endgame x"),
+        Err(ParamsError::InvalidLine(2))
+    ));
+    assert!(matches!(
+        EvalParams::parse("pawns.tripled 5"),
+        Err(ParamsError::UnknownParameter { line: 1, .. })
+    ));
+}
+
+#[test]
+fn test_values_round_trip() {
+    let mut values = DEFAULT_PARAMS.get_values();
+    assert_eq!(values.len(), DEFAULT_PARAMS.to_string().lines().count());
+    values[0] += 1;
+    let mut params = EvalParams::default();
+    params.set_values(&values);
+    assert_eq!(params.material[0], Tapered::new(101, 120));
+    assert_eq!(params.get_values(), values);
+}
+
+#[test]
+fn test_evaluation_uses_params() {
+    let position = Position::from_fen("4k3/pppp4/8/8/8/8/PPPPP3/4K3 w - - 0 1").unwrap();
+    let mut params = EvalParams::default();
+    params.material[0] = Tapered::new(200, 220);
+    let default_score = Evaluation::new(&position).score;
+    assert_eq!(
+        Evaluation::with_params(&position, &params).score - default_score,
+        100
+    );
+}
+
+#[test]
+fn test_tables_are_mirrored_for_black() {
+    let position = Position::from_fen("4k3/8/2n5/r7/R7/2N5/8/4K3 w - - 0 1").unwrap();
+    let evaluation = Evaluation::new(&position);
+    assert_eq!(
+        evaluation.white_score.squares,
+        evaluation.black_score.squares
+    );
+}
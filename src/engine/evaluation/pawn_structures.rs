@@ -1,110 +1,256 @@
 use crate::engine::{
-    directions::{self, squares::Square, DirectionFn},
-    piece::Piece,
-    position::Position,
+    attacks::pawn_attacks,
+    directions::squares::Square,
+    evaluation::{params::EvalParams, phase::Tapered},
+    piece::{Color, Piece, Typ},
+    position::{bitboard::Bitboard, Position},
 };
-const PAWN_IN_FRONT_SCORE: f32 = -3.0;
-const ISOLATED_PAWN_SCORE: f32 = -4.0;
-const PASSED_PAWN_SCORE: f32 = 15.0;
-
-pub fn count_black(position: &Position) -> f32 {
-    let mut score: f32 = 0.0;
-    score += get_doubled_pawns(position, Piece::BlackPawn);
-    score += get_isolated_pawns(position, Piece::BlackPawn);
-    score += get_passed_pawns(position, Piece::BlackPawn);
-    score
+
+const FILE_A: u64 = 0x0101_0101_0101_0101;
+
+pub(super) const DOUBLED_PAWN_SCORE: Tapered = Tapered::new(-30, -40);
+pub(super) const ISOLATED_PAWN_SCORE: Tapered = Tapered::new(-40, -30);
+// A backward pawn cannot advance safely and no pawn can ever protect it.
+pub(super) const BACKWARD_PAWN_SCORE: Tapered = Tapered::new(-10, -15);
+
+// The tables are indexed by the rank counted from the pawn's own side, starting at 0.
+// Passed pawns decide endgames, while pieces can still stop them easily before.
+pub(super) const PASSED_PAWN_SCORE: [Tapered; 8] = [
+    Tapered::new(0, 0),
+    Tapered::new(5, 10),
+    Tapered::new(10, 20),
+    Tapered::new(15, 35),
+    Tapered::new(30, 60),
+    Tapered::new(50, 100),
+    Tapered::new(80, 150),
+    Tapered::new(0, 0),
+];
+/// Extra endgame bonus for a passed pawn with no piece in front of it.
+pub(super) const FREE_PATH_SCORE: [i32; 8] = [0, 0, 5, 10, 20, 35, 60, 0];
+/// Endgame weight of the kings' distances to the square in front of a passed pawn.
+pub(super) const KING_DISTANCE_WEIGHT: [i32; 8] = [0, 0, 0, 1, 2, 3, 5, 0];
+pub(super) const OPPONENT_KING_DISTANCE_SCORE: i32 = 5;
+pub(super) const OWN_KING_DISTANCE_SCORE: i32 = -2;
+pub(super) const CANDIDATE_PAWN_SCORE: [Tapered; 8] = [
+    Tapered::new(0, 0),
+    Tapered::new(3, 5),
+    Tapered::new(5, 10),
+    Tapered::new(10, 20),
+    Tapered::new(20, 35),
+    Tapered::new(30, 50),
+    Tapered::new(0, 0),
+    Tapered::new(0, 0),
+];
+/// For pawns side by side or protected by another pawn.
+pub(super) const CONNECTED_PAWN_SCORE: [Tapered; 8] = [
+    Tapered::new(0, 0),
+    Tapered::new(3, 2),
+    Tapered::new(5, 3),
+    Tapered::new(8, 6),
+    Tapered::new(15, 12),
+    Tapered::new(25, 25),
+    Tapered::new(40, 40),
+    Tapered::new(0, 0),
+];
+
+/// The part of one side's pawn evaluation that depends on nothing but the pawns, so it
+/// can be cached by the pawn key.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PawnStructure {
+    pub score: Tapered,
+    pub passed: Bitboard,
+    /// The squares the pawns attack.
+    pub attacks: Bitboard,
 }
-pub fn count_white(position: &Position) -> f32 {
-    let mut score: f32 = 0.0;
-    score += get_doubled_pawns(position, Piece::WhitePawn);
-    score += get_isolated_pawns(position, Piece::WhitePawn);
-    score += get_passed_pawns(position, Piece::WhitePawn);
-    score
+
+/// The pawn structure score of `color`, given its cached part.
+pub fn count(
+    position: &Position,
+    params: &EvalParams,
+    color: Color,
+    structure: PawnStructure,
+) -> Tapered {
+    structure.score + get_passed_pawn_score(position, params, color, structure.passed)
 }
 
-pub fn get_doubled_pawns(position: &Position, piece: Piece) -> f32 {
-    let mut score = 0.0;
-    for mut square in position.get_squares(piece).iter() {
-        let mut has_pawn_in_front = false;
-
-        while let Some(front_square) = directions::up(square) {
-            if position.is_occupied_by_piece(front_square, piece) {
-                has_pawn_in_front = true;
-                break;
-            }
-            square = front_square;
+pub fn evaluate(position: &Position, params: &EvalParams, color: Color) -> PawnStructure {
+    let pawns = Pawns::new(position, color);
+    let mut structure = PawnStructure::default();
+    for square in pawns.own.iter() {
+        let rank = get_relative_rank(color, square) as usize;
+        structure.attacks = structure.attacks | pawn_attacks(color, square);
+        if pawns.is_doubled(square) {
+            structure.score += params.doubled_pawn;
         }
-        if has_pawn_in_front {
-            score += PAWN_IN_FRONT_SCORE;
+        if pawns.is_isolated(square) {
+            structure.score += params.isolated_pawn;
+        } else if pawns.is_backward(square) {
+            structure.score += params.backward_pawn;
         }
-    }
-    score
-}
-pub fn get_isolated_pawns(position: &Position, pawn: Piece) -> f32 {
-    let mut score: f32 = 0.0;
-    let mut columns: u32 = 0;
-    for square in position.get_squares(pawn).iter() {
-        columns |= 1 << (directions::get_column(square))
-    }
-
-    for col in 1..9 {
-        let left_column_has_pawn = (columns & (1 << (col - 1))) != 0;
-        let center_column_has_pawn = (columns & (1 << col)) != 0;
-        let right_column_has_pawn = (columns & (1 << (col + 1))) != 0;
-        if !left_column_has_pawn && center_column_has_pawn && !right_column_has_pawn {
-            score += ISOLATED_PAWN_SCORE;
+        if pawns.is_connected(square) {
+            structure.score += params.connected_pawn[rank];
+        }
+        if pawns.is_passed(square) {
+            structure.passed.set_bit(square);
+            structure.score += params.passed_pawn[rank];
+        } else if pawns.is_candidate(square) {
+            structure.score += params.candidate_pawn[rank];
         }
     }
-    score
+    structure
 }
-fn has_piece_in_direction(
+
+/// The part of the passed pawns' value that depends on the pieces around them, only
+/// counted in the endgame.
+fn get_passed_pawn_score(
     position: &Position,
-    square: Square,
-    direction_fn: DirectionFn,
-    piece: Piece,
-) -> bool {
-    if let Some(square) = direction_fn(square) {
-        if position.is_occupied_by_piece(square, piece) {
-            return true;
-        } else {
-            return has_piece_in_direction(position, square, direction_fn, piece);
+    params: &EvalParams,
+    color: Color,
+    passed: Bitboard,
+) -> Tapered {
+    let kings = (
+        position
+            .get_squares(Piece::new(color, Typ::King))
+            .iter()
+            .next(),
+        position
+            .get_squares(Piece::new(color.get_opponent_color(), Typ::King))
+            .iter()
+            .next(),
+    );
+    let mut endgame = 0;
+    for square in passed.iter() {
+        let rank = get_relative_rank(color, square) as usize;
+        if (get_front_span(color, square) & position.get_all()).is_empty() {
+            endgame += params.free_path[rank];
+        }
+        let stop = get_stop_square(color, square);
+        if let (Some(stop), (Some(own_king), Some(opponent_king))) = (stop, kings) {
+            endgame += params.king_distance_weight[rank]
+                * (params.opponent_king_distance * get_distance(opponent_king, stop)
+                    + params.own_king_distance * get_distance(own_king, stop));
         }
     }
-    false
+    Tapered::new(0, endgame)
+}
+
+/// The pawns of both sides as seen by `color`.
+pub(super) struct Pawns {
+    color: Color,
+    pub(super) own: Bitboard,
+    opponent: Bitboard,
 }
 
-pub fn get_passed_pawns(position: &Position, pawn: Piece) -> f32 {
-    let mut score = 0.0;
-    let opponent_pawn = match pawn.get_color() {
-        crate::engine::piece::Color::Black => Piece::WhitePawn,
-        crate::engine::piece::Color::White => Piece::BlackPawn,
-    };
-
-    let direction_fn = match pawn.get_color() {
-        crate::engine::piece::Color::Black => directions::down,
-        crate::engine::piece::Color::White => directions::up,
-    };
-
-    for square in position.get_squares(pawn).iter() {
-        let mut has_pawn_in_front_on_left_column = false;
-        let mut has_pawn_in_front_on_right_column = false;
-
-        if let Some(left) = directions::left(square) {
-            has_pawn_in_front_on_left_column =
-                has_piece_in_direction(position, left, direction_fn, opponent_pawn);
+impl Pawns {
+    pub(super) fn new(position: &Position, color: Color) -> Pawns {
+        Pawns {
+            color,
+            own: position.get_squares(Piece::new(color, Typ::Pawn)),
+            opponent: position.get_squares(Piece::new(color.get_opponent_color(), Typ::Pawn)),
         }
-        if let Some(right) = directions::right(square) {
-            has_pawn_in_front_on_right_column =
-                has_piece_in_direction(position, right, direction_fn, opponent_pawn);
+    }
+
+    /// Another pawn of the same color stands in front.
+    pub(super) fn is_doubled(&self, square: Square) -> bool {
+        !(get_front_span(self.color, square) & self.own).is_empty()
+    }
+
+    /// No pawn of the same color on a neighbouring file.
+    pub(super) fn is_isolated(&self, square: Square) -> bool {
+        (get_adjacent_files(square) & self.own).is_empty()
+    }
+
+    /// The pawns on the neighbouring files have all advanced further and an opposing
+    /// pawn controls the square in front.
+    pub(super) fn is_backward(&self, square: Square) -> bool {
+        let supporters = get_adjacent_files(square) & !get_forward_ranks(self.color, square);
+        if !(supporters & self.own).is_empty() {
+            return false;
         }
-        let has_pawn_in_front_on_column =
-            has_piece_in_direction(position, square, direction_fn, opponent_pawn);
-        if !has_pawn_in_front_on_column
-            && !has_pawn_in_front_on_left_column
-            && !has_pawn_in_front_on_right_column
-        {
-            score += PASSED_PAWN_SCORE;
+        get_stop_square(self.color, square)
+            .is_some_and(|stop| !(pawn_attacks(self.color, stop) & self.opponent).is_empty())
+    }
+
+    /// Protected by a pawn of the same color or standing beside one.
+    pub(super) fn is_connected(&self, square: Square) -> bool {
+        let protectors = pawn_attacks(self.color.get_opponent_color(), square);
+        let phalanx = get_adjacent_files(square) & get_rank(square);
+        !((protectors | phalanx) & self.own).is_empty()
+    }
+
+    /// No opposing pawn can block or capture it on its way.
+    pub(super) fn is_passed(&self, square: Square) -> bool {
+        let span = get_front_span(self.color, square) | get_attack_span(self.color, square);
+        (span & self.opponent).is_empty()
+    }
+
+    /// Not passed yet, but nothing blocks it and its neighbours outnumber the opposing
+    /// pawns that guard its way.
+    pub(super) fn is_candidate(&self, square: Square) -> bool {
+        if !(get_front_span(self.color, square) & self.opponent).is_empty() {
+            return false;
         }
+        let sentries = get_attack_span(self.color, square) & self.opponent;
+        let helpers =
+            get_adjacent_files(square) & !get_forward_ranks(self.color, square) & self.own;
+        helpers.count_ones() >= sentries.count_ones()
+    }
+}
+
+/// The rank counted from `color`'s side of the board, starting at 0.
+pub fn get_relative_rank(color: Color, square: Square) -> u32 {
+    match color {
+        Color::White => square.as_index() / 8,
+        Color::Black => 7 - square.as_index() / 8,
     }
-    score
+}
+
+pub fn get_file(file: u32) -> Bitboard {
+    Bitboard::from_inner(FILE_A << file)
+}
+
+fn get_rank(square: Square) -> Bitboard {
+    Bitboard::from_inner(0xff << (square.as_index() / 8 * 8))
+}
+
+fn get_adjacent_files(square: Square) -> Bitboard {
+    let file = square.as_index() % 8;
+    let left = if file > 0 { FILE_A << (file - 1) } else { 0 };
+    let right = if file < 7 { FILE_A << (file + 1) } else { 0 };
+    Bitboard::from_inner(left | right)
+}
+
+/// All ranks in front of `square` from `color`'s point of view.
+fn get_forward_ranks(color: Color, square: Square) -> Bitboard {
+    let rank = square.as_index() / 8;
+    Bitboard::from_inner(match color {
+        Color::White if rank < 7 => !0 << ((rank + 1) * 8),
+        Color::White => 0,
+        Color::Black => (1 << (rank * 8)) - 1,
+    })
+}
+
+/// The square a pawn of `color` on `square` advances to.
+fn get_stop_square(color: Color, square: Square) -> Option<Square> {
+    match color {
+        Color::White => Square::new(square.as_index() + 8),
+        Color::Black => square.as_index().checked_sub(8).and_then(Square::new),
+    }
+}
+
+/// The squares in front of `square` on its file.
+fn get_front_span(color: Color, square: Square) -> Bitboard {
+    get_file(square.as_index() % 8) & get_forward_ranks(color, square)
+}
+
+/// The squares in front of `square` on the neighbouring files, where opposing pawns could
+/// capture it.
+fn get_attack_span(color: Color, square: Square) -> Bitboard {
+    get_adjacent_files(square) & get_forward_ranks(color, square)
+}
+
+/// The number of king moves between two squares.
+fn get_distance(from: Square, to: Square) -> i32 {
+    let (from, to) = (from.as_index() as i32, to.as_index() as i32);
+    ((from % 8 - to % 8).abs()).max((from / 8 - to / 8).abs())
 }
@@ -0,0 +1,86 @@
+use crate::engine::{
+    evaluation::{
+        params::EvalParams,
+        pawn_structures::{self, PawnStructure},
+    },
+    piece::Color,
+    position::Position,
+    transposition_table::TableStats,
+};
+
+/// Far fewer pawn structures than positions occur in a search, so a small table suffices.
+pub const DEFAULT_PAWN_TABLE_ENTRIES: usize = 1 << 14;
+
+/// The pawn structures of both sides.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PawnEntry {
+    pub white: PawnStructure,
+    pub black: PawnStructure,
+}
+
+impl PawnEntry {
+    pub fn new(position: &Position, params: &EvalParams) -> PawnEntry {
+        PawnEntry {
+            white: pawn_structures::evaluate(position, params, Color::White),
+            black: pawn_structures::evaluate(position, params, Color::Black),
+        }
+    }
+}
+
+/// Caches pawn structures by the pawn key. Every search thread has its own table, which
+/// must always be probed with the same parameters.
+pub struct PawnTable {
+    entries: Vec<Option<(u64, PawnEntry)>>,
+    stats: TableStats,
+}
+
+impl Default for PawnTable {
+    fn default() -> Self {
+        PawnTable::new(DEFAULT_PAWN_TABLE_ENTRIES)
+    }
+}
+
+impl PawnTable {
+    pub fn new(entries: usize) -> Self {
+        PawnTable {
+            entries: vec![None; entries.max(1)],
+            stats: TableStats::default(),
+        }
+    }
+
+    /// The pawn structures of `position`, evaluated and stored unless cached already.
+    pub fn probe(&mut self, position: &Position, params: &EvalParams) -> PawnEntry {
+        let key = position.get_pawn_key();
+        let index = (key % self.entries.len() as u64) as usize;
+        self.stats.probes += 1;
+        match self.entries[index] {
+            Some((entry_key, entry)) if entry_key == key => {
+                self.stats.hits += 1;
+                entry
+            }
+            old_entry => {
+                let entry = PawnEntry::new(position, params);
+                self.stats.stores += 1;
+                if old_entry.is_some() {
+                    self.stats.overwrites += 1;
+                }
+                self.entries[index] = Some((key, entry));
+                entry
+            }
+        }
+    }
+
+    pub fn get_stats(&self) -> TableStats {
+        self.stats
+    }
+
+    /// Share of probes answered from the table in permille.
+    pub fn get_hit_permille(&self) -> u32 {
+        (self.stats.hits * 1000)
+            .checked_div(self.stats.probes)
+            .unwrap_or(0) as u32
+    }
+}
+
+#[cfg(test)]
+mod tests;
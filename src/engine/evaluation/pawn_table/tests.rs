@@ -0,0 +1,61 @@
+use crate::engine::{
+    directions::squares::*,
+    evaluation::{
+        params::DEFAULT_PARAMS,
+        pawn_table::{PawnEntry, PawnTable},
+        Evaluation,
+    },
+    piece::Piece,
+    position::Position,
+    transposition_table::TableStats,
+};
+
+#[test]
+fn test_same_pawns_hit() {
+    let mut table = PawnTable::new(64);
+    let position = Position::new_starting_position();
+    let entry = table.probe(&position, &DEFAULT_PARAMS);
+    assert_eq!(entry, PawnEntry::new(&position, &DEFAULT_PARAMS));
+    let developed = position.remove_piece(G1).put_piece(Piece::WhiteKnight, F3);
+    assert_eq!(table.probe(&developed, &DEFAULT_PARAMS), entry);
+    assert_eq!(
+        table.get_stats(),
+        TableStats {
+            probes: 2,
+            hits: 1,
+            stores: 1,
+            overwrites: 0,
+        }
+    );
+    assert_eq!(table.get_hit_permille(), 500);
+}
+
+#[test]
+fn test_cached_evaluation_matches() {
+    let mut table = PawnTable::new(1);
+    let fens = [
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+    ];
+    for fen in fens {
+        let position = Position::from_fen(fen).unwrap();
+        assert_eq!(
+            Evaluation::with_pawn_table(&position, &DEFAULT_PARAMS, &mut table),
+            Evaluation::new(&position)
+        );
+    }
+    // A single entry, so the second position replaced the first.
+    assert_eq!(table.get_stats().overwrites, 1);
+    assert_eq!(table.get_stats().hits, 1);
+}
+
+#[test]
+fn test_passed_pawns_and_attacks_are_cached() {
+    let position = Position::from_fen("4k3/8/8/8/3P4/8/p7/4K3 w - - 0 1").unwrap();
+    let entry = PawnEntry::new(&position, &DEFAULT_PARAMS);
+    assert!(entry.white.passed.contains(D4));
+    assert!(entry.black.passed.contains(A2));
+    assert!(entry.white.attacks.contains(C5) && entry.white.attacks.contains(E5));
+    assert!(entry.black.attacks.contains(B1));
+}
@@ -0,0 +1,90 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+
+use crate::engine::{
+    piece::{Color, Piece, Typ},
+    position::Position,
+};
+
+/// The phase with all minor and major pieces on the board. Promotions can exceed it.
+pub const MIDDLEGAME_PHASE: i32 = 24;
+
+/// A value for the middlegame and one for the endgame, in centipawns.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Tapered {
+    pub middlegame: i32,
+    pub endgame: i32,
+}
+
+impl Tapered {
+    pub const fn new(middlegame: i32, endgame: i32) -> Tapered {
+        Tapered {
+            middlegame,
+            endgame,
+        }
+    }
+
+    /// Interpolates between both values, `phase` runs from 0 in the endgame to
+    /// `MIDDLEGAME_PHASE`.
+    pub fn blend(self, phase: i32) -> i32 {
+        let phase = phase.clamp(0, MIDDLEGAME_PHASE);
+        (self.middlegame * phase + self.endgame * (MIDDLEGAME_PHASE - phase)) / MIDDLEGAME_PHASE
+    }
+}
+
+impl Add for Tapered {
+    type Output = Tapered;
+
+    fn add(self, other: Tapered) -> Tapered {
+        Tapered::new(
+            self.middlegame + other.middlegame,
+            self.endgame + other.endgame,
+        )
+    }
+}
+
+impl AddAssign for Tapered {
+    fn add_assign(&mut self, other: Tapered) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Tapered {
+    type Output = Tapered;
+
+    fn sub(self, other: Tapered) -> Tapered {
+        self + -other
+    }
+}
+
+impl Neg for Tapered {
+    type Output = Tapered;
+
+    fn neg(self) -> Tapered {
+        Tapered::new(-self.middlegame, -self.endgame)
+    }
+}
+
+impl Mul<i32> for Tapered {
+    type Output = Tapered;
+
+    fn mul(self, factor: i32) -> Tapered {
+        Tapered::new(self.middlegame * factor, self.endgame * factor)
+    }
+}
+
+/// How far the game is from the endgame, judged by the minor and major pieces left.
+/// Pawns don't count, a pawn ending is an endgame however many there are.
+pub fn get_phase(position: &Position) -> i32 {
+    let mut phase = 0;
+    for color in [Color::White, Color::Black] {
+        for (typ, weight) in [
+            (Typ::Knight, 1),
+            (Typ::Bishop, 1),
+            (Typ::Rook, 2),
+            (Typ::Queen, 4),
+        ] {
+            phase += weight * position.count_pieces(Piece::new(color, typ)) as i32;
+        }
+    }
+    phase.min(MIDDLEGAME_PHASE)
+}
@@ -1,206 +1,217 @@
-use once_cell::sync::Lazy;
-
-use crate::engine::{directions::squares::Square, piece::*, position::Position};
-
-type PieceSquareRow = [f32; 8];
-
-const WHITE_PAWN_ROW_1: PieceSquareRow = [0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
-const WHITE_PAWN_ROW_2: PieceSquareRow = [0.5, 1.0, 1.0, -2.0, -2.0, 1.0, 1.0, 0.5];
-const WHITE_PAWN_ROW_3: PieceSquareRow = [0.5, -0.5, -1.0, 0.0, 0.0, -1.0, -0.5, 0.5];
-const WHITE_PAWN_ROW_4: PieceSquareRow = [0.0, 0.0, 0.0, 2.0, 2.0, 0.0, 0.0, 0.0];
-const WHITE_PAWN_ROW_5: PieceSquareRow = [0.5, 0.5, 1.0, 2.5, 2.5, 1.0, 0.5, 0.5];
-const WHITE_PAWN_ROW_6: PieceSquareRow = [1.0, 1.0, 2.0, 3.0, 3.0, 2.0, 1.0, 1.0];
-const WHITE_PAWN_ROW_7: PieceSquareRow = [5.0, 5.0, 5.0, 5.0, 5.0, 5.0, 5.0, 5.0];
-const WHITE_PAWN_ROW_8: PieceSquareRow = [0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
-
-const KNIGHT_ROW_8: PieceSquareRow = [-5.0, -4.0, -3.0, -3.0, -3.0, -3.0, -4.0, -5.0];
-const KNIGHT_ROW_7: PieceSquareRow = [-4.0, -2.0, 0.0, 0.0, 0.0, 0.0, -2.0, -4.0];
-const KNIGHT_ROW_6: PieceSquareRow = [-3.0, 0.0, 1.0, 1.5, 1.5, 1.0, 0.0, -3.0];
-const KNIGHT_ROW_5: PieceSquareRow = [-3.0, 0.5, 1.5, 2.0, 2.0, 1.5, 0.5, -3.0];
-const KNIGHT_ROW_4: PieceSquareRow = [-3.0, 0.0, 1.5, 2.0, 2.0, 1.5, 0.0, -3.0];
-const KNIGHT_ROW_3: PieceSquareRow = [-3.0, 0.5, 1.0, 1.5, 1.5, 1.0, 0.5, -3.0];
-const KNIGHT_ROW_2: PieceSquareRow = [-4.0, -2.0, 0.0, 0.5, 0.5, 0.0, -2.0, -4.0];
-const KNIGHT_ROW_1: PieceSquareRow = [-5.0, -4.0, -3.0, -3.0, -3.0, -3.0, -4.0, -5.0];
-
-const BISHOP_ROW_8: PieceSquareRow = [-2.0, -1.0, -1.0, -1.0, -1.0, -1.0, -1.0, -2.0];
-const BISHOP_ROW_7: PieceSquareRow = [-1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, -1.0];
-const BISHOP_ROW_6: PieceSquareRow = [-1.0, 0.0, 0.5, 1.0, 1.0, 0.5, 0.0, -1.0];
-const BISHOP_ROW_5: PieceSquareRow = [-1.0, 0.0, 1.0, 1.0, 1.0, 1.0, 0.0, -1.0];
-const BISHOP_ROW_4: PieceSquareRow = [-1.0, 0.0, 1.0, 1.0, 1.0, 1.0, 0.0, -1.0];
-const BISHOP_ROW_3: PieceSquareRow = [-1.0, 0.0, 0.5, 1.0, 1.0, 0.5, 0.0, -1.0];
-const BISHOP_ROW_2: PieceSquareRow = [-1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, -1.0];
-const BISHOP_ROW_1: PieceSquareRow = [-2.0, -1.0, -1.0, -1.0, -1.0, -1.0, -1.0, -2.0];
-
-const ROOK_ROW_8: PieceSquareRow = [0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
-const ROOK_ROW_7: PieceSquareRow = [0.5, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 0.5];
-const ROOK_ROW_6: PieceSquareRow = [-0.5, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, -0.5];
-const ROOK_ROW_5: PieceSquareRow = [-0.5, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, -0.5];
-const ROOK_ROW_4: PieceSquareRow = [-0.5, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, -0.5];
-const ROOK_ROW_3: PieceSquareRow = [-0.5, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, -0.5];
-const ROOK_ROW_2: PieceSquareRow = [-0.5, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, -0.5];
-const ROOK_ROW_1: PieceSquareRow = [0.0, 0.0, 0.0, 0.5, 0.5, 0.0, 0.0, 0.0];
-
-const QUEEN_ROW_8: PieceSquareRow = [-2.0, -1.0, -1.0, -0.5, -0.5, -1.0, -1.0, -2.0];
-const QUEEN_ROW_7: PieceSquareRow = [-1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, -1.0];
-const QUEEN_ROW_6: PieceSquareRow = [-1.0, 0.0, 0.5, 0.5, 0.5, 0.5, 0.0, -1.0];
-const QUEEN_ROW_5: PieceSquareRow = [-0.5, 0.0, 0.5, 0.5, 0.5, 0.5, 0.0, -0.5];
-const QUEEN_ROW_4: PieceSquareRow = [-0.5, 0.0, 0.5, 0.5, 0.5, 0.5, 0.0, -0.5];
-const QUEEN_ROW_3: PieceSquareRow = [-1.0, 0.0, 0.5, 0.5, 0.5, 0.5, 0.0, -1.0];
-const QUEEN_ROW_2: PieceSquareRow = [-1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, -1.0];
-const QUEEN_ROW_1: PieceSquareRow = [-2.0, -1.0, -1.0, -0.5, -0.5, -1.0, -1.0, -2.0];
-
-const WHITE_KING_ROW_8: PieceSquareRow = [-3.0, -4.0, -4.0, -5.0, -5.0, -4.0, -4.0, -3.0];
-const WHITE_KING_ROW_7: PieceSquareRow = [-3.0, -4.0, -4.0, -5.0, -5.0, -4.0, -4.0, -3.0];
-const WHITE_KING_ROW_6: PieceSquareRow = [-3.0, -4.0, -4.0, -5.0, -5.0, -4.0, -4.0, -3.0];
-const WHITE_KING_ROW_5: PieceSquareRow = [-3.0, -4.0, -4.0, -5.0, -5.0, -4.0, -4.0, -3.0];
-const WHITE_KING_ROW_4: PieceSquareRow = [-2.0, -3.0, -4.0, -4.0, -4.0, -4.0, -3.0, -2.0];
-const WHITE_KING_ROW_3: PieceSquareRow = [-1.0, -2.0, -2.0, -2.0, -2.0, -2.0, -2.0, -1.0];
-const WHITE_KING_ROW_2: PieceSquareRow = [2.0, 2.0, 0.0, 0.0, 0.0, 0.0, 2.0, 2.0];
-const WHITE_KING_ROW_1: PieceSquareRow = [2.0, 3.0, 1.0, 0.0, 0.0, 1.0, 3.0, 2.0];
-
-static WHITE_PAWN_TABLE: Lazy<Vec<f32>> = Lazy::new(|| {
-    [
-        WHITE_PAWN_ROW_1,
-        WHITE_PAWN_ROW_2,
-        WHITE_PAWN_ROW_3,
-        WHITE_PAWN_ROW_4,
-        WHITE_PAWN_ROW_5,
-        WHITE_PAWN_ROW_6,
-        WHITE_PAWN_ROW_7,
-        WHITE_PAWN_ROW_8,
-    ]
-    .concat()
-});
-
-static BLACK_PAWN_TABLE: Lazy<Vec<f32>> = Lazy::new(|| {
-    [
-        WHITE_PAWN_ROW_8,
-        WHITE_PAWN_ROW_7,
-        WHITE_PAWN_ROW_6,
-        WHITE_PAWN_ROW_5,
-        WHITE_PAWN_ROW_4,
-        WHITE_PAWN_ROW_3,
-        WHITE_PAWN_ROW_2,
-        WHITE_PAWN_ROW_1,
-    ]
-    .concat()
-});
-
-static KNIGHT_TABLE: Lazy<Vec<f32>> = Lazy::new(|| {
-    [
-        KNIGHT_ROW_1,
-        KNIGHT_ROW_2,
-        KNIGHT_ROW_3,
-        KNIGHT_ROW_4,
-        KNIGHT_ROW_5,
-        KNIGHT_ROW_6,
-        KNIGHT_ROW_7,
-        KNIGHT_ROW_8,
-    ]
-    .concat()
-});
-
-static QUEEN_TABLE: Lazy<Vec<f32>> = Lazy::new(|| {
-    [
-        QUEEN_ROW_1,
-        QUEEN_ROW_2,
-        QUEEN_ROW_3,
-        QUEEN_ROW_4,
-        QUEEN_ROW_5,
-        QUEEN_ROW_6,
-        QUEEN_ROW_7,
-        QUEEN_ROW_8,
-    ]
-    .concat()
-});
-
-static BISHOP_TABLE: Lazy<Vec<f32>> = Lazy::new(|| {
-    [
-        BISHOP_ROW_1,
-        BISHOP_ROW_2,
-        BISHOP_ROW_3,
-        BISHOP_ROW_4,
-        BISHOP_ROW_5,
-        BISHOP_ROW_6,
-        BISHOP_ROW_7,
-        BISHOP_ROW_8,
-    ]
-    .concat()
-});
-
-static ROOK_TABLE: Lazy<Vec<f32>> = Lazy::new(|| {
-    [
-        ROOK_ROW_1, ROOK_ROW_2, ROOK_ROW_3, ROOK_ROW_4, ROOK_ROW_5, ROOK_ROW_6, ROOK_ROW_7,
-        ROOK_ROW_8,
-    ]
-    .concat()
-});
-
-static WHITE_KING_TABLE: Lazy<Vec<f32>> = Lazy::new(|| {
-    [
-        WHITE_KING_ROW_1,
-        WHITE_KING_ROW_2,
-        WHITE_KING_ROW_3,
-        WHITE_KING_ROW_4,
-        WHITE_KING_ROW_5,
-        WHITE_KING_ROW_6,
-        WHITE_KING_ROW_7,
-        WHITE_KING_ROW_8,
-    ]
-    .concat()
-});
-
-static BLACK_KING_TABLE: Lazy<Vec<f32>> = Lazy::new(|| {
-    [
-        WHITE_KING_ROW_8,
-        WHITE_KING_ROW_7,
-        WHITE_KING_ROW_6,
-        WHITE_KING_ROW_5,
-        WHITE_KING_ROW_4,
-        WHITE_KING_ROW_3,
-        WHITE_KING_ROW_2,
-        WHITE_KING_ROW_1,
-    ]
-    .concat()
-});
-
-fn get_table(piece: Piece) -> &'static once_cell::sync::Lazy<Vec<f32>> {
-    match piece.get_type() {
-        crate::engine::piece::Typ::Knight => &KNIGHT_TABLE,
-        crate::engine::piece::Typ::Bishop => &BISHOP_TABLE,
-        crate::engine::piece::Typ::Queen => &QUEEN_TABLE,
-        crate::engine::piece::Typ::Rook => &ROOK_TABLE,
-        crate::engine::piece::Typ::Pawn => match piece.get_color() {
-            crate::engine::piece::Color::Black => &BLACK_PAWN_TABLE,
-            crate::engine::piece::Color::White => &WHITE_PAWN_TABLE,
-        },
-        crate::engine::piece::Typ::King => match piece.get_color() {
-            crate::engine::piece::Color::Black => &BLACK_KING_TABLE,
-            crate::engine::piece::Color::White => &WHITE_KING_TABLE,
-        },
+use crate::engine::{
+    directions::squares::Square,
+    evaluation::{params::EvalParams, phase::Tapered},
+    piece::*,
+    position::Position,
+};
+
+type PieceSquareRow = [i32; 8];
+
+const WHITE_PAWN_ROW_1: PieceSquareRow = [0, 0, 0, 0, 0, 0, 0, 0];
+const WHITE_PAWN_ROW_2: PieceSquareRow = [5, 10, 10, -20, -20, 10, 10, 5];
+const WHITE_PAWN_ROW_3: PieceSquareRow = [5, -5, -10, 0, 0, -10, -5, 5];
+const WHITE_PAWN_ROW_4: PieceSquareRow = [0, 0, 0, 20, 20, 0, 0, 0];
+const WHITE_PAWN_ROW_5: PieceSquareRow = [5, 5, 10, 25, 25, 10, 5, 5];
+const WHITE_PAWN_ROW_6: PieceSquareRow = [10, 10, 20, 30, 30, 20, 10, 10];
+const WHITE_PAWN_ROW_7: PieceSquareRow = [50, 50, 50, 50, 50, 50, 50, 50];
+const WHITE_PAWN_ROW_8: PieceSquareRow = [0, 0, 0, 0, 0, 0, 0, 0];
+
+const KNIGHT_ROW_8: PieceSquareRow = [-50, -40, -30, -30, -30, -30, -40, -50];
+const KNIGHT_ROW_7: PieceSquareRow = [-40, -20, 0, 0, 0, 0, -20, -40];
+const KNIGHT_ROW_6: PieceSquareRow = [-30, 0, 10, 15, 15, 10, 0, -30];
+const KNIGHT_ROW_5: PieceSquareRow = [-30, 5, 15, 20, 20, 15, 5, -30];
+const KNIGHT_ROW_4: PieceSquareRow = [-30, 0, 15, 20, 20, 15, 0, -30];
+const KNIGHT_ROW_3: PieceSquareRow = [-30, 5, 10, 15, 15, 10, 5, -30];
+const KNIGHT_ROW_2: PieceSquareRow = [-40, -20, 0, 5, 5, 0, -20, -40];
+const KNIGHT_ROW_1: PieceSquareRow = [-50, -40, -30, -30, -30, -30, -40, -50];
+
+const BISHOP_ROW_8: PieceSquareRow = [-20, -10, -10, -10, -10, -10, -10, -20];
+const BISHOP_ROW_7: PieceSquareRow = [-10, 0, 0, 0, 0, 0, 0, -10];
+const BISHOP_ROW_6: PieceSquareRow = [-10, 0, 5, 10, 10, 5, 0, -10];
+const BISHOP_ROW_5: PieceSquareRow = [-10, 0, 10, 10, 10, 10, 0, -10];
+const BISHOP_ROW_4: PieceSquareRow = [-10, 0, 10, 10, 10, 10, 0, -10];
+const BISHOP_ROW_3: PieceSquareRow = [-10, 0, 5, 10, 10, 5, 0, -10];
+const BISHOP_ROW_2: PieceSquareRow = [-10, 0, 0, 0, 0, 0, 0, -10];
+const BISHOP_ROW_1: PieceSquareRow = [-20, -10, -10, -10, -10, -10, -10, -20];
+
+const ROOK_ROW_8: PieceSquareRow = [0, 0, 0, 0, 0, 0, 0, 0];
+const ROOK_ROW_7: PieceSquareRow = [5, 10, 10, 10, 10, 10, 10, 5];
+const ROOK_ROW_6: PieceSquareRow = [-5, 0, 0, 0, 0, 0, 0, -5];
+const ROOK_ROW_5: PieceSquareRow = [-5, 0, 0, 0, 0, 0, 0, -5];
+const ROOK_ROW_4: PieceSquareRow = [-5, 0, 0, 0, 0, 0, 0, -5];
+const ROOK_ROW_3: PieceSquareRow = [-5, 0, 0, 0, 0, 0, 0, -5];
+const ROOK_ROW_2: PieceSquareRow = [-5, 0, 0, 0, 0, 0, 0, -5];
+const ROOK_ROW_1: PieceSquareRow = [0, 0, 0, 5, 5, 0, 0, 0];
+
+const QUEEN_ROW_8: PieceSquareRow = [-20, -10, -10, -5, -5, -10, -10, -20];
+const QUEEN_ROW_7: PieceSquareRow = [-10, 0, 0, 0, 0, 0, 0, -10];
+const QUEEN_ROW_6: PieceSquareRow = [-10, 0, 5, 5, 5, 5, 0, -10];
+const QUEEN_ROW_5: PieceSquareRow = [-5, 0, 5, 5, 5, 5, 0, -5];
+const QUEEN_ROW_4: PieceSquareRow = [-5, 0, 5, 5, 5, 5, 0, -5];
+const QUEEN_ROW_3: PieceSquareRow = [-10, 0, 5, 5, 5, 5, 0, -10];
+const QUEEN_ROW_2: PieceSquareRow = [-10, 0, 0, 0, 0, 0, 0, -10];
+const QUEEN_ROW_1: PieceSquareRow = [-20, -10, -10, -5, -5, -10, -10, -20];
+
+const WHITE_KING_ROW_8: PieceSquareRow = [-30, -40, -40, -50, -50, -40, -40, -30];
+const WHITE_KING_ROW_7: PieceSquareRow = [-30, -40, -40, -50, -50, -40, -40, -30];
+const WHITE_KING_ROW_6: PieceSquareRow = [-30, -40, -40, -50, -50, -40, -40, -30];
+const WHITE_KING_ROW_5: PieceSquareRow = [-30, -40, -40, -50, -50, -40, -40, -30];
+const WHITE_KING_ROW_4: PieceSquareRow = [-20, -30, -40, -40, -40, -40, -30, -20];
+const WHITE_KING_ROW_3: PieceSquareRow = [-10, -20, -20, -20, -20, -20, -20, -10];
+const WHITE_KING_ROW_2: PieceSquareRow = [20, 20, 0, 0, 0, 0, 20, 20];
+const WHITE_KING_ROW_1: PieceSquareRow = [20, 30, 10, 0, 0, 10, 30, 20];
+
+// In the endgame pawns gain the closer they get to promotion.
+const WHITE_PAWN_ENDGAME_ROW_1: PieceSquareRow = [0, 0, 0, 0, 0, 0, 0, 0];
+const WHITE_PAWN_ENDGAME_ROW_2: PieceSquareRow = [0, 0, 0, 0, 0, 0, 0, 0];
+const WHITE_PAWN_ENDGAME_ROW_3: PieceSquareRow = [5, 5, 5, 5, 5, 5, 5, 5];
+const WHITE_PAWN_ENDGAME_ROW_4: PieceSquareRow = [15, 15, 15, 15, 15, 15, 15, 15];
+const WHITE_PAWN_ENDGAME_ROW_5: PieceSquareRow = [30, 30, 30, 30, 30, 30, 30, 30];
+const WHITE_PAWN_ENDGAME_ROW_6: PieceSquareRow = [50, 50, 50, 50, 50, 50, 50, 50];
+const WHITE_PAWN_ENDGAME_ROW_7: PieceSquareRow = [80, 80, 80, 80, 80, 80, 80, 80];
+const WHITE_PAWN_ENDGAME_ROW_8: PieceSquareRow = [0, 0, 0, 0, 0, 0, 0, 0];
+
+// Without the danger of mate the king belongs in the center.
+const WHITE_KING_ENDGAME_ROW_8: PieceSquareRow = [-50, -40, -30, -20, -20, -30, -40, -50];
+const WHITE_KING_ENDGAME_ROW_7: PieceSquareRow = [-30, -20, -10, 0, 0, -10, -20, -30];
+const WHITE_KING_ENDGAME_ROW_6: PieceSquareRow = [-30, -10, 20, 30, 30, 20, -10, -30];
+const WHITE_KING_ENDGAME_ROW_5: PieceSquareRow = [-30, -10, 30, 40, 40, 30, -10, -30];
+const WHITE_KING_ENDGAME_ROW_4: PieceSquareRow = [-30, -10, 30, 40, 40, 30, -10, -30];
+const WHITE_KING_ENDGAME_ROW_3: PieceSquareRow = [-30, -10, 20, 30, 30, 20, -10, -30];
+const WHITE_KING_ENDGAME_ROW_2: PieceSquareRow = [-30, -30, 0, 0, 0, 0, -30, -30];
+const WHITE_KING_ENDGAME_ROW_1: PieceSquareRow = [-50, -30, -30, -30, -30, -30, -30, -50];
+
+const PAWN_ROWS: [PieceSquareRow; 8] = [
+    WHITE_PAWN_ROW_1,
+    WHITE_PAWN_ROW_2,
+    WHITE_PAWN_ROW_3,
+    WHITE_PAWN_ROW_4,
+    WHITE_PAWN_ROW_5,
+    WHITE_PAWN_ROW_6,
+    WHITE_PAWN_ROW_7,
+    WHITE_PAWN_ROW_8,
+];
+
+const KNIGHT_ROWS: [PieceSquareRow; 8] = [
+    KNIGHT_ROW_1,
+    KNIGHT_ROW_2,
+    KNIGHT_ROW_3,
+    KNIGHT_ROW_4,
+    KNIGHT_ROW_5,
+    KNIGHT_ROW_6,
+    KNIGHT_ROW_7,
+    KNIGHT_ROW_8,
+];
+
+const BISHOP_ROWS: [PieceSquareRow; 8] = [
+    BISHOP_ROW_1,
+    BISHOP_ROW_2,
+    BISHOP_ROW_3,
+    BISHOP_ROW_4,
+    BISHOP_ROW_5,
+    BISHOP_ROW_6,
+    BISHOP_ROW_7,
+    BISHOP_ROW_8,
+];
+
+const ROOK_ROWS: [PieceSquareRow; 8] = [
+    ROOK_ROW_1, ROOK_ROW_2, ROOK_ROW_3, ROOK_ROW_4, ROOK_ROW_5, ROOK_ROW_6, ROOK_ROW_7, ROOK_ROW_8,
+];
+
+const QUEEN_ROWS: [PieceSquareRow; 8] = [
+    QUEEN_ROW_1,
+    QUEEN_ROW_2,
+    QUEEN_ROW_3,
+    QUEEN_ROW_4,
+    QUEEN_ROW_5,
+    QUEEN_ROW_6,
+    QUEEN_ROW_7,
+    QUEEN_ROW_8,
+];
+
+const KING_ROWS: [PieceSquareRow; 8] = [
+    WHITE_KING_ROW_1,
+    WHITE_KING_ROW_2,
+    WHITE_KING_ROW_3,
+    WHITE_KING_ROW_4,
+    WHITE_KING_ROW_5,
+    WHITE_KING_ROW_6,
+    WHITE_KING_ROW_7,
+    WHITE_KING_ROW_8,
+];
+
+const PAWN_ENDGAME_ROWS: [PieceSquareRow; 8] = [
+    WHITE_PAWN_ENDGAME_ROW_1,
+    WHITE_PAWN_ENDGAME_ROW_2,
+    WHITE_PAWN_ENDGAME_ROW_3,
+    WHITE_PAWN_ENDGAME_ROW_4,
+    WHITE_PAWN_ENDGAME_ROW_5,
+    WHITE_PAWN_ENDGAME_ROW_6,
+    WHITE_PAWN_ENDGAME_ROW_7,
+    WHITE_PAWN_ENDGAME_ROW_8,
+];
+
+const KING_ENDGAME_ROWS: [PieceSquareRow; 8] = [
+    WHITE_KING_ENDGAME_ROW_1,
+    WHITE_KING_ENDGAME_ROW_2,
+    WHITE_KING_ENDGAME_ROW_3,
+    WHITE_KING_ENDGAME_ROW_4,
+    WHITE_KING_ENDGAME_ROW_5,
+    WHITE_KING_ENDGAME_ROW_6,
+    WHITE_KING_ENDGAME_ROW_7,
+    WHITE_KING_ENDGAME_ROW_8,
+];
+
+/// The rows of a piece type from white's side. Only pawns and kings change their
+/// preferred squares in the endgame.
+fn get_rows(typ: Typ) -> ([PieceSquareRow; 8], [PieceSquareRow; 8]) {
+    match typ {
+        Typ::Pawn => (PAWN_ROWS, PAWN_ENDGAME_ROWS),
+        Typ::Knight => (KNIGHT_ROWS, KNIGHT_ROWS),
+        Typ::Bishop => (BISHOP_ROWS, BISHOP_ROWS),
+        Typ::Rook => (ROOK_ROWS, ROOK_ROWS),
+        Typ::Queen => (QUEEN_ROWS, QUEEN_ROWS),
+        Typ::King => (KING_ROWS, KING_ENDGAME_ROWS),
     }
 }
-fn get_value(piece: Piece, square: Square) -> f32 {
-    get_table(piece)[square.as_index() as usize]
+
+/// The default table of a piece type from white's side, a1 first.
+pub(super) fn get_default_table(typ: Typ) -> [Tapered; 64] {
+    let (middlegame, endgame) = get_rows(typ);
+    let mut table = [Tapered::default(); 64];
+    for (index, value) in table.iter_mut().enumerate() {
+        *value = Tapered::new(
+            middlegame[index / 8][index % 8],
+            endgame[index / 8][index % 8],
+        );
+    }
+    table
+}
+
+/// Black's tables are white's mirrored from rank to rank.
+fn get_value(params: &EvalParams, piece: Piece, square: Square) -> Tapered {
+    let index = match piece.get_color() {
+        Color::White => square.as_index(),
+        Color::Black => square.as_index() ^ 56,
+    };
+    params.get_squares(piece.get_type())[index as usize]
 }
-fn get_score_for_pieces(position: &Position, pieces: [Piece; 6]) -> f32 {
-    let mut total_score: f32 = 0.0;
+fn get_score_for_pieces(position: &Position, params: &EvalParams, pieces: [Piece; 6]) -> Tapered {
+    let mut total_score = Tapered::default();
     for piece in pieces {
-        let mut piece_score: f32 = 0.0;
         for square in position.get_squares(piece).iter() {
-            piece_score += get_value(piece, square);
+            total_score += get_value(params, piece, square);
         }
-        total_score += piece_score;
     }
     total_score
 }
 
-pub fn count_black(position: &Position) -> f32 {
-    get_score_for_pieces(position, BLACK_PIECES)
+pub fn count_black(position: &Position, params: &EvalParams) -> Tapered {
+    get_score_for_pieces(position, params, BLACK_PIECES)
 }
 
-pub fn count_white(position: &Position) -> f32 {
-    get_score_for_pieces(position, WHITE_PIECES)
+pub fn count_white(position: &Position, params: &EvalParams) -> Tapered {
+    get_score_for_pieces(position, params, WHITE_PIECES)
 }
 
 const WHITE_PIECES: [Piece; 6] = [
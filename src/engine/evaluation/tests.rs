@@ -1,8 +1,20 @@
 use crate::engine::directions::squares::*;
-use crate::engine::evaluation::pawn_structures::get_passed_pawns;
+use crate::engine::evaluation::params::DEFAULT_PARAMS;
+use crate::engine::evaluation::pawn_structures::Pawns;
+use crate::engine::evaluation::phase::{get_phase, Tapered, MIDDLEGAME_PHASE};
+use crate::engine::evaluation::*;
+use crate::engine::piece::Color;
 use crate::engine::piece::Piece::{self, *};
 use crate::engine::position::*;
-use crate::engine::{evaluation::*, position};
+
+fn count_pawns(position: &Position, pawn: Piece, predicate: fn(&Pawns, Square) -> bool) -> usize {
+    let pawns = Pawns::new(position, pawn.get_color());
+    pawns
+        .own
+        .iter()
+        .filter(|square| predicate(&pawns, *square))
+        .count()
+}
 
 #[test]
 fn test_queen_loss() {
@@ -27,10 +39,7 @@ fn test_isolated_pawns() {
         .put_piece(WhitePawn, C1)
         .put_piece(WhitePawn, D1)
         .put_piece(WhitePawn, F1);
-    assert_eq!(
-        pawn_structures::get_isolated_pawns(&position, WhitePawn),
-        -8.0
-    );
+    assert_eq!(count_pawns(&position, WhitePawn, Pawns::is_isolated), 2);
 }
 #[test]
 fn test_isolated_pawns_2() {
@@ -39,10 +48,7 @@ fn test_isolated_pawns_2() {
         .put_piece(BlackPawn, C3)
         .put_piece(BlackPawn, D4)
         .put_piece(BlackPawn, F8);
-    assert_eq!(
-        pawn_structures::get_isolated_pawns(&position, BlackPawn),
-        -8.0
-    );
+    assert_eq!(count_pawns(&position, BlackPawn, Pawns::is_isolated), 2);
 }
 
 #[test]
@@ -53,64 +59,224 @@ fn test_doubled_pawns() {
         .put_piece(BlackPawn, F4)
         .put_piece(WhitePawn, D2)
         .put_piece(WhitePawn, D4);
-    assert_eq!(
-        pawn_structures::get_doubled_pawns(&position, BlackPawn),
-        -6.0
-    );
-    assert_eq!(
-        pawn_structures::get_doubled_pawns(&position, WhitePawn),
-        -3.0
-    );
+    assert_eq!(count_pawns(&position, BlackPawn, Pawns::is_doubled), 2);
+    assert_eq!(count_pawns(&position, WhitePawn, Pawns::is_doubled), 1);
 
     let position_2 = Position::default().put_piece(BlackPawn, F2);
-    assert_eq!(
-        pawn_structures::get_doubled_pawns(&position_2, BlackPawn),
-        0.0
-    );
+    assert_eq!(count_pawns(&position_2, BlackPawn, Pawns::is_doubled), 0);
 }
 #[test]
 fn test_equal_material() {
     let position = Position::new_starting_position();
-    let score = material::count_white(&position) - material::count_black(&position);
-    assert_eq!(score, 0.0);
+    let score = material::count_white(&position, &DEFAULT_PARAMS)
+        - material::count_black(&position, &DEFAULT_PARAMS);
+    assert_eq!(score, Tapered::default());
 }
 
 #[test]
 fn test_white_queen_missing() {
     let position = Position::new_starting_position().remove_piece(D1);
-    assert_eq!(Evaluation::new(&position).score, -89.44995);
+    assert_eq!(Evaluation::new(&position).score, -900);
 }
 
 #[test]
 fn test_black_queen_missing() {
     let position = Position::new_starting_position().remove_piece(D8);
     assert_eq!(
-        material::count_white(&position) - material::count_black(&position),
-        90.0
+        material::count_white(&position, &DEFAULT_PARAMS)
+            - material::count_black(&position, &DEFAULT_PARAMS),
+        Tapered::new(900, 940)
     );
+    let evaluation = Evaluation::new(&position);
     assert_eq!(
-        mobility::count_white(&position) - mobility::count_black(&position),
-        -0.049999952
+        evaluation.white_score.mobility - evaluation.black_score.mobility,
+        Tapered::new(-1, -2)
     );
     assert_eq!(
-        squares::count_white(&position) - squares::count_black(&position),
-        -0.5
+        (squares::count_white(&position, &DEFAULT_PARAMS)
+            - squares::count_black(&position, &DEFAULT_PARAMS))
+        .middlegame,
+        -5
     );
-    assert_eq!(Evaluation::new(&position).score, 89.44995);
+    assert_eq!(Evaluation::new(&position).score, 900);
 }
 
 #[test]
 fn test_get_passed_pawns() {
     let position1 = Position::default().put_piece(Piece::WhitePawn, A4);
-    assert_eq!(get_passed_pawns(&position1, WhitePawn), 15.0);
+    assert_eq!(count_pawns(&position1, WhitePawn, Pawns::is_passed), 1);
 
     let position2 = Position::default()
         .put_piece(Piece::BlackPawn, A4)
         .put_piece(WhitePawn, A3);
-    assert_eq!(get_passed_pawns(&position2, BlackPawn), 0.0);
+    assert_eq!(count_pawns(&position2, BlackPawn, Pawns::is_passed), 0);
 
     let position3 = Position::default()
         .put_piece(Piece::BlackPawn, A4)
         .put_piece(WhitePawn, B3);
-    assert_eq!(get_passed_pawns(&position3, BlackPawn), 0.0);
+    assert_eq!(count_pawns(&position3, BlackPawn, Pawns::is_passed), 0);
+}
+
+#[test]
+fn test_phase() {
+    assert_eq!(
+        get_phase(&Position::new_starting_position()),
+        MIDDLEGAME_PHASE
+    );
+    let position = Position::from_fen("4k3/pppp4/8/8/8/8/PPPP4/4K3 w - - 0 1").unwrap();
+    assert_eq!(get_phase(&position), 0);
+    let position = Position::from_fen("r3k3/8/8/8/8/8/8/4KB2 w - - 0 1").unwrap();
+    assert_eq!(get_phase(&position), 3);
+}
+
+#[test]
+fn test_blend() {
+    let value = Tapered::new(100, 200);
+    assert_eq!(value.blend(MIDDLEGAME_PHASE), 100);
+    assert_eq!(value.blend(0), 200);
+    assert_eq!(value.blend(MIDDLEGAME_PHASE / 2), 150);
+}
+
+#[test]
+fn test_king_is_not_material() {
+    let position = Position::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    assert_eq!(
+        material::count_white(&position, &DEFAULT_PARAMS),
+        Tapered::default()
+    );
+}
+
+#[test]
+fn test_king_centralizes_in_the_endgame() {
+    let corner = Position::from_fen("7k/8/8/8/8/8/P7/K7 w - - 0 1").unwrap();
+    let center = Position::from_fen("7k/8/8/8/4K3/8/P7/8 w - - 0 1").unwrap();
+    assert!(Evaluation::new(&center).score > Evaluation::new(&corner).score);
+    // With all pieces on the board the king still hides.
+    let walked = Position::new_starting_position()
+        .remove_piece(E1)
+        .put_piece(WhiteKing, E3);
+    assert!(
+        Evaluation::new(&Position::new_starting_position()).score > Evaluation::new(&walked).score
+    );
+}
+
+fn get_king_safety(fen: &str) -> i32 {
+    let position = Position::from_fen(fen).unwrap();
+    Evaluation::new(&position)
+        .white_score
+        .king_safety
+        .middlegame
+}
+
+#[test]
+fn test_castled_king_is_safer_than_exposed_king() {
+    let castled = get_king_safety("6k1/5ppp/8/8/8/8/5PPP/6K1 w - - 0 1");
+    assert_eq!(castled, 0);
+    let pushed = get_king_safety("6k1/5ppp/8/8/6P1/7P/5P2/6K1 w - - 0 1");
+    let half_open = get_king_safety("6k1/5ppp/8/8/8/8/5P1P/6K1 w - - 0 1");
+    let open = get_king_safety("6k1/5p1p/8/8/8/8/5P1P/6K1 w - - 0 1");
+    let center = get_king_safety("6k1/5ppp/8/8/8/8/5PPP/4K3 w - - 0 1");
+    assert!(pushed < castled);
+    assert!(half_open < pushed);
+    assert!(open < half_open);
+    assert!(center < castled);
+}
+
+#[test]
+fn test_pawn_storm() {
+    let calm = get_king_safety("6k1/5ppp/8/8/8/8/5PPP/6K1 w - - 0 1");
+    let storm = get_king_safety("6k1/5p1p/8/8/8/6p1/5PPP/6K1 w - - 0 1");
+    assert!(storm < calm);
+}
+
+#[test]
+fn test_king_attackers() {
+    let position = Position::from_fen("6k1/5ppp/8/8/8/5q2/5PPP/6K1 w - - 0 1").unwrap();
+    // A lone attacker is no threat yet.
+    assert_eq!(
+        king_safety::get_attacks(&position, &DEFAULT_PARAMS, Color::White, G1),
+        0
+    );
+    let position = Position::from_fen("6k1/5ppp/8/8/8/5q2/5PPP/3r2K1 w - - 0 1").unwrap();
+    assert_eq!(
+        king_safety::get_attacks(&position, &DEFAULT_PARAMS, Color::White, G1),
+        -60
+    );
+    let position = Position::from_fen("6k1/5ppp/8/8/8/5qn1/5PPP/3r2K1 w - - 0 1").unwrap();
+    assert_eq!(
+        king_safety::get_attacks(&position, &DEFAULT_PARAMS, Color::White, G1),
+        -105
+    );
+}
+
+#[test]
+fn test_backward_pawns() {
+    // c6 guards d5, the c- and e-pawns have advanced past d4.
+    let position = Position::from_fen("4k3/8/2p5/2P1P3/3P4/8/8/4K3 w - - 0 1").unwrap();
+    assert_eq!(count_pawns(&position, WhitePawn, Pawns::is_backward), 1);
+    // Nothing guards d4.
+    let position = Position::from_fen("4k3/8/2p5/2P1P3/8/3P4/8/4K3 w - - 0 1").unwrap();
+    assert_eq!(count_pawns(&position, WhitePawn, Pawns::is_backward), 0);
+}
+
+#[test]
+fn test_connected_pawns() {
+    // A phalanx on d4 and e4, and c3 protecting d4.
+    let position = Position::from_fen("4k3/8/8/8/3PP3/2P5/7P/4K3 w - - 0 1").unwrap();
+    assert_eq!(count_pawns(&position, WhitePawn, Pawns::is_connected), 2);
+}
+
+#[test]
+fn test_candidate_pawns() {
+    // Two white pawns against one black pawn on the queenside, only the a-pawn's file
+    // is free.
+    let position = Position::from_fen("4k3/1p6/8/8/8/8/PP6/4K3 w - - 0 1").unwrap();
+    assert_eq!(count_pawns(&position, WhitePawn, Pawns::is_passed), 0);
+    assert_eq!(count_pawns(&position, WhitePawn, Pawns::is_candidate), 1);
+    let position = Position::from_fen("4k3/pp6/8/8/8/8/1P6/4K3 w - - 0 1").unwrap();
+    assert_eq!(count_pawns(&position, WhitePawn, Pawns::is_candidate), 0);
+}
+
+#[test]
+fn test_passed_pawn_scales_with_rank() {
+    let passed_pawn_score = |fen: &str| {
+        let position = Position::from_fen(fen).unwrap();
+        Evaluation::new(&position)
+            .white_score
+            .pawn_structure
+            .endgame
+    };
+    let third_rank = passed_pawn_score("4k3/8/8/8/8/P7/8/4K3 w - - 0 1");
+    let sixth_rank = passed_pawn_score("4k3/8/P7/8/8/8/8/4K3 w - - 0 1");
+    assert!(sixth_rank > third_rank);
+    // A blocked passed pawn is worth less than one with a free path.
+    let blocked = passed_pawn_score("4k3/8/n7/P7/8/8/8/4K3 w - - 0 1");
+    let free = passed_pawn_score("4k3/8/8/P7/8/8/8/4K3 w - - 0 1");
+    assert!(free > blocked);
+    // The defending king close to the pawn lowers its value.
+    let far = passed_pawn_score("7k/8/8/P7/8/8/8/K7 w - - 0 1");
+    let near = passed_pawn_score("8/k7/8/P7/8/8/8/K7 w - - 0 1");
+    assert!(far > near);
+}
+
+fn get_mobility(fen: &str) -> Tapered {
+    Evaluation::new(&Position::from_fen(fen).unwrap())
+        .white_score
+        .mobility
+}
+
+#[test]
+fn test_mobility_counts_safe_squares() {
+    // The king reaches a1 and c1 behind its pawns.
+    let king = get_mobility("4k3/8/8/8/8/8/PPP5/1K6 w - - 0 1");
+    assert_eq!(king, Tapered::new(1, 4));
+    // The knight reaches 8 squares on e4, but only 2 from h1.
+    let center = get_mobility("4k3/8/8/8/4N3/8/PPP5/1K6 w - - 0 1");
+    assert_eq!(center - king, Tapered::new(12, 8));
+    let corner = get_mobility("4k3/8/8/8/8/8/PPP5/1K5N w - - 0 1");
+    assert_eq!(corner - king, Tapered::new(3, 2));
+    // The pawn on e7 covers d6 and f6, the own pawn blocks g5.
+    let restricted = get_mobility("4k3/4p3/8/6P1/4N3/8/PPP5/1K6 w - - 0 1");
+    let without_knight = get_mobility("4k3/4p3/8/6P1/8/8/PPP5/1K6 w - - 0 1");
+    assert_eq!(restricted - without_knight, Tapered::new(8, 5));
 }
@@ -0,0 +1,109 @@
+use crate::engine::{
+    chess_moves::ChessMove,
+    directions::{self, squares::Square},
+    piece::Piece,
+    position::Position,
+    DrawReason,
+};
+
+const FIFTY_MOVE_RULE_HALFMOVES: u32 = 100;
+const SEVENTY_FIVE_MOVE_RULE_HALFMOVES: u32 = 150;
+
+/// The positions of a game from its start up to the current position.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GameHistory {
+    positions: Vec<Position>,
+}
+
+impl GameHistory {
+    pub fn new(position: Position) -> Self {
+        GameHistory {
+            positions: vec![position],
+        }
+    }
+
+    pub fn push(&mut self, position: Position) {
+        self.positions.push(position);
+    }
+
+    /// Plays a move that is legal in the current position.
+    pub fn push_move(&mut self, chess_move: ChessMove) {
+        self.push(self.get_current().apply(chess_move));
+    }
+
+    /// Takes back the current position, the start position is never removed.
+    pub fn pop(&mut self) -> Option<Position> {
+        if self.positions.len() > 1 {
+            self.positions.pop()
+        } else {
+            None
+        }
+    }
+
+    pub fn get_current(&self) -> Position {
+        self.positions[self.positions.len() - 1]
+    }
+
+    pub fn get_positions(&self) -> &[Position] {
+        &self.positions
+    }
+
+    /// How often the current position has occurred, including itself. Only the
+    /// positions since the last capture or pawn move can repeat it.
+    pub fn count_repetitions(&self) -> usize {
+        let current = self.get_current();
+        self.positions
+            .iter()
+            .rev()
+            .take(current.get_halfmove_clock() as usize + 1)
+            .filter(|position| **position == current)
+            .count()
+    }
+
+    /// The rule that makes the current position a draw, apart from stalemate.
+    pub fn get_draw_reason(&self) -> Option<DrawReason> {
+        let current = self.get_current();
+        if is_insufficient_material(&current) {
+            Some(DrawReason::InsufficientMaterial)
+        } else if current.get_halfmove_clock() >= SEVENTY_FIVE_MOVE_RULE_HALFMOVES {
+            Some(DrawReason::SeventyFiveMoveRule)
+        } else if self.count_repetitions() >= 3 {
+            Some(DrawReason::ThreefoldRepetition)
+        } else if current.get_halfmove_clock() >= FIFTY_MOVE_RULE_HALFMOVES {
+            Some(DrawReason::FiftyMoveRule)
+        } else {
+            None
+        }
+    }
+}
+
+/// True if neither side can ever checkmate: only kings, plus at most one knight
+/// or bishop, or plus any number of bishops that all stand on squares of one color.
+pub fn is_insufficient_material(position: &Position) -> bool {
+    let mut minor_pieces = 0;
+    let mut bishop_square_colors: Vec<u32> = Vec::new();
+    for (square, piece) in position.get_all_pieces() {
+        match piece {
+            Piece::WhiteKing | Piece::BlackKing => {}
+            Piece::WhiteKnight | Piece::BlackKnight => minor_pieces += 1,
+            Piece::WhiteBishop | Piece::BlackBishop => {
+                minor_pieces += 1;
+                bishop_square_colors.push(square_color(square));
+            }
+            _ => return false,
+        }
+    }
+    minor_pieces <= 1
+        || (bishop_square_colors.len() == minor_pieces
+            && bishop_square_colors
+                .iter()
+                .all(|color| *color == bishop_square_colors[0]))
+}
+
+fn square_color(square: Square) -> u32 {
+    let row = square.as_index() / 8;
+    (row + directions::get_column(square)) % 2
+}
+
+#[cfg(test)]
+mod tests;
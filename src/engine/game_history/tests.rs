@@ -0,0 +1,152 @@
+use crate::engine::{
+    chess_moves::get_current_player_moves,
+    game_history::{is_insufficient_material, GameHistory},
+    get_game_end,
+    position::Position,
+    DrawReason, GameEnd,
+};
+
+fn play(history: &mut GameHistory, moves: &[&str]) {
+    for uci_move in moves {
+        let chess_move = get_current_player_moves(&history.get_current())
+            .into_iter()
+            .find(|chess_move| {
+                format!("{}{}", chess_move.get_from(), chess_move.get_to()) == *uci_move
+            })
+            .unwrap();
+        history.push_move(chess_move);
+    }
+}
+
+#[test]
+fn test_threefold_repetition() {
+    let mut history = GameHistory::new(Position::new_starting_position());
+    play(&mut history, &["g1f3", "g8f6", "f3g1", "f6g8"]);
+    assert_eq!(history.count_repetitions(), 2);
+    assert_eq!(history.get_draw_reason(), None);
+    play(&mut history, &["g1f3", "g8f6", "f3g1", "f6g8"]);
+    assert_eq!(history.count_repetitions(), 3);
+    assert_eq!(
+        get_game_end(&history),
+        Some(GameEnd::Draw(DrawReason::ThreefoldRepetition))
+    );
+}
+
+#[test]
+fn test_pawn_move_breaks_repetition() {
+    let mut history = GameHistory::new(Position::new_starting_position());
+    play(&mut history, &["g1f3", "g8f6", "f3g1", "f6g8"]);
+    play(&mut history, &["e2e4", "e7e5"]);
+    // No white pawn can take on e6, so the position after e7e5 repeats as well.
+    play(&mut history, &["g1f3", "g8f6", "f3g1", "f6g8"]);
+    assert_eq!(history.count_repetitions(), 2);
+    play(&mut history, &["g1f3", "g8f6", "f3g1", "f6g8"]);
+    assert_eq!(history.count_repetitions(), 3);
+    assert_eq!(
+        history.get_draw_reason(),
+        Some(DrawReason::ThreefoldRepetition)
+    );
+}
+
+#[test]
+fn test_capturable_en_passant_breaks_repetition() {
+    let mut history =
+        GameHistory::new(Position::from_fen("4k3/3p4/8/4P3/8/8/8/4K3 b - - 0 1").unwrap());
+    play(&mut history, &["d7d5"]);
+    play(&mut history, &["e1e2", "e8e7", "e2e1", "e7e8"]);
+    play(&mut history, &["e1e2", "e8e7", "e2e1", "e7e8"]);
+    // Only the first time white could take en passant on d6.
+    assert_eq!(history.count_repetitions(), 2);
+    assert_eq!(history.get_draw_reason(), None);
+}
+
+#[test]
+fn test_move_counters() {
+    let mut history = GameHistory::new(Position::new_starting_position());
+    play(&mut history, &["g1f3", "g8f6", "f3g1"]);
+    let position = history.get_current();
+    assert_eq!(position.get_halfmove_clock(), 3);
+    assert_eq!(position.get_fullmove_number(), 2);
+    play(&mut history, &["e7e5"]);
+    let position = history.get_current();
+    assert_eq!(position.get_halfmove_clock(), 0);
+    assert_eq!(position.get_fullmove_number(), 3);
+}
+
+#[test]
+fn test_fifty_and_seventy_five_move_rule() {
+    let history =
+        GameHistory::new(Position::from_fen("4k3/8/8/8/8/8/4R3/4K3 b - - 99 80").unwrap());
+    assert_eq!(history.get_draw_reason(), None);
+    let mut history = history;
+    play(&mut history, &["e8d8"]);
+    assert_eq!(history.get_draw_reason(), Some(DrawReason::FiftyMoveRule));
+    let history =
+        GameHistory::new(Position::from_fen("4k3/8/8/8/8/8/4R3/4K3 b - - 150 105").unwrap());
+    assert_eq!(
+        history.get_draw_reason(),
+        Some(DrawReason::SeventyFiveMoveRule)
+    );
+}
+
+#[test]
+fn test_checkmate_beats_fifty_move_rule() {
+    let history =
+        GameHistory::new(Position::from_fen("R5k1/5ppp/8/8/8/8/8/6K1 b - - 100 80").unwrap());
+    assert_eq!(get_game_end(&history), Some(GameEnd::Victory));
+}
+
+#[test]
+fn test_insufficient_material() {
+    let drawn = [
+        "4k3/8/8/8/8/8/8/4K3 w - - 0 1",
+        "4k3/8/8/8/8/8/8/2B1K3 w - - 0 1",
+        "4k3/8/8/8/8/8/8/1N2K3 w - - 0 1",
+        "4kb2/8/8/8/8/8/8/2B1K3 w - - 0 1",
+    ];
+    for fen in drawn {
+        assert!(
+            is_insufficient_material(&Position::from_fen(fen).unwrap()),
+            "{}",
+            fen
+        );
+    }
+    let not_drawn = [
+        "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1",
+        "4k3/8/8/8/8/8/8/1NB1K3 w - - 0 1",
+        "4k1b1/8/8/8/8/8/8/2B1K3 w - - 0 1",
+        "4kn2/8/8/8/8/8/8/1N2K3 w - - 0 1",
+        "4k3/8/8/8/8/8/8/R3K3 w - - 0 1",
+    ];
+    for fen in not_drawn {
+        assert!(
+            !is_insufficient_material(&Position::from_fen(fen).unwrap()),
+            "{}",
+            fen
+        );
+    }
+    let history = GameHistory::new(Position::from_fen(drawn[3]).unwrap());
+    assert_eq!(
+        get_game_end(&history),
+        Some(GameEnd::Draw(DrawReason::InsufficientMaterial))
+    );
+}
+
+#[test]
+fn test_stalemate() {
+    let history = GameHistory::new(Position::from_fen("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1").unwrap());
+    assert_eq!(
+        get_game_end(&history),
+        Some(GameEnd::Draw(DrawReason::Stalemate))
+    );
+}
+
+#[test]
+fn test_pop_keeps_start_position() {
+    let start = Position::new_starting_position();
+    let mut history = GameHistory::new(start);
+    play(&mut history, &["e2e4"]);
+    assert!(history.pop().is_some());
+    assert_eq!(history.pop(), None);
+    assert_eq!(history.get_positions(), [start]);
+}
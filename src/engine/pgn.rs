@@ -0,0 +1,399 @@
+use std::{
+    fmt,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::engine::{
+    chess_moves::{notation::MoveParseError, ChessMove},
+    piece::Color,
+    position::{
+        fen::{FenError, STARTING_POSITION_FEN},
+        Position,
+    },
+    GameEnd,
+};
+
+const MAX_LINE_LENGTH: usize = 80;
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GameResult {
+    WhiteWins,
+    BlackWins,
+    Draw,
+    Ongoing,
+}
+
+impl GameResult {
+    /// The result once `game_end` happened with `player` to move.
+    pub fn from_game_end(game_end: GameEnd, player: Color) -> GameResult {
+        match (game_end, player) {
+            (GameEnd::Draw(_), _) => GameResult::Draw,
+            (GameEnd::Victory, Color::White) => GameResult::BlackWins,
+            (GameEnd::Victory, Color::Black) => GameResult::WhiteWins,
+        }
+    }
+
+    fn from_token(token: &str) -> Option<GameResult> {
+        match token {
+            "1-0" => Some(GameResult::WhiteWins),
+            "0-1" => Some(GameResult::BlackWins),
+            "1/2-1/2" => Some(GameResult::Draw),
+            "*" => Some(GameResult::Ongoing),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for GameResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameResult::WhiteWins => write!(f, "1-0"),
+            GameResult::BlackWins => write!(f, "0-1"),
+            GameResult::Draw => write!(f, "1/2-1/2"),
+            GameResult::Ongoing => write!(f, "*"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PgnError {
+    InvalidTag(String),
+    InvalidFen(FenError),
+    UnterminatedComment,
+    InvalidMove { ply: usize, error: MoveParseError },
+}
+
+impl fmt::Display for PgnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PgnError::InvalidTag(line) => write!(f, "invalid tag pair '{}'", line),
+            PgnError::InvalidFen(error) => write!(f, "invalid FEN tag: {}", error),
+            PgnError::UnterminatedComment => write!(f, "comment or variation is not closed"),
+            PgnError::InvalidMove { ply, error } => write!(f, "half move {}: {}", ply, error),
+        }
+    }
+}
+
+impl std::error::Error for PgnError {}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecordedMove {
+    pub chess_move: ChessMove,
+    pub san: String,
+    /// Depth the engine searched for this move, none for moves of a human player.
+    pub depth: Option<u8>,
+}
+
+/// A game with the information of a PGN file: players, date, result and the moves
+/// from the start position.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GameRecord {
+    pub white: String,
+    pub black: String,
+    /// In PGN format, "YYYY.MM.DD".
+    pub date: String,
+    pub result: GameResult,
+    start: Position,
+    moves: Vec<RecordedMove>,
+}
+
+impl GameRecord {
+    pub fn new(start: Position, white: &str, black: &str, date: &str) -> Self {
+        GameRecord {
+            white: white.to_string(),
+            black: black.to_string(),
+            date: date.to_string(),
+            result: GameResult::Ongoing,
+            start,
+            moves: Vec::new(),
+        }
+    }
+
+    /// Appends a move, which must be legal in the current position.
+    pub fn push(&mut self, chess_move: ChessMove, depth: Option<u8>) {
+        let san = chess_move.to_san(&self.get_current());
+        self.moves.push(RecordedMove {
+            chess_move,
+            san,
+            depth,
+        });
+    }
+
+    /// Takes back the last move. The game is ongoing again afterwards.
+    pub fn pop(&mut self) -> Option<RecordedMove> {
+        let recorded_move = self.moves.pop()?;
+        self.result = GameResult::Ongoing;
+        Some(recorded_move)
+    }
+
+    pub fn get_moves(&self) -> &[RecordedMove] {
+        &self.moves
+    }
+
+    pub fn get_current(&self) -> Position {
+        self.moves
+            .iter()
+            .fold(self.start, |position, recorded_move| {
+                position.apply(recorded_move.chess_move)
+            })
+    }
+
+    /// The start position followed by the position after every move.
+    pub fn get_positions(&self) -> Vec<Position> {
+        let mut positions = vec![self.start];
+        for recorded_move in &self.moves {
+            positions.push(positions[positions.len() - 1].apply(recorded_move.chess_move));
+        }
+        positions
+    }
+
+    pub fn to_pgn(&self) -> String {
+        let mut pgn = String::new();
+        let mut tags = vec![
+            ("Event", String::from("luma game")),
+            ("Site", String::from("?")),
+            ("Date", self.date.clone()),
+            ("Round", String::from("-")),
+            ("White", self.white.clone()),
+            ("Black", self.black.clone()),
+            ("Result", self.result.to_string()),
+        ];
+        let start_fen = self.start.to_fen();
+        if start_fen != STARTING_POSITION_FEN {
+            tags.push(("SetUp", String::from("1")));
+            tags.push(("FEN", start_fen));
+        }
+        for (name, value) in tags {
+            pgn.push_str(&format!("[{} \"{}\"]\n", name, escape(&value)));
+        }
+        pgn.push('\n');
+        pgn.push_str(&wrap(&self.movetext_tokens()));
+        pgn.push('\n');
+        pgn
+    }
+
+    fn movetext_tokens(&self) -> Vec<String> {
+        let mut tokens = Vec::new();
+        let mut position = self.start;
+        for (index, recorded_move) in self.moves.iter().enumerate() {
+            match position.get_player() {
+                Color::White => tokens.push(format!("{}.", position.get_fullmove_number())),
+                Color::Black if index == 0 => {
+                    tokens.push(format!("{}...", position.get_fullmove_number()))
+                }
+                Color::Black => {}
+            }
+            tokens.push(recorded_move.san.clone());
+            if let Some(depth) = recorded_move.depth {
+                tokens.push(format!("{{depth {}}}", depth));
+            }
+            position = position.apply(recorded_move.chess_move);
+        }
+        tokens.push(self.result.to_string());
+        tokens
+    }
+
+    /// Reads the first game of a PGN text and replays its moves. Comments, variations
+    /// and annotations are skipped, every move must be legal.
+    pub fn from_pgn(pgn: &str) -> Result<GameRecord, PgnError> {
+        let mut tags: Vec<(String, String)> = Vec::new();
+        let mut movetext = String::new();
+        for line in pgn.lines() {
+            let line = line.trim();
+            if line.starts_with('[') && movetext.trim().is_empty() {
+                tags.push(parse_tag(line)?);
+            } else if line.starts_with('[') {
+                // The tags of the next game.
+                break;
+            } else if !line.starts_with('%') {
+                movetext.push_str(line);
+                movetext.push('\n');
+            }
+        }
+        let tag = |name: &str| {
+            tags.iter()
+                .find(|(tag_name, _)| tag_name == name)
+                .map(|(_, value)| value.clone())
+        };
+        let start = match tag("FEN") {
+            Some(fen) => Position::from_fen(&fen).map_err(PgnError::InvalidFen)?,
+            None => Position::new_starting_position(),
+        };
+        let mut record = GameRecord::new(
+            start,
+            &tag("White").unwrap_or_else(|| String::from("?")),
+            &tag("Black").unwrap_or_else(|| String::from("?")),
+            &tag("Date").unwrap_or_else(|| String::from("????.??.??")),
+        );
+        let (tokens, depths) = tokenize(&movetext)?;
+        for (token, depth) in tokens.iter().zip(depths) {
+            if let Some(result) = GameResult::from_token(token) {
+                record.result = result;
+                break;
+            }
+            let chess_move =
+                ChessMove::parse_san(&record.get_current(), token).map_err(|error| {
+                    PgnError::InvalidMove {
+                        ply: record.moves.len() + 1,
+                        error,
+                    }
+                })?;
+            record.push(chess_move, depth);
+        }
+        if record.result == GameResult::Ongoing {
+            if let Some(result) = tag("Result").and_then(|result| GameResult::from_token(&result)) {
+                record.result = result;
+            }
+        }
+        Ok(record)
+    }
+}
+
+/// Today's date in PGN format, computed from the system clock in UTC.
+pub fn today() -> String {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+    let (year, month, day) = civil_from_days((seconds / SECONDS_PER_DAY) as i64);
+    format!("{:04}.{:02}.{:02}", year, month, day)
+}
+
+// Converts days since 1970-01-01 to a proleptic Gregorian date, after Howard Hinnant.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+fn parse_tag(line: &str) -> Result<(String, String), PgnError> {
+    let invalid = || PgnError::InvalidTag(line.to_string());
+    let inner = line
+        .strip_prefix('[')
+        .and_then(|line| line.strip_suffix(']'))
+        .ok_or_else(invalid)?;
+    let (name, value) = inner.trim().split_once(' ').ok_or_else(invalid)?;
+    let value = value
+        .trim()
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .ok_or_else(invalid)?;
+    Ok((
+        name.to_string(),
+        value.replace("\\\"", "\"").replace("\\\\", "\\"),
+    ))
+}
+
+/// Splits movetext into moves and results. Each move comes with the engine depth of a
+/// following `{depth n}` comment, as written by `to_pgn`.
+fn tokenize(movetext: &str) -> Result<(Vec<String>, Vec<Option<u8>>), PgnError> {
+    let mut tokens: Vec<String> = Vec::new();
+    let mut depths: Vec<Option<u8>> = Vec::new();
+    let mut chars = movetext.chars().peekable();
+    let mut variation_level = 0;
+    while let Some(c) = chars.next() {
+        match c {
+            '{' => {
+                let mut comment = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => comment.push(c),
+                        None => return Err(PgnError::UnterminatedComment),
+                    }
+                }
+                let depth = comment
+                    .trim()
+                    .strip_prefix("depth ")
+                    .and_then(|depth| depth.parse().ok());
+                if let (Some(depth), 0, Some(last)) = (depth, variation_level, depths.last_mut()) {
+                    *last = Some(depth);
+                }
+            }
+            ';' => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            '(' => variation_level += 1,
+            ')' => variation_level -= 1,
+            c if c.is_whitespace() => {}
+            _ => {
+                let mut token = c.to_string();
+                while let Some(c) = chars.peek() {
+                    if c.is_whitespace() || matches!(c, '{' | '(' | ')' | ';') {
+                        break;
+                    }
+                    token.push(*c);
+                    chars.next();
+                }
+                if variation_level == 0 {
+                    if let Some(token) = strip_move_number(&token) {
+                        if !token.starts_with('$') {
+                            tokens.push(token.to_string());
+                            depths.push(None);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    if variation_level != 0 {
+        return Err(PgnError::UnterminatedComment);
+    }
+    Ok((tokens, depths))
+}
+
+/// Removes a leading move number like "12." or "12...", returns None if nothing is left.
+fn strip_move_number(token: &str) -> Option<&str> {
+    let rest = token.trim_start_matches(|c: char| c.is_ascii_digit());
+    let rest = if rest.len() < token.len() && rest.starts_with('.') {
+        rest.trim_start_matches('.')
+    } else {
+        token
+    };
+    if rest.is_empty() {
+        None
+    } else {
+        Some(rest)
+    }
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn wrap(tokens: &[String]) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    let mut line = String::new();
+    for token in tokens {
+        if !line.is_empty() && line.len() + 1 + token.len() > MAX_LINE_LENGTH {
+            lines.push(line);
+            line = String::new();
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(token);
+    }
+    lines.push(line);
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests;
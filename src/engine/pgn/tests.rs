@@ -0,0 +1,133 @@
+use crate::engine::{
+    chess_moves::ChessMove,
+    pgn::{civil_from_days, GameRecord, GameResult, PgnError},
+    piece::Color,
+    position::Position,
+    DrawReason, GameEnd,
+};
+
+#[test]
+fn test_pgn_round_trip() {
+    let mut record = GameRecord::new(
+        Position::new_starting_position(),
+        "Player",
+        "luma",
+        "2024.05.01",
+    );
+    for (san, depth) in [
+        ("e4", None),
+        ("e5", Some(4)),
+        ("Bc4", None),
+        ("Nc6", Some(5)),
+        ("Qh5", None),
+        ("Nf6", Some(4)),
+        ("Qxf7#", None),
+    ] {
+        let chess_move = ChessMove::parse_san(&record.get_current(), san).unwrap();
+        record.push(chess_move, depth);
+    }
+    record.result = GameResult::WhiteWins;
+    let pgn = record.to_pgn();
+    assert!(pgn.contains("[White \"Player\"]"));
+    assert!(pgn.contains("[Result \"1-0\"]"));
+    assert!(
+        pgn.contains("1. e4 e5 {depth 4} 2. Bc4 Nc6 {depth 5} 3. Qh5 Nf6 {depth 4} 4. Qxf7# 1-0")
+    );
+    assert_eq!(GameRecord::from_pgn(&pgn), Ok(record));
+}
+
+#[test]
+fn test_pgn_from_fen_with_black_to_move() {
+    let start = Position::from_fen("4k3/8/8/8/8/8/4P3/4K3 b - - 0 30").unwrap();
+    let mut record = GameRecord::new(start, "a", "b", "2024.01.01");
+    record.push(ChessMove::parse_san(&start, "Kd7").unwrap(), None);
+    record.push(
+        ChessMove::parse_san(&record.get_current(), "e4").unwrap(),
+        None,
+    );
+    let pgn = record.to_pgn();
+    assert!(pgn.contains("[FEN \"4k3/8/8/8/8/8/4P3/4K3 b - - 0 30\"]"));
+    assert!(pgn.contains("30... Kd7 31. e4 *"));
+    let read = GameRecord::from_pgn(&pgn).unwrap();
+    assert_eq!(read.get_positions(), record.get_positions());
+}
+
+#[test]
+fn test_read_annotated_pgn() {
+    let pgn = r#"[Event "Casual"]
+[White "Anderssen"]
+[Black "Kieseritzky"]
+[Result "1-0"]
+
+1. e4 e5 2. f4 {King's Gambit} exf4 3. Bc4 $1 Qh4+ (3... d5 4. Bxd5) 4. Kf1
+; a rest of line comment
+4... b5?! 1-0
+
+[Event "Next game"]
+1. d4 *
+"#;
+    let record = GameRecord::from_pgn(pgn).unwrap();
+    assert_eq!(record.white, "Anderssen");
+    assert_eq!(record.result, GameResult::WhiteWins);
+    let sans: Vec<&str> = record
+        .get_moves()
+        .iter()
+        .map(|recorded_move| recorded_move.san.as_str())
+        .collect();
+    assert_eq!(sans, ["e4", "e5", "f4", "exf4", "Bc4", "Qh4+", "Kf1", "b5"]);
+    assert_eq!(record.get_positions().len(), 9);
+}
+
+#[test]
+fn test_pgn_errors() {
+    assert!(matches!(
+        GameRecord::from_pgn("1. e4 e5 2. Ke3 *"),
+        Err(PgnError::InvalidMove { ply: 3, .. })
+    ));
+    assert_eq!(
+        GameRecord::from_pgn("[White Anderssen]\n1. e4 *"),
+        Err(PgnError::InvalidTag(String::from("[White Anderssen]")))
+    );
+    assert_eq!(
+        GameRecord::from_pgn("1. e4 {unfinished *"),
+        Err(PgnError::UnterminatedComment)
+    );
+    assert!(matches!(
+        GameRecord::from_pgn("[FEN \"8/8 w - -\"]\n*"),
+        Err(PgnError::InvalidFen(_))
+    ));
+}
+
+#[test]
+fn test_game_result() {
+    assert_eq!(
+        GameResult::from_game_end(GameEnd::Victory, Color::Black),
+        GameResult::WhiteWins
+    );
+    assert_eq!(
+        GameResult::from_game_end(GameEnd::Draw(DrawReason::Stalemate), Color::White),
+        GameResult::Draw
+    );
+}
+
+#[test]
+fn test_civil_from_days() {
+    assert_eq!(civil_from_days(0), (1970, 1, 1));
+    assert_eq!(civil_from_days(11_016), (2000, 2, 29));
+    assert_eq!(civil_from_days(19_844), (2024, 5, 1));
+}
+
+#[test]
+fn test_pop_reopens_game() {
+    let start = Position::new_starting_position();
+    let mut record = GameRecord::new(start, "a", "b", "2024.01.01");
+    record.push(ChessMove::parse_san(&start, "e4").unwrap(), None);
+    record.result = GameResult::Draw;
+    assert_eq!(
+        record.pop().map(|recorded_move| recorded_move.san),
+        Some(String::from("e4"))
+    );
+    assert_eq!(record.result, GameResult::Ongoing);
+    assert_eq!(record.get_current(), start);
+    assert_eq!(record.pop(), None);
+}
@@ -14,7 +14,39 @@ pub enum Piece {
     WhitePawn,
     WhiteKnight,
 }
+/// All pieces in declaration order, so `PIECES[piece as usize] == piece`.
+pub const PIECES: [Piece; 12] = [
+    Piece::BlackQueen,
+    Piece::BlackKing,
+    Piece::BlackBishop,
+    Piece::BlackRook,
+    Piece::BlackPawn,
+    Piece::BlackKnight,
+    Piece::WhiteQueen,
+    Piece::WhiteKing,
+    Piece::WhiteBishop,
+    Piece::WhiteRook,
+    Piece::WhitePawn,
+    Piece::WhiteKnight,
+];
+
 impl Piece {
+    pub fn new(color: Color, typ: Typ) -> Piece {
+        match (color, typ) {
+            (Color::Black, Typ::Queen) => Piece::BlackQueen,
+            (Color::Black, Typ::King) => Piece::BlackKing,
+            (Color::Black, Typ::Bishop) => Piece::BlackBishop,
+            (Color::Black, Typ::Rook) => Piece::BlackRook,
+            (Color::Black, Typ::Pawn) => Piece::BlackPawn,
+            (Color::Black, Typ::Knight) => Piece::BlackKnight,
+            (Color::White, Typ::Queen) => Piece::WhiteQueen,
+            (Color::White, Typ::King) => Piece::WhiteKing,
+            (Color::White, Typ::Bishop) => Piece::WhiteBishop,
+            (Color::White, Typ::Rook) => Piece::WhiteRook,
+            (Color::White, Typ::Pawn) => Piece::WhitePawn,
+            (Color::White, Typ::Knight) => Piece::WhiteKnight,
+        }
+    }
     pub fn get_color(&self) -> Color {
         match self {
             Piece::BlackQueen => Color::Black,
@@ -47,6 +79,40 @@ impl Piece {
             Piece::WhiteKnight => Typ::Knight,
         }
     }
+    /// Returns the FEN letter of the piece: uppercase for white, lowercase for black.
+    pub fn as_char(&self) -> char {
+        match self {
+            Piece::BlackQueen => 'q',
+            Piece::BlackKing => 'k',
+            Piece::BlackBishop => 'b',
+            Piece::BlackRook => 'r',
+            Piece::BlackPawn => 'p',
+            Piece::BlackKnight => 'n',
+            Piece::WhiteQueen => 'Q',
+            Piece::WhiteKing => 'K',
+            Piece::WhiteBishop => 'B',
+            Piece::WhiteRook => 'R',
+            Piece::WhitePawn => 'P',
+            Piece::WhiteKnight => 'N',
+        }
+    }
+    pub fn from_char(c: char) -> Option<Piece> {
+        match c {
+            'q' => Some(Piece::BlackQueen),
+            'k' => Some(Piece::BlackKing),
+            'b' => Some(Piece::BlackBishop),
+            'r' => Some(Piece::BlackRook),
+            'p' => Some(Piece::BlackPawn),
+            'n' => Some(Piece::BlackKnight),
+            'Q' => Some(Piece::WhiteQueen),
+            'K' => Some(Piece::WhiteKing),
+            'B' => Some(Piece::WhiteBishop),
+            'R' => Some(Piece::WhiteRook),
+            'P' => Some(Piece::WhitePawn),
+            'N' => Some(Piece::WhiteKnight),
+            _ => None,
+        }
+    }
 }
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum Typ {
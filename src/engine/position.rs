@@ -1,7 +1,11 @@
 use crate::engine::{
+    chess_moves::pawn::can_capture_en_passant,
     directions::squares::*,
-    piece::{Color, Piece, Typ},
-    position::bitboard::Bitboard,
+    piece::{Color, Piece, Typ, PIECES},
+    position::{
+        bitboard::Bitboard,
+        zobrist::{black_to_move_key, castling_key, en_passant_key},
+    },
 };
 use std::hash::{Hash, Hasher};
 
@@ -21,8 +25,18 @@ pub struct Position {
     black_pawns: Bitboard,
     castling_rights: [bool; 4],
     en_passant: Option<Square>,
+    /// Whether a pawn can legally take en passant. Only then the en passant square
+    /// counts for the Zobrist key and equality, so a double step that no pawn can take
+    /// doesn't prevent repetitions.
+    en_passant_capturable: bool,
     player: Color,
+    halfmove_clock: u32,
+    fullmove_number: u32,
+    zobrist_key: u64,
+    /// Covers the pawns only, for caching the pawn structure.
+    pawn_key: u64,
 }
+
 impl Position {
     pub fn new_starting_position() -> Position {
         Position {
@@ -40,6 +54,13 @@ impl Position {
             black_pawns: Bitboard::from_vec(vec![A7, B7, C7, D7, E7, F7, G7, H7]),
             ..Default::default()
         }
+        .with_computed_zobrist_key()
+    }
+
+    fn with_computed_zobrist_key(mut self) -> Position {
+        self.zobrist_key = self.compute_zobrist_key();
+        self.pawn_key = self.compute_pawn_key();
+        self
     }
 
     pub fn disallow_castling_for_color(mut self, color: Color) -> Position {
@@ -63,7 +84,10 @@ impl Position {
     }
 
     pub fn remove_castling_right(mut self, castling_type: CastlingType) -> Position {
-        self.castling_rights[castling_type.as_index()] = false;
+        if self.castling_rights[castling_type.as_index()] {
+            self.castling_rights[castling_type.as_index()] = false;
+            self.zobrist_key ^= castling_key(castling_type);
+        }
         self
     }
     pub fn is_occupied(&self, square: Square) -> bool {
@@ -71,10 +95,7 @@ impl Position {
     }
 
     pub fn is_occupied_by_color(&self, square: Square, color: Color) -> bool {
-        match color {
-            Color::Black => self.get_black().contains(square),
-            Color::White => self.get_white().contains(square),
-        }
+        self.get_squares_of_color(color).contains(square)
     }
     pub fn is_occupied_by_piece(&self, square: Square, piece: Piece) -> bool {
         self.get_squares(piece).contains(square)
@@ -97,14 +118,23 @@ impl Position {
 
     pub fn set_en_passant(mut self, square: Square) -> Position {
         if A4 <= square && square <= H5 {
+            self = self.reset_en_passant();
             self.en_passant = Some(square);
+            self.en_passant_capturable = can_capture_en_passant(&self, square);
+            if self.en_passant_capturable {
+                self.zobrist_key ^= en_passant_key(square);
+            }
             self
         } else {
             panic!("Invalid en passant square {:?}", square);
         }
     }
     pub fn reset_en_passant(mut self) -> Position {
+        if let Some(square) = self.get_capturable_en_passant() {
+            self.zobrist_key ^= en_passant_key(square);
+        }
         self.en_passant = None;
+        self.en_passant_capturable = false;
         self
     }
 
@@ -116,11 +146,39 @@ impl Position {
         self.en_passant
     }
 
+    /// The en passant pawn if it can actually be taken.
+    pub fn get_capturable_en_passant(&self) -> Option<Square> {
+        self.en_passant.filter(|_| self.en_passant_capturable)
+    }
+
+    /// Half moves since the last capture or pawn move, for the fifty-move rule.
+    pub fn get_halfmove_clock(&self) -> u32 {
+        self.halfmove_clock
+    }
+
+    pub fn get_fullmove_number(&self) -> u32 {
+        self.fullmove_number
+    }
+
+    /// Updates the move counters after a move. Call it once the player has been toggled.
+    pub fn advance_move_counters(mut self, is_capture_or_pawn_move: bool) -> Position {
+        if is_capture_or_pawn_move {
+            self.halfmove_clock = 0;
+        } else {
+            self.halfmove_clock += 1;
+        }
+        if self.player == Color::White {
+            self.fullmove_number += 1;
+        }
+        self
+    }
+
     pub fn toggle_player(mut self) -> Position {
         match self.player {
             Color::Black => self.player = Color::White,
             Color::White => self.player = Color::Black,
         }
+        self.zobrist_key ^= black_to_move_key();
         self
     }
 
@@ -142,10 +200,17 @@ impl Position {
             | self.white_bishops
     }
 
-    fn get_all(&self) -> Bitboard {
+    pub fn get_all(&self) -> Bitboard {
         self.get_black() | self.get_white()
     }
 
+    pub fn get_squares_of_color(&self, color: Color) -> Bitboard {
+        match color {
+            Color::Black => self.get_black(),
+            Color::White => self.get_white(),
+        }
+    }
+
     pub fn get_all_pieces(&self) -> Vec<(Square, Piece)> {
         let mut all_pieces: Vec<(Square, Piece)> = Vec::new();
         for square in self.get_all().iter() {
@@ -177,41 +242,42 @@ impl Position {
         }
     }
 
-    pub fn put_piece(mut self, piece: Piece, square: Square) -> Position {
+    fn get_squares_mut(&mut self, piece: Piece) -> &mut Bitboard {
         match piece.get_color() {
             Color::Black => match piece.get_type() {
-                Typ::King => self.black_king.set_bit(square),
-                Typ::Queen => self.black_queen.set_bit(square),
-                Typ::Rook => self.black_rooks.set_bit(square),
-                Typ::Pawn => self.black_pawns.set_bit(square),
-                Typ::Knight => self.black_knights.set_bit(square),
-                Typ::Bishop => self.black_bishops.set_bit(square),
+                Typ::King => &mut self.black_king,
+                Typ::Queen => &mut self.black_queen,
+                Typ::Rook => &mut self.black_rooks,
+                Typ::Pawn => &mut self.black_pawns,
+                Typ::Knight => &mut self.black_knights,
+                Typ::Bishop => &mut self.black_bishops,
             },
             Color::White => match piece.get_type() {
-                Typ::King => self.white_king.set_bit(square),
-                Typ::Queen => self.white_queen.set_bit(square),
-                Typ::Rook => self.white_rooks.set_bit(square),
-                Typ::Pawn => self.white_pawns.set_bit(square),
-                Typ::Knight => self.white_knights.set_bit(square),
-                Typ::Bishop => self.white_bishops.set_bit(square),
+                Typ::King => &mut self.white_king,
+                Typ::Queen => &mut self.white_queen,
+                Typ::Rook => &mut self.white_rooks,
+                Typ::Pawn => &mut self.white_pawns,
+                Typ::Knight => &mut self.white_knights,
+                Typ::Bishop => &mut self.white_bishops,
             },
         }
+    }
+
+    pub fn put_piece(mut self, piece: Piece, square: Square) -> Position {
+        if !self.is_occupied_by_piece(square, piece) {
+            self.get_squares_mut(piece).set_bit(square);
+            self.toggle_piece_key(piece, square);
+        }
         self
     }
 
     pub fn remove_piece(mut self, square: Square) -> Position {
-        self.black_king.remove_bit(square);
-        self.black_queen.remove_bit(square);
-        self.black_rooks.remove_bit(square);
-        self.black_pawns.remove_bit(square);
-        self.black_knights.remove_bit(square);
-        self.black_bishops.remove_bit(square);
-        self.white_king.remove_bit(square);
-        self.white_queen.remove_bit(square);
-        self.white_rooks.remove_bit(square);
-        self.white_pawns.remove_bit(square);
-        self.white_knights.remove_bit(square);
-        self.white_bishops.remove_bit(square);
+        for piece in PIECES {
+            if self.is_occupied_by_piece(square, piece) {
+                self.get_squares_mut(piece).remove_bit(square);
+                self.toggle_piece_key(piece, square);
+            }
+        }
         self
     }
 
@@ -274,8 +340,14 @@ impl Default for Position {
             black_pawns: Default::default(),
             castling_rights: [true, true, true, true],
             en_passant: None,
+            en_passant_capturable: false,
             player: Color::White,
+            halfmove_clock: 0,
+            fullmove_number: 1,
+            zobrist_key: 0,
+            pawn_key: 0,
         }
+        .with_computed_zobrist_key()
     }
 }
 
@@ -286,29 +358,26 @@ pub enum CastlingType {
     WhiteQueenside = 2,
     WhiteKingside = 3,
 }
+/// The castling types ordered by `as_index`.
+pub const CASTLING_TYPES: [CastlingType; 4] = [
+    CastlingType::BlackQueenside,
+    CastlingType::BlackKingside,
+    CastlingType::WhiteQueenside,
+    CastlingType::WhiteKingside,
+];
+
 impl CastlingType {
-    fn as_index(&self) -> usize {
+    pub(crate) fn as_index(&self) -> usize {
         *self as usize
     }
 }
 
+// The move counters are deliberately left out of Hash and PartialEq: two
+// positions that only differ in their counters are the same for the search.
+// Equal positions always have equal Zobrist keys, so the key is all Hash needs.
 impl Hash for Position {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        self.white_king.hash(state);
-        self.white_queen.hash(state);
-        self.white_rooks.hash(state);
-        self.white_bishops.hash(state);
-        self.white_knights.hash(state);
-        self.white_pawns.hash(state);
-        self.black_king.hash(state);
-        self.black_queen.hash(state);
-        self.black_rooks.hash(state);
-        self.black_bishops.hash(state);
-        self.black_knights.hash(state);
-        self.black_pawns.hash(state);
-        self.castling_rights.hash(state);
-        self.en_passant.hash(state);
-        self.player.hash(state);
+        self.zobrist_key.hash(state);
     }
 }
 
@@ -327,12 +396,15 @@ impl PartialEq for Position {
             && self.black_knights == other.black_knights
             && self.black_pawns == other.black_pawns
             && self.castling_rights == other.castling_rights
-            && self.en_passant == other.en_passant
+            && self.get_capturable_en_passant() == other.get_capturable_en_passant()
             && self.player == other.player
     }
 }
 pub mod bitboard;
+pub mod fen;
+pub mod make_move;
 pub mod print;
+pub mod zobrist;
 
 #[cfg(test)]
 mod tests;
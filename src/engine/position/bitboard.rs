@@ -1,6 +1,6 @@
 use intbits::Bits;
 use iterator::BitboardIterator;
-use std::ops::{BitAnd, BitOr};
+use std::ops::{BitAnd, BitOr, Not};
 
 use crate::engine::directions::squares::Square;
 
@@ -8,6 +8,10 @@ use crate::engine::directions::squares::Square;
 
 pub struct Bitboard(u64);
 impl Bitboard {
+    pub const fn from_inner(inner: u64) -> Bitboard {
+        Bitboard(inner)
+    }
+
     pub fn from(square: Square) -> Bitboard {
         let index = square.as_index();
         if index > 63 {
@@ -34,25 +38,23 @@ impl Bitboard {
     pub fn remove_bit(&mut self, square: Square) {
         self.0.set_bit(square.as_index(), false);
     }
-    #[allow(dead_code)]
     pub fn get_inner(&self) -> u64 {
         self.0
     }
 
-    pub fn iter(&self) -> BitboardIterator<'_> {
-        BitboardIterator {
-            bitboard: self,
-            index: 0,
-        }
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn iter(&self) -> BitboardIterator {
+        BitboardIterator { bits: self.0 }
     }
     pub fn contains(&self, square: Square) -> bool {
         let index = square.as_index();
         if index > 63 {
             return false;
         }
-        let bitboard = Bitboard::from(square);
-        let intersection = *self & bitboard;
-        intersection.count_ones() > 0
+        self.0.bit(index)
     }
 }
 impl BitOr for Bitboard {
@@ -69,6 +71,13 @@ impl BitAnd for Bitboard {
         Bitboard(self.0.bitand(rhs.0))
     }
 }
+impl Not for Bitboard {
+    type Output = Bitboard;
+
+    fn not(self) -> Self::Output {
+        Bitboard(!self.0)
+    }
+}
 
 mod iterator;
 #[cfg(test)]
@@ -1,25 +1,18 @@
 use crate::engine::directions::squares::Square;
 
-use super::Bitboard;
-use intbits::Bits;
-
-pub struct BitboardIterator<'a> {
-    pub(crate) bitboard: &'a Bitboard,
-    pub(crate) index: u32,
+/// Yields the set squares from a1 to h8, clearing the lowest set bit on each step.
+pub struct BitboardIterator {
+    pub(crate) bits: u64,
 }
-impl<'a> Iterator for BitboardIterator<'a> {
+impl Iterator for BitboardIterator {
     type Item = Square;
 
     fn next(&mut self) -> Option<Self::Item> {
-        while self.index < 64 {
-            if self.bitboard.0.bit(self.index) {
-                let result = Some(Square::new_unchecked(self.index));
-                self.index += 1;
-                return result;
-            } else {
-                self.index += 1;
-            }
+        if self.bits == 0 {
+            return None;
         }
-        None
+        let index = self.bits.trailing_zeros();
+        self.bits &= self.bits - 1;
+        Some(Square::new_unchecked(index))
     }
 }
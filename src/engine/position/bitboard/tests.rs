@@ -51,7 +51,15 @@ fn test_iterator() {
     assert_eq!(iter.next(), None);
     let mut sum = 0;
     for square in bitboard.iter() {
-        sum = sum + square.as_index();
+        sum += square.as_index();
     }
     assert_eq!(sum, 30);
 }
+
+#[test]
+fn test_not() {
+    let bitboard = !Bitboard::from_vec(vec![A1, B1]);
+    assert_eq!(bitboard.count_ones(), 62);
+    assert!(!bitboard.contains(A1));
+    assert!(bitboard.contains(C1));
+}
@@ -0,0 +1,286 @@
+use std::fmt;
+
+use crate::engine::{
+    check::is_check,
+    chess_moves::pawn::can_capture_en_passant,
+    directions::{
+        self,
+        squares::{Square, A3, A6, H3, H6},
+    },
+    piece::{Color, Piece, Typ},
+    position::{CastlingType, Position},
+};
+
+pub const STARTING_POSITION_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+const CASTLING_CHARS: [(char, CastlingType); 4] = [
+    ('K', CastlingType::WhiteKingside),
+    ('Q', CastlingType::WhiteQueenside),
+    ('k', CastlingType::BlackKingside),
+    ('q', CastlingType::BlackQueenside),
+];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FenError {
+    WrongNumberOfFields(usize),
+    WrongNumberOfRanks(usize),
+    InvalidRankLength {
+        rank: usize,
+        length: u32,
+    },
+    InvalidPiece(char),
+    InvalidPlayer(String),
+    InvalidCastling(String),
+    InvalidEnPassant(String),
+    InvalidHalfmoveClock(String),
+    InvalidFullmoveNumber(String),
+    /// A side has no king or more than one.
+    KingCount {
+        color: Color,
+        count: u32,
+    },
+    PawnOnBackRank(Square),
+    /// The side that just moved left its king in check.
+    OpponentInCheck,
+}
+
+impl fmt::Display for FenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FenError::WrongNumberOfFields(count) => {
+                write!(f, "expected 4 to 6 space separated fields, found {}", count)
+            }
+            FenError::WrongNumberOfRanks(count) => {
+                write!(f, "expected 8 ranks in piece placement, found {}", count)
+            }
+            FenError::InvalidRankLength { rank, length } => {
+                write!(f, "rank {} describes {} squares instead of 8", rank, length)
+            }
+            FenError::InvalidPiece(c) => write!(f, "invalid piece character '{}'", c),
+            FenError::InvalidPlayer(field) => {
+                write!(f, "side to move must be 'w' or 'b', found '{}'", field)
+            }
+            FenError::InvalidCastling(field) => {
+                write!(f, "invalid castling availability '{}'", field)
+            }
+            FenError::InvalidEnPassant(field) => {
+                write!(f, "invalid en passant target square '{}'", field)
+            }
+            FenError::InvalidHalfmoveClock(field) => {
+                write!(f, "invalid halfmove clock '{}'", field)
+            }
+            FenError::InvalidFullmoveNumber(field) => {
+                write!(f, "invalid fullmove number '{}'", field)
+            }
+            FenError::KingCount { color, count } => {
+                write!(f, "{:?} has {} kings instead of one", color, count)
+            }
+            FenError::PawnOnBackRank(square) => {
+                write!(
+                    f,
+                    "pawn on {} cannot stand on the first or last rank",
+                    square
+                )
+            }
+            FenError::OpponentInCheck => write!(f, "the side not to move is in check"),
+        }
+    }
+}
+
+impl std::error::Error for FenError {}
+
+impl Position {
+    /// Builds a position from Forsyth-Edwards Notation. The halfmove clock and
+    /// fullmove number may be omitted and default to 0 and 1.
+    pub fn from_fen(fen: &str) -> Result<Position, FenError> {
+        let fields: Vec<&str> = fen.split_whitespace().collect();
+        if fields.len() < 4 || fields.len() > 6 {
+            return Err(FenError::WrongNumberOfFields(fields.len()));
+        }
+        let mut position = parse_piece_placement(fields[0])?;
+        position.player = parse_player(fields[1])?;
+        position.castling_rights = parse_castling_rights(fields[2])?;
+        position.en_passant = parse_en_passant(fields[3], position.player)?;
+        position.en_passant_capturable = position
+            .en_passant
+            .is_some_and(|square| can_capture_en_passant(&position, square));
+        if let Some(field) = fields.get(4) {
+            position.halfmove_clock = field
+                .parse()
+                .map_err(|_| FenError::InvalidHalfmoveClock(field.to_string()))?;
+        }
+        if let Some(field) = fields.get(5) {
+            position.fullmove_number = match field.parse() {
+                Ok(number) if number > 0 => number,
+                _ => return Err(FenError::InvalidFullmoveNumber(field.to_string())),
+            };
+        }
+        validate(&position)?;
+        Ok(position.with_computed_zobrist_key())
+    }
+
+    pub fn to_fen(self) -> String {
+        format!(
+            "{} {} {} {} {} {}",
+            self.piece_placement_to_fen(),
+            match self.player {
+                Color::White => 'w',
+                Color::Black => 'b',
+            },
+            self.castling_rights_to_fen(),
+            self.en_passant_to_fen(),
+            self.halfmove_clock,
+            self.fullmove_number
+        )
+    }
+
+    fn piece_placement_to_fen(&self) -> String {
+        let mut ranks: Vec<String> = Vec::new();
+        for row in (0..8).rev() {
+            let mut rank = String::new();
+            let mut empty = 0;
+            for column in 0..8 {
+                match self.get_piece_at(Square::new_unchecked(row * 8 + column)) {
+                    Some(piece) => {
+                        if empty > 0 {
+                            rank.push_str(&empty.to_string());
+                            empty = 0;
+                        }
+                        rank.push(piece.as_char());
+                    }
+                    None => empty += 1,
+                }
+            }
+            if empty > 0 {
+                rank.push_str(&empty.to_string());
+            }
+            ranks.push(rank);
+        }
+        ranks.join("/")
+    }
+
+    fn castling_rights_to_fen(&self) -> String {
+        let castling: String = CASTLING_CHARS
+            .iter()
+            .filter(|(_, castling_type)| self.get_castling_right(*castling_type))
+            .map(|(c, _)| *c)
+            .collect();
+        if castling.is_empty() {
+            String::from("-")
+        } else {
+            castling
+        }
+    }
+
+    fn en_passant_to_fen(&self) -> String {
+        // The position stores the square of the pawn that advanced two rows,
+        // FEN wants the square that pawn skipped.
+        match self.en_passant {
+            Some(square) if directions::is_in_row_4(square) => {
+                Square::new_unchecked(square.as_index() - 8).to_string()
+            }
+            Some(square) => Square::new_unchecked(square.as_index() + 8).to_string(),
+            None => String::from("-"),
+        }
+    }
+}
+
+fn parse_piece_placement(field: &str) -> Result<Position, FenError> {
+    let ranks: Vec<&str> = field.split('/').collect();
+    if ranks.len() != 8 {
+        return Err(FenError::WrongNumberOfRanks(ranks.len()));
+    }
+    let mut position = Position::default();
+    for (rank_index, rank) in ranks.iter().enumerate() {
+        let row = 7 - rank_index as u32;
+        let mut column: u32 = 0;
+        for c in rank.chars() {
+            if let Some(empty) = c.to_digit(10) {
+                if empty == 0 || empty > 8 {
+                    return Err(FenError::InvalidPiece(c));
+                }
+                column += empty;
+            } else {
+                let piece = Piece::from_char(c).ok_or(FenError::InvalidPiece(c))?;
+                if column < 8 {
+                    position = position.put_piece(piece, Square::new_unchecked(row * 8 + column));
+                }
+                column += 1;
+            }
+        }
+        if column != 8 {
+            return Err(FenError::InvalidRankLength {
+                rank: row as usize + 1,
+                length: column,
+            });
+        }
+    }
+    Ok(position)
+}
+
+/// Rejects positions the engine can't play from, which the syntax alone allows.
+fn validate(position: &Position) -> Result<(), FenError> {
+    for color in [Color::White, Color::Black] {
+        let count = position.count_pieces(Piece::new(color, Typ::King));
+        if count != 1 {
+            return Err(FenError::KingCount { color, count });
+        }
+    }
+    let pawns = position.get_squares(Piece::WhitePawn) | position.get_squares(Piece::BlackPawn);
+    if let Some(square) = pawns
+        .iter()
+        .find(|square| directions::is_in_first_row(*square) || directions::is_in_last_row(*square))
+    {
+        return Err(FenError::PawnOnBackRank(square));
+    }
+    if is_check(position, position.player.get_opponent_color()) {
+        return Err(FenError::OpponentInCheck);
+    }
+    Ok(())
+}
+
+fn parse_player(field: &str) -> Result<Color, FenError> {
+    match field {
+        "w" => Ok(Color::White),
+        "b" => Ok(Color::Black),
+        _ => Err(FenError::InvalidPlayer(field.to_string())),
+    }
+}
+
+fn parse_castling_rights(field: &str) -> Result<[bool; 4], FenError> {
+    let mut castling_rights = [false; 4];
+    if field == "-" {
+        return Ok(castling_rights);
+    }
+    for c in field.chars() {
+        match CASTLING_CHARS
+            .iter()
+            .find(|(castling_char, _)| *castling_char == c)
+        {
+            Some((_, castling_type)) if !castling_rights[castling_type.as_index()] => {
+                castling_rights[castling_type.as_index()] = true;
+            }
+            _ => return Err(FenError::InvalidCastling(field.to_string())),
+        }
+    }
+    Ok(castling_rights)
+}
+
+fn parse_en_passant(field: &str, player: Color) -> Result<Option<Square>, FenError> {
+    if field == "-" {
+        return Ok(None);
+    }
+    let target =
+        Square::from_algebraic(field).ok_or(FenError::InvalidEnPassant(field.to_string()))?;
+    // A target on the 6th row means black just moved a pawn from the 7th row
+    // to the 5th, so it must be white's turn (and vice versa for the 3rd row).
+    let pawn_index = match player {
+        Color::White if A6 <= target && target <= H6 => target.as_index() - 8,
+        Color::Black if A3 <= target && target <= H3 => target.as_index() + 8,
+        _ => return Err(FenError::InvalidEnPassant(field.to_string())),
+    };
+    Ok(Some(Square::new_unchecked(pawn_index)))
+}
+
+#[cfg(test)]
+mod tests;
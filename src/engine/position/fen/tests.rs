@@ -0,0 +1,151 @@
+use crate::engine::{
+    directions::squares::*,
+    piece::{Color, Piece},
+    position::{
+        fen::{FenError, STARTING_POSITION_FEN},
+        CastlingType, Position,
+    },
+};
+
+#[test]
+fn test_starting_position_from_fen() {
+    let position = Position::from_fen(STARTING_POSITION_FEN).unwrap();
+    assert_eq!(position, Position::new_starting_position());
+    assert_eq!(position.halfmove_clock, 0);
+    assert_eq!(position.fullmove_number, 1);
+}
+
+#[test]
+fn test_starting_position_to_fen() {
+    assert_eq!(
+        Position::new_starting_position().to_fen(),
+        STARTING_POSITION_FEN
+    );
+}
+
+#[test]
+fn test_round_trip() {
+    let fens = [
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+        "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+        "rnbqkbnr/pp1ppppp/8/2p5/4P3/8/PPPP1PPP/RNBQKBNR w Kq c6 0 2",
+        "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1",
+    ];
+    for fen in fens {
+        assert_eq!(Position::from_fen(fen).unwrap().to_fen(), fen);
+    }
+}
+
+#[test]
+fn test_fen_fields() {
+    let position =
+        Position::from_fen("rnbqkbnr/pp1ppppp/8/2p5/4P3/8/PPPP1PPP/RNBQKBNR b Kq e3 3 12").unwrap();
+    assert_eq!(position.get_player(), Color::Black);
+    assert!(position.get_castling_right(CastlingType::WhiteKingside));
+    assert!(!position.get_castling_right(CastlingType::WhiteQueenside));
+    assert!(!position.get_castling_right(CastlingType::BlackKingside));
+    assert!(position.get_castling_right(CastlingType::BlackQueenside));
+    assert_eq!(position.get_en_passant(), Some(E4));
+    assert_eq!(position.halfmove_clock, 3);
+    assert_eq!(position.fullmove_number, 12);
+    assert!(position.is_occupied_by_piece(C5, Piece::BlackPawn));
+}
+
+#[test]
+fn test_fen_without_counters() {
+    let position = Position::from_fen("4k3/8/8/8/8/8/8/4K3 w - -").unwrap();
+    assert_eq!(position.halfmove_clock, 0);
+    assert_eq!(position.fullmove_number, 1);
+    assert_eq!(position.to_fen(), "4k3/8/8/8/8/8/8/4K3 w - - 0 1");
+}
+
+#[test]
+fn test_fen_errors() {
+    assert_eq!(
+        Position::from_fen("8/8/8/8/8/8/8/8 w"),
+        Err(FenError::WrongNumberOfFields(2))
+    );
+    assert_eq!(
+        Position::from_fen("8/8/8/8/8/8/8 w - - 0 1"),
+        Err(FenError::WrongNumberOfRanks(7))
+    );
+    assert_eq!(
+        Position::from_fen("8/8/8/8/8/8/8/7 w - - 0 1"),
+        Err(FenError::InvalidRankLength { rank: 1, length: 7 })
+    );
+    assert_eq!(
+        Position::from_fen("8/8/8/8/8/8/8/4K4 w - - 0 1"),
+        Err(FenError::InvalidRankLength { rank: 1, length: 9 })
+    );
+    assert_eq!(
+        Position::from_fen("8/8/8/8/8/8/8/4X3 w - - 0 1"),
+        Err(FenError::InvalidPiece('X'))
+    );
+    assert_eq!(
+        Position::from_fen("8/8/8/8/8/8/8/8 x - - 0 1"),
+        Err(FenError::InvalidPlayer(String::from("x")))
+    );
+    assert_eq!(
+        Position::from_fen("8/8/8/8/8/8/8/8 w KK - 0 1"),
+        Err(FenError::InvalidCastling(String::from("KK")))
+    );
+    assert_eq!(
+        Position::from_fen("8/8/8/8/8/8/8/8 w - e3 0 1"),
+        Err(FenError::InvalidEnPassant(String::from("e3")))
+    );
+    assert_eq!(
+        Position::from_fen("8/8/8/8/8/8/8/8 w - - x 1"),
+        Err(FenError::InvalidHalfmoveClock(String::from("x")))
+    );
+    assert_eq!(
+        Position::from_fen("8/8/8/8/8/8/8/8 w - - 0 0"),
+        Err(FenError::InvalidFullmoveNumber(String::from("0")))
+    );
+}
+
+#[test]
+fn test_fen_king_count() {
+    assert_eq!(
+        Position::from_fen("8/8/8/8/8/8/8/8 w - - 0 1"),
+        Err(FenError::KingCount {
+            color: Color::White,
+            count: 0
+        })
+    );
+    assert_eq!(
+        Position::from_fen("4k3/8/8/8/8/8/8/8 w - - 0 1"),
+        Err(FenError::KingCount {
+            color: Color::White,
+            count: 0
+        })
+    );
+    assert_eq!(
+        Position::from_fen("3kk3/8/8/8/8/8/8/4K3 w - - 0 1"),
+        Err(FenError::KingCount {
+            color: Color::Black,
+            count: 2
+        })
+    );
+}
+
+#[test]
+fn test_fen_pawn_on_back_rank() {
+    assert_eq!(
+        Position::from_fen("4k3/8/8/8/8/8/8/P3K3 w - - 0 1"),
+        Err(FenError::PawnOnBackRank(A1))
+    );
+    assert_eq!(
+        Position::from_fen("4k2p/8/8/8/8/8/8/4K3 w - - 0 1"),
+        Err(FenError::PawnOnBackRank(H8))
+    );
+}
+
+#[test]
+fn test_fen_opponent_in_check() {
+    assert_eq!(
+        Position::from_fen("4k3/4R3/8/8/8/8/8/4K3 w - - 0 1"),
+        Err(FenError::OpponentInCheck)
+    );
+    assert!(Position::from_fen("4k3/4R3/8/8/8/8/8/4K3 b - - 0 1").is_ok());
+}
@@ -0,0 +1,152 @@
+use crate::engine::{
+    chess_moves::{
+        castling::{get_castling_configuration, remove_castling_rights_if_necessary},
+        pawn::set_en_passant_if_necessary,
+        ChessMove, MoveType,
+    },
+    directions::{self, squares::Square},
+    piece::{Color, Piece, Typ},
+    position::Position,
+};
+
+/// The state a move overwrites and `unmake_move` cannot derive from the move itself.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UndoInfo {
+    castling_rights: [bool; 4],
+    en_passant: Option<Square>,
+    en_passant_capturable: bool,
+    halfmove_clock: u32,
+    fullmove_number: u32,
+    zobrist_key: u64,
+}
+
+impl Position {
+    /// Plays a move generated for this position. Pass the returned undo information to
+    /// `unmake_move` to take the move back.
+    pub fn make_move(&mut self, chess_move: ChessMove) -> UndoInfo {
+        let undo = UndoInfo {
+            castling_rights: self.castling_rights,
+            en_passant: self.en_passant,
+            en_passant_capturable: self.en_passant_capturable,
+            halfmove_clock: self.halfmove_clock,
+            fullmove_number: self.fullmove_number,
+            zobrist_key: self.zobrist_key,
+        };
+        let (piece, from, to) = (
+            chess_move.get_piece(),
+            chess_move.get_from(),
+            chess_move.get_to(),
+        );
+        if let Some(capture) = chess_move.get_capture() {
+            self.take(capture, get_capture_square(chess_move));
+        }
+        self.take(piece, from);
+        self.place(chess_move.get_promotion().unwrap_or(piece), to);
+        if let MoveType::Castling { castling_type } = chess_move.get_move_type() {
+            let castling = get_castling_configuration(castling_type);
+            self.take(castling.rook, castling.rook_from);
+            self.place(castling.rook, castling.rook_to);
+        }
+
+        let mut position = remove_castling_rights_if_necessary(*self, from);
+        position = remove_castling_rights_if_necessary(position, to).reset_en_passant();
+        position = set_en_passant_if_necessary(position, piece, from, to);
+        let is_capture_or_pawn_move =
+            chess_move.get_capture().is_some() || piece.get_type() == Typ::Pawn;
+        *self = position
+            .toggle_player()
+            .advance_move_counters(is_capture_or_pawn_move);
+        undo
+    }
+
+    /// Takes back `chess_move`, which must be the last move made on this position.
+    pub fn unmake_move(&mut self, chess_move: ChessMove, undo: UndoInfo) {
+        let (piece, from, to) = (
+            chess_move.get_piece(),
+            chess_move.get_from(),
+            chess_move.get_to(),
+        );
+        if let MoveType::Castling { castling_type } = chess_move.get_move_type() {
+            let castling = get_castling_configuration(castling_type);
+            self.take(castling.rook, castling.rook_to);
+            self.place(castling.rook, castling.rook_from);
+        }
+        self.take(chess_move.get_promotion().unwrap_or(piece), to);
+        self.place(piece, from);
+        if let Some(capture) = chess_move.get_capture() {
+            self.place(capture, get_capture_square(chess_move));
+        }
+        self.player = piece.get_color();
+        self.castling_rights = undo.castling_rights;
+        self.en_passant = undo.en_passant;
+        self.en_passant_capturable = undo.en_passant_capturable;
+        self.halfmove_clock = undo.halfmove_clock;
+        self.fullmove_number = undo.fullmove_number;
+        self.zobrist_key = undo.zobrist_key;
+    }
+
+    /// Passes the turn to the opponent, for null-move pruning. The halfmove clock is reset
+    /// because no earlier position can repeat across a null move.
+    pub fn make_null_move(&mut self) -> UndoInfo {
+        let undo = UndoInfo {
+            castling_rights: self.castling_rights,
+            en_passant: self.en_passant,
+            en_passant_capturable: self.en_passant_capturable,
+            halfmove_clock: self.halfmove_clock,
+            fullmove_number: self.fullmove_number,
+            zobrist_key: self.zobrist_key,
+        };
+        *self = self
+            .reset_en_passant()
+            .toggle_player()
+            .advance_move_counters(true);
+        undo
+    }
+
+    pub fn unmake_null_move(&mut self, undo: UndoInfo) {
+        self.player = self.player.get_opponent_color();
+        self.en_passant = undo.en_passant;
+        self.en_passant_capturable = undo.en_passant_capturable;
+        self.halfmove_clock = undo.halfmove_clock;
+        self.fullmove_number = undo.fullmove_number;
+        self.zobrist_key = undo.zobrist_key;
+    }
+
+    /// The position after `chess_move`, leaving this one untouched.
+    pub fn apply(mut self, chess_move: ChessMove) -> Position {
+        self.make_move(chess_move);
+        self
+    }
+
+    fn take(&mut self, piece: Piece, square: Square) {
+        let squares = self.get_squares_mut(piece);
+        if squares.contains(square) {
+            squares.remove_bit(square);
+            self.toggle_piece_key(piece, square);
+        }
+    }
+
+    fn place(&mut self, piece: Piece, square: Square) {
+        let squares = self.get_squares_mut(piece);
+        if !squares.contains(square) {
+            squares.set_bit(square);
+            self.toggle_piece_key(piece, square);
+        }
+    }
+}
+
+/// En passant captures the pawn beside the to square, every other capture the piece on it.
+fn get_capture_square(chess_move: ChessMove) -> Square {
+    let to = chess_move.get_to();
+    if chess_move.get_move_type() != MoveType::EnPassant {
+        return to;
+    }
+    let behind = match chess_move.get_piece().get_color() {
+        Color::White => directions::down(to),
+        Color::Black => directions::up(to),
+    };
+    behind.unwrap_or(to)
+}
+
+#[cfg(test)]
+mod tests;
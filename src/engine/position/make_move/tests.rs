@@ -0,0 +1,104 @@
+use crate::engine::{
+    chess_moves::{get_current_player_moves, ChessMove},
+    directions::squares::*,
+    piece::{Color, Piece},
+    position::{CastlingType, Position},
+};
+
+fn find_move(position: &Position, uci: &str) -> ChessMove {
+    ChessMove::parse_uci(position, uci).unwrap()
+}
+
+/// Makes and unmakes every move down to `depth` and checks that the position is restored.
+fn assert_unmake_restores(position: &mut Position, depth: u8) {
+    if depth == 0 {
+        return;
+    }
+    let before = *position;
+    for chess_move in get_current_player_moves(position) {
+        let undo = position.make_move(chess_move);
+        assert_eq!(position.get_zobrist_key(), position.compute_zobrist_key());
+        assert_unmake_restores(position, depth - 1);
+        position.unmake_move(chess_move, undo);
+        assert_eq!(*position, before, "{:?}", chess_move);
+        assert_eq!(position.get_zobrist_key(), before.get_zobrist_key());
+        assert_eq!(position.get_halfmove_clock(), before.get_halfmove_clock());
+        assert_eq!(position.get_fullmove_number(), before.get_fullmove_number());
+    }
+}
+
+#[test]
+fn test_unmake_restores_position() {
+    for fen in [
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+        "rnbqkb1r/pp1p1ppp/5n2/2pPp3/8/8/PPP1PPPP/RNBQKBNR w KQkq c6 0 4",
+    ] {
+        assert_unmake_restores(&mut Position::from_fen(fen).unwrap(), 2);
+    }
+}
+
+#[test]
+fn test_make_move_castling() {
+    let position =
+        Position::from_fen("r3k2r/pppppppp/8/8/8/8/PPPPPPPP/R3K2R w KQkq - 3 10").unwrap();
+    let after = position.apply(find_move(&position, "e1c1"));
+    assert!(after.is_occupied_by_piece(C1, Piece::WhiteKing));
+    assert!(after.is_occupied_by_piece(D1, Piece::WhiteRook));
+    assert!(!after.is_occupied(A1));
+    assert!(!after.get_castling_right(CastlingType::WhiteKingside));
+    assert!(after.get_castling_right(CastlingType::BlackQueenside));
+    assert_eq!(after.get_player(), Color::Black);
+    assert_eq!(after.get_halfmove_clock(), 4);
+}
+
+#[test]
+fn test_make_move_en_passant() {
+    let position =
+        Position::from_fen("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3")
+            .unwrap();
+    let after = position.apply(find_move(&position, "e5f6"));
+    assert!(after.is_occupied_by_piece(F6, Piece::WhitePawn));
+    assert!(!after.is_occupied(F5));
+    assert!(!after.is_occupied(E5));
+    assert_eq!(after.get_en_passant(), None);
+    assert_eq!(after.get_halfmove_clock(), 0);
+    assert_eq!(after.get_fullmove_number(), 3);
+}
+
+#[test]
+fn test_make_move_promotion_capture() {
+    let position = Position::from_fen("r3k3/1P6/8/8/8/8/8/4K3 w q - 0 1").unwrap();
+    let after = position.apply(find_move(&position, "b7a8n"));
+    assert!(after.is_occupied_by_piece(A8, Piece::WhiteKnight));
+    assert!(!after.is_occupied(B7));
+    assert!(!after.get_castling_right(CastlingType::BlackQueenside));
+    assert_eq!(after.get_zobrist_key(), after.compute_zobrist_key());
+}
+
+#[test]
+fn test_make_move_double_push_sets_en_passant() {
+    let position = Position::new_starting_position();
+    let after = position.apply(find_move(&position, "e2e4"));
+    assert_eq!(after.get_en_passant(), Some(E4));
+    assert_eq!(
+        after.to_fen(),
+        "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1"
+    );
+}
+
+#[test]
+fn test_null_move() {
+    let mut position =
+        Position::from_fen("rnbqkb1r/pp1p1ppp/5n2/2pPp3/8/8/PPP1PPPP/RNBQKBNR w KQkq c6 3 4")
+            .unwrap();
+    let before = position;
+    let undo = position.make_null_move();
+    assert_eq!(position.get_player(), Color::Black);
+    assert_eq!(position.get_en_passant(), None);
+    assert_eq!(position.get_halfmove_clock(), 0);
+    assert_eq!(position.get_zobrist_key(), position.compute_zobrist_key());
+    position.unmake_null_move(undo);
+    assert_eq!(position, before);
+    assert_eq!(position.get_halfmove_clock(), 3);
+}
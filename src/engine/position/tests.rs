@@ -36,9 +36,6 @@ fn test_remove_white_king() {
 fn test_put_white_king() {
     let position = Position::new_starting_position();
     let new_position = position.put_piece(Piece::WhiteKing, E2);
-    assert_ne!(position.is_occupied_by_piece(E2, Piece::WhiteKing), true);
-    assert_eq!(
-        new_position.is_occupied_by_piece(E2, Piece::WhiteKing),
-        true
-    );
+    assert!(!position.is_occupied_by_piece(E2, Piece::WhiteKing));
+    assert!(new_position.is_occupied_by_piece(E2, Piece::WhiteKing));
 }
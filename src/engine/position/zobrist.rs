@@ -0,0 +1,117 @@
+use crate::engine::{
+    directions::{self, squares::Square},
+    piece::{Color, Piece, Typ},
+    position::{CastlingType, Position},
+};
+
+const PIECE_KEYS: [[u64; 64]; 12] = generate_piece_keys();
+const CASTLING_KEYS: [u64; 4] = generate_keys::<4>(0x2545_f491_4f6c_dd1d);
+const EN_PASSANT_KEYS: [u64; 8] = generate_keys::<8>(0x9e37_79b9_7f4a_7c15);
+const BLACK_TO_MOVE_KEY: u64 = splitmix64(0xd1b5_4a32_d192_ed03);
+
+// The keys are generated at compile time so they are the same in every run.
+const fn splitmix64(state: u64) -> u64 {
+    let mut z = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+const fn generate_keys<const N: usize>(seed: u64) -> [u64; N] {
+    let mut keys = [0; N];
+    let mut state = seed;
+    let mut i = 0;
+    while i < N {
+        state = splitmix64(state);
+        keys[i] = state;
+        i += 1;
+    }
+    keys
+}
+
+const fn generate_piece_keys() -> [[u64; 64]; 12] {
+    let mut keys = [[0; 64]; 12];
+    let mut piece = 0;
+    while piece < 12 {
+        keys[piece] = generate_keys::<64>(piece as u64 * 0x1000_0000_0001);
+        piece += 1;
+    }
+    keys
+}
+
+pub fn piece_key(piece: Piece, square: Square) -> u64 {
+    PIECE_KEYS[piece as usize][square.as_index() as usize]
+}
+
+pub fn castling_key(castling_type: CastlingType) -> u64 {
+    CASTLING_KEYS[castling_type.as_index()]
+}
+
+/// Only the column of the en passant pawn is part of the key.
+pub fn en_passant_key(square: Square) -> u64 {
+    EN_PASSANT_KEYS[directions::get_column(square) as usize - 1]
+}
+
+pub fn black_to_move_key() -> u64 {
+    BLACK_TO_MOVE_KEY
+}
+
+impl Position {
+    /// Computes the key from scratch. The builder methods keep `zobrist_key` up to date
+    /// incrementally, this is needed when fields are set directly.
+    pub fn compute_zobrist_key(&self) -> u64 {
+        let mut key = 0;
+        for (square, piece) in self.get_all_pieces() {
+            key ^= piece_key(piece, square);
+        }
+        for castling_type in [
+            CastlingType::BlackQueenside,
+            CastlingType::BlackKingside,
+            CastlingType::WhiteQueenside,
+            CastlingType::WhiteKingside,
+        ] {
+            if self.get_castling_right(castling_type) {
+                key ^= castling_key(castling_type);
+            }
+        }
+        if let Some(square) = self.get_capturable_en_passant() {
+            key ^= en_passant_key(square);
+        }
+        if self.player == Color::Black {
+            key ^= black_to_move_key();
+        }
+        key
+    }
+
+    /// Computes the key of the pawns alone from scratch.
+    pub fn compute_pawn_key(&self) -> u64 {
+        let mut key = 0;
+        for (square, piece) in self.get_all_pieces() {
+            if piece.get_type() == Typ::Pawn {
+                key ^= piece_key(piece, square);
+            }
+        }
+        key
+    }
+
+    pub fn get_zobrist_key(&self) -> u64 {
+        self.zobrist_key
+    }
+
+    /// Equal for all positions with the same pawns, whatever the other pieces do.
+    pub fn get_pawn_key(&self) -> u64 {
+        self.pawn_key
+    }
+
+    /// Adds or removes `piece` on `square` in the keys.
+    pub(crate) fn toggle_piece_key(&mut self, piece: Piece, square: Square) {
+        let key = piece_key(piece, square);
+        self.zobrist_key ^= key;
+        if piece.get_type() == Typ::Pawn {
+            self.pawn_key ^= key;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,106 @@
+use crate::engine::{
+    chess_moves::get_current_player_moves, directions::squares::*, piece::Piece, position::Position,
+};
+
+fn assert_incremental_keys(position: &Position, depth: u8) {
+    assert_eq!(
+        position.get_zobrist_key(),
+        position.compute_zobrist_key(),
+        "{}",
+        position.to_fen()
+    );
+    assert_eq!(
+        position.get_pawn_key(),
+        position.compute_pawn_key(),
+        "{}",
+        position.to_fen()
+    );
+    if depth > 0 {
+        for chess_move in get_current_player_moves(position) {
+            assert_incremental_keys(&position.apply(chess_move), depth - 1);
+        }
+    }
+}
+
+#[test]
+fn test_incremental_keys_match_computed_keys() {
+    // Castling, en passant and promotions all occur within three plies.
+    let fens = [
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+    ];
+    for fen in fens {
+        assert_incremental_keys(&Position::from_fen(fen).unwrap(), 3);
+    }
+}
+
+#[test]
+fn test_starting_position_key() {
+    let position = Position::new_starting_position();
+    assert_eq!(position.get_zobrist_key(), position.compute_zobrist_key());
+    assert_eq!(
+        position.get_zobrist_key(),
+        Position::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1")
+            .unwrap()
+            .get_zobrist_key()
+    );
+}
+
+#[test]
+fn test_transpositions_have_equal_keys() {
+    let position = Position::new_starting_position();
+    let via_knights =
+        Position::from_fen("r1bqkbnr/pppppppp/2n5/8/8/5N2/PPPPPPPP/RNBQKB1R w KQkq - 2 2").unwrap();
+    let moved = position
+        .remove_piece(G1)
+        .put_piece(Piece::WhiteKnight, F3)
+        .toggle_player()
+        .remove_piece(B8)
+        .put_piece(Piece::BlackKnight, C6)
+        .toggle_player();
+    assert_eq!(moved.get_zobrist_key(), via_knights.get_zobrist_key());
+}
+
+#[test]
+fn test_state_changes_the_key() {
+    let position = Position::new_starting_position();
+    assert_ne!(
+        position.get_zobrist_key(),
+        position.toggle_player().get_zobrist_key()
+    );
+    assert_ne!(
+        position.get_zobrist_key(),
+        position
+            .remove_castling_right(crate::engine::position::CastlingType::WhiteKingside)
+            .get_zobrist_key()
+    );
+    let pushed = position.remove_piece(E2).put_piece(Piece::WhitePawn, E4);
+    // Without a black pawn next to it, the pawn cannot be taken en passant.
+    assert_eq!(
+        pushed.get_zobrist_key(),
+        pushed.set_en_passant(E4).get_zobrist_key()
+    );
+    let en_passant = pushed.remove_piece(D7).put_piece(Piece::BlackPawn, D4);
+    assert_ne!(
+        en_passant.get_zobrist_key(),
+        en_passant.set_en_passant(E4).get_zobrist_key()
+    );
+    assert_eq!(
+        en_passant.get_zobrist_key(),
+        en_passant
+            .set_en_passant(E4)
+            .reset_en_passant()
+            .get_zobrist_key()
+    );
+}
+
+#[test]
+fn test_pawn_key_ignores_other_pieces() {
+    let position = Position::new_starting_position();
+    let moved = position.remove_piece(G1).put_piece(Piece::WhiteKnight, F3);
+    assert_eq!(moved.get_pawn_key(), position.get_pawn_key());
+    assert_ne!(moved.get_zobrist_key(), position.get_zobrist_key());
+    let pushed = position.remove_piece(E2).put_piece(Piece::WhitePawn, E4);
+    assert_ne!(pushed.get_pawn_key(), position.get_pawn_key());
+}
@@ -1,41 +1,64 @@
-use std::sync::Mutex;
+use std::{sync::atomic::AtomicBool, time::Duration};
 
 use crate::engine::{
-    cache::Cache, chess_moves::ChessMove, position::Position,
-    search_algorithms::alpha_beta::alpha_beta,
+    chess_moves::ChessMove,
+    game_history::GameHistory,
+    position::Position,
+    search_algorithms::{
+        alpha_beta::{alpha_beta, AlphaBetaResult},
+        context::SearchContext,
+        iterative_deepening::iterative_deepening,
+        limits::SearchLimits,
+    },
+    transposition_table::TranspositionTable,
 };
-lazy_static::lazy_static! {
-    pub static ref CALL_COUNT: Mutex<u64> = Mutex::new(0);
+/// Bounds every search value.
+pub const INFINITY: i32 = 32_000;
+/// The value of mating at the root, a mate `n` plies away is worth `MATE_VALUE - n`.
+pub const MATE_VALUE: i32 = 31_000;
+/// More plies than any search line reaches, quiescence included.
+pub const MAX_PLY: i32 = 256;
+pub const DEFAULT_MOVE_TIME: Duration = Duration::from_secs(2);
+
+/// The value for the side to move when it is mated `ply` plies from the root.
+pub fn mated_in(ply: u8) -> i32 {
+    -MATE_VALUE + ply as i32
+}
+
+pub fn is_mate_value(value: i32) -> bool {
+    value.abs() > MATE_VALUE - MAX_PLY
 }
-pub const MAX_VALUE: f32 = f32::MAX;
-pub const MIN_VALUE: f32 = f32::MIN;
 
-#[derive(Debug, Clone, Copy)]
-pub enum Player {
-    #[allow(unused)]
-    Min,
-    Max,
+pub fn get_best_move(
+    history: &GameHistory,
+    limits: &SearchLimits,
+    table: &mut TranspositionTable,
+) -> Option<ChessMove> {
+    let stop = AtomicBool::new(false);
+    iterative_deepening(history, limits, &stop, table, |_| {})
+        .and_then(|iteration| iteration.best_move())
 }
 
-pub fn get_best_move(position: Position) -> Option<ChessMove> {
-    let cache = &mut Cache::new();
-    let depth = 4;
-    //let tree = build_tree(position, depth);
-    let minimx_player = match position.get_player() {
-        crate::engine::piece::Color::Black => Player::Min,
-        crate::engine::piece::Color::White => Player::Max,
-    };
-    let best_move =
-        alpha_beta(&position, minimx_player, MIN_VALUE, MAX_VALUE, depth, cache).best_move;
-    let mut hits = 0;
-    for evaluation in cache.values() {
-        hits += evaluation.hits;
-    }
-    println!("Cache size = {:?}", cache.len());
-    println!("Number of hits = {:?}", hits);
-    best_move
+/// Runs a fixed depth search that tries `pv` first. The returned value is from the point
+/// of view of the side to move.
+pub fn search(
+    position: &Position,
+    depth: u8,
+    pv: &[ChessMove],
+    context: &mut SearchContext,
+) -> AlphaBetaResult {
+    let mut position = *position;
+    alpha_beta(&mut position, -INFINITY, INFINITY, depth, 0, pv, context)
 }
 pub mod alpha_beta;
+pub mod config;
+pub mod context;
+pub mod iterative_deepening;
+pub mod limits;
 pub mod minimax;
+pub mod move_ordering;
 pub mod node;
+pub mod quiescence;
+pub mod score;
+pub mod search_info;
 mod tests;
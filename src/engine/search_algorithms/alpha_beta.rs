@@ -1,97 +1,289 @@
 use crate::engine::{
-    cache::Cache,
+    check::is_check,
     chess_moves::ChessMove,
     position::Position,
     search_algorithms::{
-        node::{evaluate, get_children},
-        Player, MAX_VALUE, MIN_VALUE,
+        context::SearchContext,
+        is_mate_value,
+        iterative_deepening::MAX_DEPTH,
+        move_ordering::is_quiet,
+        node::{get_children, get_static_value, get_terminal_value, has_non_pawn_material},
+        quiescence::quiescence,
+        INFINITY,
     },
+    transposition_table::Bound,
 };
 
+// Below this depth the null move search would go straight into quiescence anyway.
+const NULL_MOVE_MIN_DEPTH: u8 = 3;
+const LATE_MOVE_MIN_DEPTH: u8 = 3;
+// The hash move, the best captures and the killers come first and are never reduced.
+const LATE_MOVE_MIN_INDEX: usize = 3;
+const LATE_MOVE_DEEP_INDEX: usize = 6;
+
 pub struct AlphaBetaResult {
-    pub value: f32,
-    pub leaf: Option<Position>,
-    pub best_move: Option<ChessMove>,
+    /// From the point of view of the side to move.
+    pub value: i32,
+    pub pv: Vec<ChessMove>,
 }
+
+/// A negamax search. `pv` is the principal variation of a previous search starting at
+/// `position`, its moves are searched first. `ply` is the distance to the root, whose
+/// result is never taken from the transposition table. Mate values count the plies
+/// from the root, so shorter mates are preferred. The selective techniques of
+/// `context.config` prune, reduce and extend the search. The result is meaningless once
+/// `context` aborted.
 pub fn alpha_beta(
-    position: &Position,
-    player: Player,
-    mut alpha: f32,
-    mut beta: f32,
+    position: &mut Position,
+    mut alpha: i32,
+    mut beta: i32,
     depth: u8,
-    cache: &mut Cache,
+    ply: u8,
+    pv: &[ChessMove],
+    context: &mut SearchContext,
 ) -> AlphaBetaResult {
+    if context.visit_node(ply) {
+        return AlphaBetaResult {
+            value: 0,
+            pv: Vec::new(),
+        };
+    }
     let children = get_children(position);
-    if depth == 0 || children.is_empty() {
-        AlphaBetaResult {
-            value: evaluate(position, cache),
-            leaf: Some(*position),
-            best_move: None,
-        }
+    if ply > 0 && !children.is_empty() && context.is_draw(position) {
+        return AlphaBetaResult {
+            value: 0,
+            pv: Vec::new(),
+        };
+    }
+    if children.is_empty() {
+        return AlphaBetaResult {
+            value: get_terminal_value(position, ply),
+            pv: Vec::new(),
+        };
+    }
+    let in_check = is_check(position, position.get_player());
+    // Checks are searched deeper so forcing lines don't vanish behind the horizon.
+    let depth = if in_check && context.config.check_extensions && ply < MAX_DEPTH {
+        depth + 1
     } else {
-        match player {
-            Player::Max => {
-                let mut max_value = MIN_VALUE;
-                let mut max_move: Option<ChessMove> = None;
-                let mut leaf: Option<Position> = None;
-                for child in children {
-                    let alpha_beta_result =
-                        alpha_beta(&child.position, Player::Min, alpha, beta, depth - 1, cache);
-                    if alpha_beta_result.value > max_value {
-                        max_value = alpha_beta_result.value;
-                        max_move = Some(child);
-                        leaf = alpha_beta_result.leaf;
-                    }
-                    alpha = max(alpha, max_value);
-                    if beta <= alpha {
-                        break;
-                    }
-                }
-                AlphaBetaResult {
-                    value: max_value,
-                    leaf,
-                    best_move: max_move,
-                }
+        depth
+    };
+    if depth == 0 {
+        return AlphaBetaResult {
+            value: quiescence(position, alpha, beta, ply, context),
+            pv: Vec::new(),
+        };
+    }
+    let key = position.get_zobrist_key();
+    let entry = context.table.probe(key);
+    let hash_move = entry.and_then(|entry| entry.best_move);
+    if let Some(entry) = entry {
+        if ply > 0 && entry.depth >= depth {
+            let value = value_from_table(entry.value, ply);
+            match entry.bound {
+                Bound::Exact => return table_result(value, hash_move, &children),
+                Bound::Lower => alpha = alpha.max(value),
+                Bound::Upper => beta = beta.min(value),
             }
-            Player::Min => {
-                let mut min_value = MAX_VALUE;
-                let mut min_move: Option<ChessMove> = None;
-                let mut leaf: Option<Position> = None;
-                for child in children {
-                    let alpha_beta_result =
-                        alpha_beta(&child.position, Player::Max, alpha, beta, depth - 1, cache);
-                    if alpha_beta_result.value < min_value {
-                        min_value = alpha_beta_result.value;
-                        min_move = Some(child);
-                        leaf = alpha_beta_result.leaf;
-                    }
-                    beta = min(beta, min_value);
-                    if beta <= alpha {
-                        break;
-                    }
-                }
-                AlphaBetaResult {
-                    value: min_value,
-                    leaf,
-                    best_move: min_move,
-                }
+            if alpha >= beta {
+                return table_result(value, hash_move, &children);
             }
         }
     }
+    // If passing the move still fails high, a real move would too. This does not hold in
+    // zugzwang, which is likely without pieces besides pawns.
+    if ply > 0
+        && context.config.null_move_pruning
+        && !in_check
+        && depth >= NULL_MOVE_MIN_DEPTH
+        && !is_mate_value(beta)
+        && has_non_pawn_material(position)
+        && get_static_value(position, context.params, &mut context.pawn_table) >= beta
+    {
+        let reduction = if depth > 6 { 3 } else { 2 };
+        let undo = position.make_null_move();
+        context.push_key(key);
+        let null_move_result = alpha_beta(
+            position,
+            -beta,
+            -beta + 1,
+            depth - 1 - reduction,
+            ply + 1,
+            &[],
+            context,
+        );
+        context.pop_key();
+        position.unmake_null_move(undo);
+        if -null_move_result.value >= beta {
+            return AlphaBetaResult {
+                value: beta,
+                pv: Vec::new(),
+            };
+        }
+    }
+    let (alpha_start, beta_start) = (alpha, beta);
+    let first_move = pv.first().copied().or(hash_move);
+    let children = context.ordering.order(children, first_move, ply);
+    context.push_key(key);
+    let mut best_value = -INFINITY;
+    let mut best_pv: Vec<ChessMove> = Vec::new();
+    for (index, child) in children.into_iter().enumerate() {
+        let undo = position.make_move(child);
+        let child_pv = child_pv(pv, &child);
+        let gives_check = is_check(position, position.get_player());
+        let reduction = get_reduction(context, child, index, depth, ply, in_check || gives_check);
+        // Reduced and zero window searches only prove that a move is not better than
+        // alpha. A move that beats alpha anyway is searched again with the full window.
+        let mut alpha_beta_result = None;
+        if reduction > 0 {
+            let result = alpha_beta(
+                position,
+                -alpha - 1,
+                -alpha,
+                depth - 1 - reduction,
+                ply + 1,
+                child_pv,
+                context,
+            );
+            if -result.value <= alpha {
+                alpha_beta_result = Some(result);
+            }
+        }
+        if alpha_beta_result.is_none() && index > 0 && context.config.principal_variation_search {
+            let result = alpha_beta(
+                position,
+                -alpha - 1,
+                -alpha,
+                depth - 1,
+                ply + 1,
+                child_pv,
+                context,
+            );
+            if -result.value <= alpha || -result.value >= beta {
+                alpha_beta_result = Some(result);
+            }
+        }
+        let alpha_beta_result = match alpha_beta_result {
+            Some(result) => result,
+            None => alpha_beta(
+                position,
+                -beta,
+                -alpha,
+                depth - 1,
+                ply + 1,
+                child_pv,
+                context,
+            ),
+        };
+        position.unmake_move(child, undo);
+        let value = -alpha_beta_result.value;
+        // Even a lost position needs a move, so the first child is always taken.
+        if value > best_value || best_pv.is_empty() {
+            best_value = value;
+            best_pv = with_first_move(child, alpha_beta_result.pv);
+        }
+        alpha = alpha.max(best_value);
+        if alpha >= beta {
+            context.ordering.add_cutoff(child, depth, ply);
+            break;
+        }
+    }
+    let result = AlphaBetaResult {
+        value: best_value,
+        pv: best_pv,
+    };
+    context.pop_key();
+    if !context.is_aborted() {
+        let bound = if result.value <= alpha_start {
+            Bound::Upper
+        } else if result.value >= beta_start {
+            Bound::Lower
+        } else {
+            Bound::Exact
+        };
+        let best_move = result.pv.first().copied();
+        context.table.store(
+            key,
+            depth,
+            bound,
+            value_to_table(result.value, ply),
+            best_move,
+        );
+    }
+    result
+}
+
+/// How many plies less than usual `chess_move` is searched. Only quiet moves late in the
+/// move order are reduced, and none in check or giving check.
+fn get_reduction(
+    context: &SearchContext,
+    chess_move: ChessMove,
+    index: usize,
+    depth: u8,
+    ply: u8,
+    is_check_involved: bool,
+) -> u8 {
+    if !context.config.late_move_reductions
+        || index < LATE_MOVE_MIN_INDEX
+        || depth < LATE_MOVE_MIN_DEPTH
+        || is_check_involved
+        || !is_quiet(chess_move)
+        || context.ordering.is_killer(chess_move, ply)
+    {
+        0
+    } else if index >= LATE_MOVE_DEEP_INDEX && depth > LATE_MOVE_DEEP_INDEX as u8 {
+        2
+    } else {
+        1
+    }
+}
+
+/// A result taken from the transposition table. Its principal variation ends with the hash move.
+fn table_result(
+    value: i32,
+    hash_move: Option<ChessMove>,
+    children: &[ChessMove],
+) -> AlphaBetaResult {
+    let pv = hash_move
+        .and_then(|hash_move| children.iter().find(|child| **child == hash_move))
+        .map(|child| vec![*child])
+        .unwrap_or_default();
+    AlphaBetaResult { value, pv }
+}
+
+/// The rest of the principal variation, if `child` continues it.
+fn child_pv<'a>(pv: &'a [ChessMove], child: &ChessMove) -> &'a [ChessMove] {
+    match pv.split_first() {
+        Some((pv_move, rest)) if pv_move == child => rest,
+        _ => &[],
+    }
+}
+
+fn with_first_move(chess_move: ChessMove, pv: Vec<ChessMove>) -> Vec<ChessMove> {
+    let mut new_pv = Vec::with_capacity(pv.len() + 1);
+    new_pv.push(chess_move);
+    new_pv.extend(pv);
+    new_pv
 }
 
-fn max(a: f32, b: f32) -> f32 {
-    if a > b {
-        a
+/// Mate values are stored counted from the node instead of the root, so they stay
+/// right when the position is reached at another ply.
+fn value_to_table(value: i32, ply: u8) -> i32 {
+    if !is_mate_value(value) {
+        value
+    } else if value > 0 {
+        value + ply as i32
     } else {
-        b
+        value - ply as i32
     }
 }
 
-fn min(a: f32, b: f32) -> f32 {
-    if a < b {
-        a
+fn value_from_table(value: i32, ply: u8) -> i32 {
+    if !is_mate_value(value) {
+        value
+    } else if value > 0 {
+        value - ply as i32
     } else {
-        b
+        value + ply as i32
     }
 }
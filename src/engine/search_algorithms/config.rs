@@ -0,0 +1,41 @@
+/// The selective search techniques and the number of search threads. Each technique can
+/// be switched off on its own to measure what it is worth, all are on by default.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SearchConfig {
+    /// Skip a move and prune when a reduced search still fails high.
+    pub null_move_pruning: bool,
+    /// Search quiet moves late in the move order less deep.
+    pub late_move_reductions: bool,
+    /// Search all but the first move with a zero window, re-searching those that beat it.
+    pub principal_variation_search: bool,
+    /// Search one ply deeper when the side to move is in check.
+    pub check_extensions: bool,
+    /// Threads searching the same position and sharing the transposition table. With a
+    /// single thread the search is deterministic.
+    pub threads: usize,
+}
+
+impl Default for SearchConfig {
+    fn default() -> Self {
+        SearchConfig {
+            null_move_pruning: true,
+            late_move_reductions: true,
+            principal_variation_search: true,
+            check_extensions: true,
+            threads: 1,
+        }
+    }
+}
+
+impl SearchConfig {
+    /// A plain alpha-beta search without any of the techniques.
+    pub fn plain() -> Self {
+        SearchConfig {
+            null_move_pruning: false,
+            late_move_reductions: false,
+            principal_variation_search: false,
+            check_extensions: false,
+            threads: 1,
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::Instant,
+};
+
+use crate::engine::{
+    evaluation::{
+        params::{EvalParams, DEFAULT_PARAMS},
+        pawn_table::PawnTable,
+    },
+    position::Position,
+    search_algorithms::{config::SearchConfig, move_ordering::MoveOrdering},
+    transposition_table::TranspositionTable,
+};
+
+const FIFTY_MOVE_RULE_HALFMOVES: u32 = 100;
+
+// Looking at the clock for every node would cost more than the nodes themselves.
+const NODES_BETWEEN_CHECKS: u64 = 256;
+
+/// The state shared by all nodes of one search. It counts nodes and decides when a
+/// running iteration has to be abandoned.
+pub struct SearchContext<'a> {
+    pub table: &'a TranspositionTable,
+    pub ordering: MoveOrdering,
+    pub config: SearchConfig,
+    pub params: &'a EvalParams,
+    /// Not shared between threads, unlike `table`.
+    pub pawn_table: PawnTable,
+    stop: &'a AtomicBool,
+    /// Keys of the game's earlier positions followed by those on the current search path.
+    keys: Vec<u64>,
+    deadline: Option<Instant>,
+    max_nodes: Option<u64>,
+    nodes: u64,
+    seldepth: u8,
+    interruptible: bool,
+    aborted: bool,
+}
+
+impl<'a> SearchContext<'a> {
+    pub fn new(
+        table: &'a TranspositionTable,
+        stop: &'a AtomicBool,
+        keys: Vec<u64>,
+    ) -> SearchContext<'a> {
+        SearchContext {
+            table,
+            ordering: MoveOrdering::default(),
+            config: SearchConfig::default(),
+            params: &DEFAULT_PARAMS,
+            pawn_table: PawnTable::default(),
+            stop,
+            keys,
+            deadline: None,
+            max_nodes: None,
+            nodes: 0,
+            seldepth: 0,
+            interruptible: false,
+            aborted: false,
+        }
+    }
+
+    pub fn get_nodes(&self) -> u64 {
+        self.nodes
+    }
+
+    /// The deepest ply visited so far.
+    pub fn get_seldepth(&self) -> u8 {
+        self.seldepth
+    }
+
+    pub fn is_aborted(&self) -> bool {
+        self.aborted
+    }
+
+    pub fn push_key(&mut self, key: u64) {
+        self.keys.push(key);
+    }
+
+    pub fn pop_key(&mut self) {
+        self.keys.pop();
+    }
+
+    /// A position that occurred before or falls under the fifty-move rule is scored as a draw.
+    /// A single repetition is enough, repeating it again cannot be better.
+    pub fn is_draw(&self, position: &Position) -> bool {
+        let halfmove_clock = position.get_halfmove_clock();
+        halfmove_clock >= FIFTY_MOVE_RULE_HALFMOVES
+            || self
+                .keys
+                .iter()
+                .rev()
+                .take(halfmove_clock as usize)
+                .any(|key| *key == position.get_zobrist_key())
+    }
+
+    /// From now on the search is abandoned when `stop` is set or a limit is reached.
+    pub fn make_interruptible(&mut self, deadline: Option<Instant>, max_nodes: Option<u64>) {
+        self.interruptible = true;
+        self.deadline = deadline;
+        self.max_nodes = max_nodes;
+    }
+
+    /// Counts a visited node `ply` plies from the root and returns true if the search has
+    /// to be abandoned.
+    pub fn visit_node(&mut self, ply: u8) -> bool {
+        self.nodes += 1;
+        self.seldepth = self.seldepth.max(ply);
+        if !self.interruptible || self.aborted {
+            return self.aborted;
+        }
+        if let Some(max_nodes) = self.max_nodes {
+            if self.nodes >= max_nodes {
+                self.aborted = true;
+            }
+        }
+        if self.nodes.is_multiple_of(NODES_BETWEEN_CHECKS) {
+            if self.stop.load(Ordering::Relaxed) {
+                self.aborted = true;
+            }
+            if let Some(deadline) = self.deadline {
+                if Instant::now() >= deadline {
+                    self.aborted = true;
+                }
+            }
+        }
+        self.aborted
+    }
+}
@@ -0,0 +1,152 @@
+use std::{
+    ops::RangeInclusive,
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+    thread,
+    time::Instant,
+};
+
+use crate::engine::{
+    game_history::GameHistory,
+    position::Position,
+    search_algorithms::{
+        context::SearchContext, limits::SearchLimits, search, search_info::SearchInfo,
+    },
+    transposition_table::TranspositionTable,
+};
+
+pub const MAX_DEPTH: u8 = 64;
+
+/// Searches the current position of `history` one ply deeper per iteration until a limit
+/// is reached or `stop` is set. `on_info` receives every completed iteration while the
+/// search runs, the last one is returned. The first
+/// iteration always completes so there is a move to play. Every iteration searches the
+/// previous principal variation first. Repetitions of earlier positions count as draws.
+///
+/// With more than one thread in `limits.config`, helper threads search the same position
+/// until the main thread is done, Lazy SMP style. They only share results through
+/// `table`, which changes the main thread's search from run to run. The reported nodes
+/// include those of the helpers, the node limit only counts the main thread's.
+pub fn iterative_deepening<F: FnMut(&SearchInfo)>(
+    history: &GameHistory,
+    limits: &SearchLimits,
+    stop: &AtomicBool,
+    table: &mut TranspositionTable,
+    mut on_info: F,
+) -> Option<SearchInfo> {
+    let start = Instant::now();
+    let time_budget = limits.time_budget();
+    let max_depth = limits.max_depth.unwrap_or(MAX_DEPTH).clamp(1, MAX_DEPTH);
+    let position = &history.get_current();
+    let positions = history.get_positions();
+    let keys: Vec<u64> = positions[..positions.len() - 1]
+        .iter()
+        .map(|position| position.get_zobrist_key())
+        .collect();
+    table.new_search();
+    let table: &TranspositionTable = table;
+    let helpers_stop = AtomicBool::new(false);
+    let helper_nodes: Vec<AtomicU64> = (1..limits.config.threads)
+        .map(|_| AtomicU64::new(0))
+        .collect();
+    thread::scope(|scope| {
+        for (index, nodes) in helper_nodes.iter().enumerate() {
+            let keys: Vec<u64> = keys.clone();
+            let helpers_stop = &helpers_stop;
+            // Half of the helpers start one ply deeper so the threads spread over depths.
+            let start_depth = (1 + index as u8 % 2).min(max_depth);
+            scope.spawn(move || {
+                run_helper(
+                    position,
+                    keys,
+                    limits,
+                    start_depth..=max_depth,
+                    table,
+                    helpers_stop,
+                    nodes,
+                )
+            });
+        }
+        let mut context = SearchContext::new(table, stop, keys);
+        context.config = limits.config;
+        context.params = limits.get_params();
+        let get_nodes = |context: &SearchContext| {
+            context.get_nodes()
+                + helper_nodes
+                    .iter()
+                    .map(|nodes| nodes.load(Ordering::Relaxed))
+                    .sum::<u64>()
+        };
+        let mut last_iteration: Option<SearchInfo> = None;
+        for depth in 1..=max_depth {
+            let pv = match &last_iteration {
+                Some(iteration) => iteration.pv.clone(),
+                None => Vec::new(),
+            };
+            let result = search(position, depth, &pv, &mut context);
+            if context.is_aborted() || result.pv.is_empty() {
+                break;
+            }
+            let iteration = SearchInfo {
+                depth,
+                seldepth: context.get_seldepth(),
+                value: result.value,
+                pv: result.pv,
+                nodes: get_nodes(&context),
+                elapsed: start.elapsed(),
+                hash_fill: context.table.get_fill_permille(),
+                pawn_hits: context.pawn_table.get_hit_permille(),
+            };
+            on_info(&iteration);
+            last_iteration = Some(iteration);
+
+            if stop.load(Ordering::Relaxed) {
+                break;
+            }
+            if let Some(max_nodes) = limits.max_nodes {
+                if context.get_nodes() >= max_nodes {
+                    break;
+                }
+            }
+            if let Some(time_budget) = time_budget {
+                // The next iteration takes several times longer than this one,
+                // don't start it if it cannot finish within the budget.
+                if start.elapsed() * 2 >= time_budget {
+                    break;
+                }
+            }
+            // From here on a running iteration may be abandoned.
+            context.make_interruptible(
+                time_budget.map(|time_budget| start + time_budget),
+                limits.max_nodes,
+            );
+        }
+        helpers_stop.store(true, Ordering::Relaxed);
+        last_iteration
+    })
+}
+
+/// Deepens like the main thread but only to fill `table`, until `stop` is set or the
+/// last depth is searched. Publishes its node count in `nodes` after every iteration.
+fn run_helper(
+    position: &Position,
+    keys: Vec<u64>,
+    limits: &SearchLimits,
+    depths: RangeInclusive<u8>,
+    table: &TranspositionTable,
+    stop: &AtomicBool,
+    nodes: &AtomicU64,
+) {
+    let mut context = SearchContext::new(table, stop, keys);
+    context.config = limits.config;
+    context.params = limits.get_params();
+    context.make_interruptible(None, None);
+    let mut pv = Vec::new();
+    for depth in depths {
+        let result = search(position, depth, &pv, &mut context);
+        nodes.store(context.get_nodes(), Ordering::Relaxed);
+        if context.is_aborted() || result.pv.is_empty() {
+            break;
+        }
+        pv = result.pv;
+    }
+}
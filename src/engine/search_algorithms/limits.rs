@@ -0,0 +1,61 @@
+use std::{sync::Arc, time::Duration};
+
+use crate::engine::{
+    evaluation::params::{EvalParams, DEFAULT_PARAMS},
+    search_algorithms::config::SearchConfig,
+};
+
+/// Share of the remaining clock spent on one move when the moves to go are unknown.
+const DEFAULT_MOVES_TO_GO: u32 = 30;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Clock {
+    pub remaining: Duration,
+    pub increment: Duration,
+    pub moves_to_go: Option<u32>,
+}
+
+/// Bounds for one search and the techniques it uses. Limits that are `None` don't apply,
+/// a search without any limit runs until it is stopped from outside.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SearchLimits {
+    pub max_depth: Option<u8>,
+    pub max_nodes: Option<u64>,
+    pub move_time: Option<Duration>,
+    pub clock: Option<Clock>,
+    pub config: SearchConfig,
+    /// The evaluation parameters, the compiled-in ones if `None`.
+    pub params: Option<Arc<EvalParams>>,
+}
+
+impl SearchLimits {
+    pub fn depth(max_depth: u8) -> SearchLimits {
+        SearchLimits {
+            max_depth: Some(max_depth),
+            ..Default::default()
+        }
+    }
+
+    pub fn move_time(move_time: Duration) -> SearchLimits {
+        SearchLimits {
+            move_time: Some(move_time),
+            ..Default::default()
+        }
+    }
+
+    pub fn get_params(&self) -> &EvalParams {
+        self.params.as_deref().unwrap_or(&DEFAULT_PARAMS)
+    }
+
+    /// Time the search may spend on the current move, if it is limited by time.
+    pub fn time_budget(&self) -> Option<Duration> {
+        if let Some(move_time) = self.move_time {
+            return Some(move_time);
+        }
+        let clock = self.clock?;
+        let moves_to_go = clock.moves_to_go.unwrap_or(DEFAULT_MOVES_TO_GO).max(1);
+        let budget = clock.remaining / moves_to_go + clock.increment / 2;
+        // Never plan to use more than what is left on the clock.
+        Some(budget.min(clock.remaining))
+    }
+}
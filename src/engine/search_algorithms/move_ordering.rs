@@ -0,0 +1,110 @@
+use crate::engine::{
+    chess_moves::ChessMove,
+    piece::{Piece, Typ},
+    search_algorithms::MAX_PLY,
+};
+
+const KILLERS_PER_PLY: usize = 2;
+
+// Every class of moves is searched before the next one, whatever its score inside the class.
+const FIRST_MOVE_SCORE: u64 = 3 << 32;
+const CAPTURE_SCORE: u64 = 2 << 32;
+const KILLER_SCORE: u64 = 1 << 32;
+
+/// What earlier nodes of a search learned about good moves: the killer moves, quiet
+/// moves that caused a cutoff at the same ply, and the history of quiet moves that
+/// caused cutoffs anywhere, by piece and target square.
+pub struct MoveOrdering {
+    killers: [[Option<ChessMove>; KILLERS_PER_PLY]; MAX_PLY as usize],
+    history: [[u32; 64]; 12],
+}
+
+impl Default for MoveOrdering {
+    fn default() -> Self {
+        MoveOrdering {
+            killers: [[None; KILLERS_PER_PLY]; MAX_PLY as usize],
+            history: [[0; 64]; 12],
+        }
+    }
+}
+
+impl MoveOrdering {
+    /// Sorts `chess_moves`: `first_move`, usually the principal variation or hash move,
+    /// then captures and promotions by MVV-LVA, then the killers of `ply`, then quiet
+    /// moves by history. Moves of equal score keep the generator's order.
+    pub fn order(
+        &self,
+        mut chess_moves: Vec<ChessMove>,
+        first_move: Option<ChessMove>,
+        ply: u8,
+    ) -> Vec<ChessMove> {
+        chess_moves.sort_by_cached_key(|chess_move| {
+            std::cmp::Reverse(self.get_score(*chess_move, first_move, ply))
+        });
+        chess_moves
+    }
+
+    /// Remembers a quiet move that caused a beta cutoff `depth` plies above the horizon.
+    /// Captures are ordered well enough by MVV-LVA.
+    pub fn add_cutoff(&mut self, chess_move: ChessMove, depth: u8, ply: u8) {
+        if !is_quiet(chess_move) {
+            return;
+        }
+        let killers = &mut self.killers[ply as usize];
+        if killers[0] != Some(chess_move) {
+            killers[1] = killers[0];
+            killers[0] = Some(chess_move);
+        }
+        // Cutoffs far from the horizon save more nodes.
+        let history = &mut self.history[chess_move.get_piece() as usize]
+            [chess_move.get_to().as_index() as usize];
+        *history = history.saturating_add(depth as u32 * depth as u32);
+    }
+
+    pub fn is_killer(&self, chess_move: ChessMove, ply: u8) -> bool {
+        self.killers[ply as usize].contains(&Some(chess_move))
+    }
+
+    fn get_score(&self, chess_move: ChessMove, first_move: Option<ChessMove>, ply: u8) -> u64 {
+        if Some(chess_move) == first_move {
+            return FIRST_MOVE_SCORE;
+        }
+        if !is_quiet(chess_move) {
+            return CAPTURE_SCORE + get_mvv_lva(chess_move);
+        }
+        let killers = &self.killers[ply as usize];
+        if let Some(index) = killers
+            .iter()
+            .position(|killer| *killer == Some(chess_move))
+        {
+            return KILLER_SCORE - index as u64;
+        }
+        self.history[chess_move.get_piece() as usize][chess_move.get_to().as_index() as usize]
+            as u64
+    }
+}
+
+pub fn is_quiet(chess_move: ChessMove) -> bool {
+    chess_move.get_capture().is_none() && chess_move.get_promotion().is_none()
+}
+
+/// Most valuable victim, least valuable attacker. A promotion counts as capturing the new piece.
+fn get_mvv_lva(chess_move: ChessMove) -> u64 {
+    let victim = chess_move.get_capture().map_or(0, get_rank)
+        + chess_move.get_promotion().map_or(0, get_rank);
+    victim * 8 - get_rank(chess_move.get_piece())
+}
+
+fn get_rank(piece: Piece) -> u64 {
+    match piece.get_type() {
+        Typ::Pawn => 1,
+        Typ::Knight => 2,
+        Typ::Bishop => 3,
+        Typ::Rook => 4,
+        Typ::Queen => 5,
+        Typ::King => 6,
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,62 @@
+use crate::engine::{
+    chess_moves::{get_current_player_moves, ChessMove},
+    directions::squares::*,
+    position::Position,
+    search_algorithms::move_ordering::MoveOrdering,
+};
+
+fn find_move(chess_moves: &[ChessMove], from: Square, to: Square) -> ChessMove {
+    *chess_moves
+        .iter()
+        .find(|chess_move| chess_move.get_from() == from && chess_move.get_to() == to)
+        .unwrap()
+}
+
+fn squares(chess_moves: &[ChessMove]) -> Vec<(Square, Square)> {
+    chess_moves
+        .iter()
+        .map(|chess_move| (chess_move.get_from(), chess_move.get_to()))
+        .collect()
+}
+
+#[test]
+fn test_captures_by_mvv_lva() {
+    // The queen and the pawn can both take the rook, the queen can also take a pawn.
+    let position = Position::from_fen("4k3/8/8/2r5/1P6/8/3p4/2Q4K w - - 0 1").unwrap();
+    let chess_moves = get_current_player_moves(&position);
+    let ordered = MoveOrdering::default().order(chess_moves, None, 0);
+    assert_eq!(squares(&ordered[..3]), vec![(B4, C5), (C1, C5), (C1, D2)]);
+}
+
+#[test]
+fn test_first_move_killers_and_history() {
+    let position = Position::new_starting_position();
+    let chess_moves = get_current_player_moves(&position);
+    let mut ordering = MoveOrdering::default();
+    ordering.add_cutoff(find_move(&chess_moves, G1, F3), 3, 1);
+    ordering.add_cutoff(find_move(&chess_moves, B1, C3), 2, 1);
+    ordering.add_cutoff(find_move(&chess_moves, D2, D4), 4, 2);
+    let first_move = find_move(&chess_moves, E2, E4);
+
+    let ordered = ordering.order(chess_moves.clone(), Some(first_move), 1);
+    assert_eq!(
+        squares(&ordered[..4]),
+        vec![(E2, E4), (B1, C3), (G1, F3), (D2, D4)]
+    );
+    // At another ply only the history is left, which prefers the deeper cutoffs.
+    let ordered = ordering.order(chess_moves, None, 3);
+    assert_eq!(squares(&ordered[..3]), vec![(D2, D4), (G1, F3), (B1, C3)]);
+}
+
+#[test]
+fn test_captures_are_not_killers() {
+    let position = Position::from_fen("4k3/8/8/2r5/1P6/8/8/4K3 w - - 0 1").unwrap();
+    let chess_moves = get_current_player_moves(&position);
+    let mut ordering = MoveOrdering::default();
+    let capture = find_move(&chess_moves, B4, C5);
+    ordering.add_cutoff(capture, 3, 0);
+    let quiet = find_move(&chess_moves, E1, E2);
+    ordering.add_cutoff(quiet, 3, 0);
+    let ordered = ordering.order(chess_moves, None, 0);
+    assert_eq!(&ordered[..2], &[capture, quiet]);
+}
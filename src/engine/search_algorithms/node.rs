@@ -1,33 +1,62 @@
 use crate::engine::{
-    cache::Cache,
     check::is_check,
     chess_moves::{get_current_player_moves, ChessMove},
-    evaluation::Evaluation,
-    piece::Color,
+    evaluation::{params::EvalParams, pawn_table::PawnTable, Evaluation},
+    piece::{Color, Piece, Typ},
     position::Position,
-    search_algorithms::{MAX_VALUE, MIN_VALUE},
+    search_algorithms::mated_in,
 };
 
-pub fn evaluate(position: &Position, cache: &mut Cache) -> f32 {
+/// The value of `position` for the side to move, `ply` plies from the root.
+pub fn evaluate(position: &Position, ply: u8) -> i32 {
     if get_current_player_moves(position).is_empty() {
-        if is_check(position, position.get_player()) {
-            match position.get_player() {
-                Color::Black => MAX_VALUE,
-                Color::White => MIN_VALUE,
-            }
-        } else {
-            0.0
-        }
-    } else if let Some(evaluation) = cache.get_mut(position) {
-        evaluation.hits += 1;
-        evaluation.score
+        get_terminal_value(position, ply)
     } else {
-        let evaluation = Evaluation::new(position);
-        cache.insert(*position, evaluation);
-        evaluation.score
+        get_relative_score(position, Evaluation::new(position).score)
     }
 }
 
+/// The evaluation from the point of view of the side to move. The pawn structure is
+/// taken from `pawn_table` when cached there.
+pub fn get_static_value(
+    position: &Position,
+    params: &EvalParams,
+    pawn_table: &mut PawnTable,
+) -> i32 {
+    get_relative_score(
+        position,
+        Evaluation::with_pawn_table(position, params, pawn_table).score,
+    )
+}
+
+fn get_relative_score(position: &Position, score: i32) -> i32 {
+    match position.get_player() {
+        Color::Black => -score,
+        Color::White => score,
+    }
+}
+
+/// The value of a position without legal moves: mate or stalemate.
+pub fn get_terminal_value(position: &Position, ply: u8) -> i32 {
+    if is_check(position, position.get_player()) {
+        mated_in(ply)
+    } else {
+        0
+    }
+}
+
+/// Whether the side to move has a piece besides king and pawns. Without one, zugzwang
+/// is common.
+pub fn has_non_pawn_material(position: &Position) -> bool {
+    [Typ::Queen, Typ::Rook, Typ::Bishop, Typ::Knight]
+        .into_iter()
+        .any(|typ| {
+            !position
+                .get_squares(Piece::new(position.get_player(), typ))
+                .is_empty()
+        })
+}
+
 pub fn get_children(position: &Position) -> Vec<ChessMove> {
     get_current_player_moves(position)
 }
@@ -0,0 +1,99 @@
+use crate::engine::{
+    check::is_check,
+    chess_moves::{get_current_player_moves, ChessMove, MoveType},
+    evaluation::material,
+    position::Position,
+    search_algorithms::{
+        context::SearchContext,
+        node::{get_static_value, get_terminal_value},
+        INFINITY,
+    },
+};
+
+// A capture that cannot lift the static value back to alpha even with this much
+// positional gain on top is not searched.
+const DELTA_MARGIN: i32 = 200;
+
+/// Searches captures and promotions below the horizon until the position is quiet, so
+/// the search does not stop in the middle of an exchange. The side to move may stand
+/// pat on the static evaluation instead of capturing, unless it is in check: then all
+/// evasions are searched. The value is from the point of view of the side to move,
+/// `ply` plies from the root. The result is meaningless once `context` aborted.
+pub fn quiescence(
+    position: &mut Position,
+    mut alpha: i32,
+    beta: i32,
+    ply: u8,
+    context: &mut SearchContext,
+) -> i32 {
+    if context.visit_node(ply) {
+        return 0;
+    }
+    let chess_moves = get_current_player_moves(position);
+    if chess_moves.is_empty() {
+        return get_terminal_value(position, ply);
+    }
+    let in_check = is_check(position, position.get_player());
+    let stand_pat = if in_check {
+        None
+    } else {
+        Some(get_static_value(
+            position,
+            context.params,
+            &mut context.pawn_table,
+        ))
+    };
+    let mut best_value = stand_pat.unwrap_or(-INFINITY);
+    alpha = alpha.max(best_value);
+    if alpha >= beta {
+        return best_value;
+    }
+
+    for chess_move in get_noisy_moves(chess_moves, in_check) {
+        // Delta pruning: the move cannot reach alpha even with a margin for position.
+        if let Some(stand_pat) = stand_pat {
+            if stand_pat + get_gain(chess_move) + DELTA_MARGIN < alpha {
+                continue;
+            }
+        }
+        let undo = position.make_move(chess_move);
+        let value = -quiescence(position, -beta, -alpha, ply + 1, context);
+        position.unmake_move(chess_move, undo);
+        best_value = best_value.max(value);
+        alpha = alpha.max(best_value);
+        if alpha >= beta {
+            break;
+        }
+    }
+    best_value
+}
+
+/// Captures and promotions, most valuable victim first. In check every evasion counts.
+fn get_noisy_moves(chess_moves: Vec<ChessMove>, in_check: bool) -> Vec<ChessMove> {
+    let mut noisy_moves: Vec<ChessMove> = if in_check {
+        chess_moves
+    } else {
+        chess_moves.into_iter().filter(is_noisy).collect()
+    };
+    noisy_moves.sort_by_key(|chess_move| std::cmp::Reverse(get_gain(*chess_move)));
+    noisy_moves
+}
+
+pub fn is_noisy(chess_move: &ChessMove) -> bool {
+    matches!(
+        chess_move.get_move_type(),
+        MoveType::Capture | MoveType::PromotionCapture | MoveType::EnPassant | MoveType::Promotion
+    )
+}
+
+/// The material the move wins at most, ignoring any recapture.
+fn get_gain(chess_move: ChessMove) -> i32 {
+    let capture = chess_move.get_capture().map_or(0, material::get_value);
+    let promotion = chess_move.get_promotion().map_or(0, |piece| {
+        material::get_value(piece) - material::get_value(chess_move.get_piece())
+    });
+    capture + promotion
+}
+
+#[cfg(test)]
+mod tests;
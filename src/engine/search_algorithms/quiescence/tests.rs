@@ -0,0 +1,58 @@
+use std::sync::atomic::AtomicBool;
+
+use crate::engine::{
+    directions::squares::*,
+    evaluation::{params::DEFAULT_PARAMS, pawn_table::PawnTable},
+    game_history::GameHistory,
+    position::Position,
+    search_algorithms::{
+        context::SearchContext, get_best_move, limits::SearchLimits, mated_in,
+        node::get_static_value, quiescence::quiescence, INFINITY,
+    },
+    transposition_table::TranspositionTable,
+};
+
+fn quiescence_value(fen: &str) -> i32 {
+    let mut position = Position::from_fen(fen).unwrap();
+    let table = TranspositionTable::new(1);
+    let stop = AtomicBool::new(false);
+    let mut context = SearchContext::new(&table, &stop, Vec::new());
+    quiescence(&mut position, -INFINITY, INFINITY, 0, &mut context)
+}
+
+#[test]
+fn test_quiescence_resolves_captures() {
+    // Statically white is a queen down, but the pawn takes it.
+    let value = quiescence_value("4k3/8/8/3q4/4P3/8/8/4K3 w - - 0 1");
+    assert!(value > 0, "{value}");
+    // The defended pawn is not worth the queen, white stands pat.
+    let fen = "4k3/8/2p5/3p4/8/8/3Q4/4K3 w - - 0 1";
+    let value = quiescence_value(fen);
+    assert_eq!(
+        value,
+        get_static_value(
+            &Position::from_fen(fen).unwrap(),
+            &DEFAULT_PARAMS,
+            &mut PawnTable::default(),
+        )
+    );
+}
+
+#[test]
+fn test_quiescence_in_check_does_not_stand_pat() {
+    // White is up a queen but mated on the back rank.
+    let value = quiescence_value("6k1/Q7/8/8/8/8/5PPP/3r2K1 w - - 0 1");
+    assert_eq!(value, mated_in(0));
+}
+
+#[test]
+fn test_search_does_not_take_a_defended_pawn_at_the_horizon() {
+    let position = Position::from_fen("4k3/8/2p5/3p4/8/8/3Q4/4K3 w - - 0 1").unwrap();
+    let best_move = get_best_move(
+        &GameHistory::new(position),
+        &SearchLimits::depth(1),
+        &mut TranspositionTable::new(1),
+    )
+    .unwrap();
+    assert_ne!((best_move.get_from(), best_move.get_to()), (D2, D5));
+}
@@ -0,0 +1,48 @@
+use std::fmt;
+
+use crate::engine::search_algorithms::{is_mate_value, MATE_VALUE};
+
+/// A search value for the side to move as shown to users.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Score {
+    Centipawns(i32),
+    /// Mate in this many moves, negative when the side to move gets mated.
+    Mate(i32),
+}
+
+impl Score {
+    pub fn from_value(value: i32) -> Score {
+        if !is_mate_value(value) {
+            Score::Centipawns(value)
+        } else if value > 0 {
+            Score::Mate((MATE_VALUE - value + 1) / 2)
+        } else {
+            Score::Mate(-(MATE_VALUE + value) / 2)
+        }
+    }
+
+    /// The score as in the UCI `info` command, e.g. `cp 35` or `mate -2`.
+    pub fn to_uci(&self) -> String {
+        match self {
+            Score::Centipawns(centipawns) => format!("cp {}", centipawns),
+            Score::Mate(moves) => format!("mate {}", moves),
+        }
+    }
+}
+
+/// Pawns with two decimals like `+0.35`, or mates like `#3` and `#-2`.
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Score::Centipawns(centipawns) => {
+                let sign = if *centipawns < 0 { '-' } else { '+' };
+                let centipawns = centipawns.unsigned_abs();
+                write!(f, "{}{}.{:02}", sign, centipawns / 100, centipawns % 100)
+            }
+            Score::Mate(moves) => write!(f, "#{}", moves),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
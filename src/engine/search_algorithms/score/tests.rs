@@ -0,0 +1,26 @@
+use crate::engine::search_algorithms::{mated_in, score::Score, MATE_VALUE};
+
+#[test]
+fn test_mate_values() {
+    // Mating on the first ply is mate in one, being mated on the second ply mated in one.
+    assert_eq!(Score::from_value(-mated_in(1)), Score::Mate(1));
+    assert_eq!(Score::from_value(mated_in(2)), Score::Mate(-1));
+    assert_eq!(Score::from_value(-mated_in(9)), Score::Mate(5));
+    assert_eq!(Score::from_value(mated_in(10)), Score::Mate(-5));
+    assert_eq!(
+        Score::from_value(MATE_VALUE - 1000),
+        Score::Centipawns(30000)
+    );
+}
+
+#[test]
+fn test_display() {
+    assert_eq!(Score::Centipawns(35).to_string(), "+0.35");
+    assert_eq!(Score::Centipawns(-5).to_string(), "-0.05");
+    assert_eq!(Score::Centipawns(-1250).to_string(), "-12.50");
+    assert_eq!(Score::Centipawns(0).to_string(), "+0.00");
+    assert_eq!(Score::Mate(3).to_string(), "#3");
+    assert_eq!(Score::Mate(-2).to_string(), "#-2");
+    assert_eq!(Score::Mate(3).to_uci(), "mate 3");
+    assert_eq!(Score::Centipawns(-40).to_uci(), "cp -40");
+}
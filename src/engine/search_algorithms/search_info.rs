@@ -0,0 +1,40 @@
+use std::time::Duration;
+
+use crate::engine::{chess_moves::ChessMove, search_algorithms::score::Score};
+
+/// What the search reports after every completed iteration. The value is from the point
+/// of view of the side to move.
+#[derive(Clone, Debug)]
+pub struct SearchInfo {
+    pub depth: u8,
+    /// The deepest ply reached so far, quiescence included.
+    pub seldepth: u8,
+    pub value: i32,
+    pub pv: Vec<ChessMove>,
+    pub nodes: u64,
+    pub elapsed: Duration,
+    /// Transposition table usage in permille.
+    pub hash_fill: u32,
+    /// Share of the main thread's evaluations that found their pawn structure in the
+    /// pawn table, in permille.
+    pub pawn_hits: u32,
+}
+
+impl SearchInfo {
+    pub fn best_move(&self) -> Option<ChessMove> {
+        self.pv.first().copied()
+    }
+
+    pub fn score(&self) -> Score {
+        Score::from_value(self.value)
+    }
+
+    pub fn get_nodes_per_second(&self) -> u64 {
+        let seconds = self.elapsed.as_secs_f64();
+        if seconds > 0.0 {
+            (self.nodes as f64 / seconds) as u64
+        } else {
+            0
+        }
+    }
+}
@@ -1,9 +1,23 @@
+#[cfg(test)]
+use std::{
+    sync::atomic::AtomicBool,
+    time::{Duration, Instant},
+};
+
 #[cfg(test)]
 use crate::engine::{
     directions::squares::*,
+    game_history::GameHistory,
     piece::*,
     position::{print::Print, Position},
-    search_algorithms::get_best_move,
+    search_algorithms::{
+        config::SearchConfig,
+        get_best_move,
+        iterative_deepening::iterative_deepening,
+        limits::{Clock, SearchLimits},
+        score::Score,
+    },
+    transposition_table::TranspositionTable,
 };
 
 #[test]
@@ -14,10 +28,14 @@ fn test_get_best_move() {
         .put_piece(Piece::WhitePawn, B6)
         .put_piece(Piece::BlackKnight, A7);
     position.print_board();
-    if let Some(best_move) = get_best_move(position) {
-        best_move.position.print_board();
-        assert!(best_move
-            .position
+    if let Some(best_move) = get_best_move(
+        &GameHistory::new(position),
+        &SearchLimits::depth(4),
+        &mut TranspositionTable::new(1),
+    ) {
+        position.apply(best_move).print_board();
+        assert!(position
+            .apply(best_move)
             .is_occupied_by_piece(A7, Piece::WhitePawn));
     }
 }
@@ -31,9 +49,280 @@ fn test_get_best_move2() {
         .put_piece(Piece::WhiteKnight, A6)
         .toggle_player();
     position.print_board();
-    if let Some(best_move) = get_best_move(position) {
-        assert!(best_move
-            .position
+    if let Some(best_move) = get_best_move(
+        &GameHistory::new(position),
+        &SearchLimits::depth(4),
+        &mut TranspositionTable::new(1),
+    ) {
+        assert!(position
+            .apply(best_move)
             .is_occupied_by_piece(A6, Piece::BlackPawn));
     }
 }
+
+#[test]
+fn test_iterative_deepening_reports_every_depth() {
+    let position = Position::new_starting_position();
+    let stop = AtomicBool::new(false);
+    let mut depths: Vec<u8> = Vec::new();
+    let last_iteration = iterative_deepening(
+        &GameHistory::new(position),
+        &SearchLimits::depth(3),
+        &stop,
+        &mut TranspositionTable::new(1),
+        |iteration| depths.push(iteration.depth),
+    )
+    .unwrap();
+    assert_eq!(depths, vec![1, 2, 3]);
+    assert_eq!(last_iteration.depth, 3);
+    // Quiescence searches beyond the nominal depth.
+    assert!(last_iteration.seldepth >= 3);
+    assert_eq!(last_iteration.pv.len(), 3);
+    assert_eq!(last_iteration.best_move(), Some(last_iteration.pv[0]));
+}
+
+#[test]
+fn test_iterative_deepening_finds_mate() {
+    let position = Position::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap();
+    let stop = AtomicBool::new(false);
+    let best_move = iterative_deepening(
+        &GameHistory::new(position),
+        &SearchLimits::depth(3),
+        &stop,
+        &mut TranspositionTable::new(1),
+        |_| {},
+    )
+    .and_then(|iteration| iteration.best_move())
+    .unwrap();
+    assert_eq!((best_move.get_from(), best_move.get_to()), (A1, A8));
+}
+
+#[test]
+fn test_iterative_deepening_node_limit() {
+    let position = Position::new_starting_position();
+    let stop = AtomicBool::new(false);
+    let limits = SearchLimits {
+        max_nodes: Some(2000),
+        ..Default::default()
+    };
+    let last_iteration = iterative_deepening(
+        &GameHistory::new(position),
+        &limits,
+        &stop,
+        &mut TranspositionTable::new(1),
+        |_| {},
+    )
+    .unwrap();
+    assert!(last_iteration.nodes <= 2000);
+    assert!(last_iteration.best_move().is_some());
+}
+
+#[test]
+fn test_iterative_deepening_time_limit() {
+    let position = Position::new_starting_position();
+    let stop = AtomicBool::new(false);
+    let start = Instant::now();
+    let last_iteration = iterative_deepening(
+        &GameHistory::new(position),
+        &SearchLimits::move_time(Duration::from_millis(200)),
+        &stop,
+        &mut TranspositionTable::new(1),
+        |_| {},
+    );
+    assert!(last_iteration.unwrap().best_move().is_some());
+    assert!(start.elapsed() < Duration::from_secs(2));
+}
+
+#[test]
+fn test_iterative_deepening_stop_completes_first_iteration() {
+    let position = Position::new_starting_position();
+    let stop = AtomicBool::new(true);
+    let last_iteration = iterative_deepening(
+        &GameHistory::new(position),
+        &SearchLimits::default(),
+        &stop,
+        &mut TranspositionTable::new(1),
+        |_| {},
+    )
+    .unwrap();
+    assert_eq!(last_iteration.depth, 1);
+}
+
+#[test]
+fn test_clock_time_budget() {
+    let limits = SearchLimits {
+        clock: Some(Clock {
+            remaining: Duration::from_millis(60000),
+            increment: Duration::from_millis(1000),
+            moves_to_go: Some(20),
+        }),
+        ..Default::default()
+    };
+    assert_eq!(limits.time_budget(), Some(Duration::from_millis(3500)));
+    let limits = SearchLimits {
+        clock: Some(Clock {
+            remaining: Duration::from_millis(100),
+            increment: Duration::from_millis(1000),
+            moves_to_go: None,
+        }),
+        ..Default::default()
+    };
+    assert_eq!(limits.time_budget(), Some(Duration::from_millis(100)));
+    assert_eq!(SearchLimits::depth(3).time_budget(), None);
+}
+
+#[test]
+fn test_repetition_is_scored_as_draw() {
+    // Black is down the exchange, going back with the knight repeats the first position.
+    let mut history =
+        GameHistory::new(Position::from_fen("1n2k3/8/8/8/8/8/8/R3K3 w - - 0 1").unwrap());
+    for fen in [
+        "1n2k3/8/8/8/8/8/8/R2K4 b - - 1 1",
+        "4k3/8/2n5/8/8/8/8/R2K4 w - - 2 2",
+        "4k3/8/2n5/8/8/8/8/R3K3 b - - 3 2",
+    ] {
+        history.push(Position::from_fen(fen).unwrap());
+    }
+    let stop = AtomicBool::new(false);
+    let last_iteration = iterative_deepening(
+        &history,
+        &SearchLimits::depth(2),
+        &stop,
+        &mut TranspositionTable::new(1),
+        |_| {},
+    )
+    .unwrap();
+    let best_move = last_iteration.best_move().unwrap();
+    assert_eq!((best_move.get_from(), best_move.get_to()), (C6, B8));
+    assert_eq!(last_iteration.value, 0);
+}
+
+#[test]
+fn test_mate_distance() {
+    // Qb8 mates at once, other queen moves only mate later.
+    let mated_in_one = |fen: &str| {
+        let stop = AtomicBool::new(false);
+        iterative_deepening(
+            &GameHistory::new(Position::from_fen(fen).unwrap()),
+            &SearchLimits::depth(4),
+            &stop,
+            &mut TranspositionTable::new(1),
+            |_| {},
+        )
+        .unwrap()
+    };
+    let iteration = mated_in_one("7k/8/6K1/8/8/8/8/1Q6 w - - 0 1");
+    assert_eq!(iteration.score(), Score::Mate(1));
+    let best_move = iteration.best_move().unwrap();
+    assert_eq!((best_move.get_from(), best_move.get_to()), (B1, B8));
+    // Black's only move walks into the same mate.
+    let iteration = mated_in_one("7k/8/6K1/8/8/8/8/1Q6 b - - 0 1");
+    assert_eq!(iteration.score(), Score::Mate(-1));
+}
+
+#[test]
+fn test_every_config_finds_the_mate() {
+    // Rd8 mates on the back rank, none of the techniques may lose that.
+    let position = Position::from_fen("6k1/5ppp/8/8/8/8/5PPP/3R2K1 w - - 0 1").unwrap();
+    let single = |switch: fn(&mut SearchConfig) -> &mut bool| {
+        let mut config = SearchConfig::plain();
+        *switch(&mut config) = true;
+        config
+    };
+    let configs = [
+        SearchConfig::plain(),
+        SearchConfig::default(),
+        single(|config| &mut config.null_move_pruning),
+        single(|config| &mut config.late_move_reductions),
+        single(|config| &mut config.principal_variation_search),
+        single(|config| &mut config.check_extensions),
+    ];
+    for config in configs {
+        let stop = AtomicBool::new(false);
+        let limits = SearchLimits {
+            max_depth: Some(4),
+            config,
+            ..Default::default()
+        };
+        let info = iterative_deepening(
+            &GameHistory::new(position),
+            &limits,
+            &stop,
+            &mut TranspositionTable::new(1),
+            |_| {},
+        )
+        .unwrap();
+        assert_eq!(info.score(), Score::Mate(1), "{config:?}");
+    }
+}
+
+#[test]
+fn test_selective_search_visits_fewer_nodes() {
+    let nodes = |config| {
+        let stop = AtomicBool::new(false);
+        let limits = SearchLimits {
+            max_depth: Some(4),
+            config,
+            ..Default::default()
+        };
+        iterative_deepening(
+            &GameHistory::new(
+                Position::from_fen(
+                    "r1bqk2r/pppp1ppp/2n2n2/2b1p3/2B1P3/3P1N2/PPP2PPP/RNBQK2R w KQkq - 0 1",
+                )
+                .unwrap(),
+            ),
+            &limits,
+            &stop,
+            &mut TranspositionTable::new(1),
+            |_| {},
+        )
+        .unwrap()
+        .nodes
+    };
+    assert!(nodes(SearchConfig::default()) < nodes(SearchConfig::plain()));
+}
+
+#[test]
+fn test_single_thread_is_deterministic() {
+    let search = || {
+        let stop = AtomicBool::new(false);
+        let info = iterative_deepening(
+            &GameHistory::new(Position::new_starting_position()),
+            &SearchLimits::depth(5),
+            &stop,
+            &mut TranspositionTable::new(1),
+            |_| {},
+        )
+        .unwrap();
+        (info.pv, info.nodes, info.value)
+    };
+    assert_eq!(search(), search());
+}
+
+#[test]
+fn test_helper_threads_share_the_search() {
+    let position = Position::from_fen("6k1/5ppp/8/8/8/8/5PPP/3R2K1 w - - 0 1").unwrap();
+    let limits = SearchLimits {
+        max_depth: Some(5),
+        config: SearchConfig {
+            threads: 4,
+            ..SearchConfig::default()
+        },
+        ..Default::default()
+    };
+    let stop = AtomicBool::new(false);
+    let mut table = TranspositionTable::new(1);
+    let info = iterative_deepening(
+        &GameHistory::new(position),
+        &limits,
+        &stop,
+        &mut table,
+        |_| {},
+    )
+    .unwrap();
+    assert_eq!(info.score(), Score::Mate(1));
+    let best_move = info.best_move().unwrap();
+    assert_eq!((best_move.get_from(), best_move.get_to()), (D1, D8));
+    assert!(table.get_stats().stores > 0);
+}
@@ -1,3 +1,5 @@
+use std::sync::atomic::AtomicBool;
+
 #[cfg(test)]
 use crate::engine::chess_moves::configurations::MovesFn;
 use crate::engine::{
@@ -8,12 +10,13 @@ use crate::engine::{
         ChessMove,
     },
     get_possible_moves, piece,
-    position::{self, print::Print},
 };
 use crate::engine::{
     directions::squares::*,
+    game_history::GameHistory,
     piece::Piece::{self},
     position::Position,
+    transposition_table::TranspositionTable,
 };
 #[test]
 fn test_preemtive_game_end() {
@@ -25,13 +28,18 @@ fn test_preemtive_game_end() {
         .put_piece(Piece::WhitePawn, H2)
         .put_piece(Piece::WhiteBishop, A3)
         .put_piece(Piece::BlackKing, B8);
-    match engine::get_next_move(&position) {
-        engine::MoveOrEnd::Move(chess_move) => println!("Move"),
-        engine::MoveOrEnd::GameEnd(game_end) => println!("GameEnd"),
+    match engine::get_next_move(
+        &GameHistory::new(position),
+        &mut TranspositionTable::new(1),
+        1,
+        &AtomicBool::new(false),
+    ) {
+        engine::MoveOrEnd::Move(_, _, _) => println!("Move"),
+        engine::MoveOrEnd::GameEnd(_) => println!("GameEnd"),
     }
-    match get_possible_moves(&position) {
-        Ok(chess_moves) => println!("moves found"),
-        Err(game_end) => println!("game end"),
+    match get_possible_moves(&GameHistory::new(position)) {
+        Ok(_) => println!("moves found"),
+        Err(_) => println!("game end"),
     }
 }
 
@@ -43,7 +51,7 @@ fn get_valid_drop_positions(position: &Position, from: Square) -> Vec<ChessMove>
         if let Some(castling_moves_fn) = get_castling_moves_fn(from, piece) {
             positions.extend(castling_moves_fn(position));
         }
-        positions = filter_checks(positions, position.get_player());
+        positions = filter_checks(position, positions);
     }
     positions
 }
@@ -80,15 +88,15 @@ fn get_moves_fn(piece: Piece) -> MovesFn {
 fn test_valid_drop_targets_pawn() {
     let position = Position::new_starting_position();
     let targets = get_valid_drop_positions(&position, D2);
-    assert!(targets.iter().any(|c| c.position.is_occupied(D3)));
-    assert!(targets.iter().any(|c| c.position.is_occupied(D4)));
+    assert!(targets.iter().any(|c| position.apply(*c).is_occupied(D3)));
+    assert!(targets.iter().any(|c| position.apply(*c).is_occupied(D4)));
 }
 #[test]
 fn test_valid_drop_targets_knight() {
     let position = Position::new_starting_position();
     let targets = get_valid_drop_positions(&position, B8);
-    assert!(targets.iter().any(|c| c.position.is_occupied(A6)));
-    assert!(targets.iter().any(|c| c.position.is_occupied(C6)));
+    assert!(targets.iter().any(|c| position.apply(*c).is_occupied(A6)));
+    assert!(targets.iter().any(|c| position.apply(*c).is_occupied(C6)));
 }
 
 #[test]
@@ -97,7 +105,7 @@ fn test_valid_drop_targets_castling() {
         .put_piece(Piece::WhiteRook, H1)
         .put_piece(Piece::WhiteKing, E1);
     let targets = get_valid_drop_positions(&position, E1);
-    assert!(targets.iter().any(|c| c.position.is_occupied(G1)));
+    assert!(targets.iter().any(|c| position.apply(*c).is_occupied(G1)));
 }
 
 #[test]
@@ -108,5 +116,5 @@ fn test_valid_drop_targets_en_passant() {
         .put_piece(Piece::BlackPawn, D4)
         .set_en_passant(E4);
     let targets = get_valid_drop_positions(&position, D4);
-    assert!(targets.iter().any(|c| c.position.is_occupied(E3)));
+    assert!(targets.iter().any(|c| position.apply(*c).is_occupied(E3)));
 }
@@ -0,0 +1,218 @@
+use std::{
+    mem,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use crate::engine::chess_moves::ChessMove;
+
+pub const DEFAULT_SIZE_MB: usize = 16;
+const BYTES_PER_MB: usize = 1024 * 1024;
+// Sample size for the fill rate, UCI reports it in permille.
+const FILL_SAMPLE: usize = 1000;
+
+// Layout of an entry packed into 64 bits. A bound code of zero marks an empty slot.
+const VALUE_SHIFT: u32 = 32;
+const DEPTH_SHIFT: u32 = 48;
+const BOUND_SHIFT: u32 = 56;
+const GENERATION_SHIFT: u32 = 58;
+const GENERATION_BITS: u8 = 0x3f;
+
+/// How the stored value relates to the real value of the position.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Bound {
+    Exact,
+    /// The real value is at least the stored one.
+    Lower,
+    /// The real value is at most the stored one.
+    Upper,
+}
+
+impl Bound {
+    fn as_code(self) -> u64 {
+        match self {
+            Bound::Exact => 1,
+            Bound::Lower => 2,
+            Bound::Upper => 3,
+        }
+    }
+
+    fn from_code(code: u64) -> Option<Bound> {
+        match code {
+            1 => Some(Bound::Exact),
+            2 => Some(Bound::Lower),
+            3 => Some(Bound::Upper),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Entry {
+    pub key: u64,
+    pub depth: u8,
+    pub bound: Bound,
+    /// From the point of view of the side to move, mate values count the plies from
+    /// this position.
+    pub value: i32,
+    pub best_move: Option<ChessMove>,
+    generation: u8,
+}
+
+impl Entry {
+    fn pack(&self) -> u64 {
+        self.best_move.map_or(0, ChessMove::to_bits) as u64
+            | (self.value as i16 as u16 as u64) << VALUE_SHIFT
+            | (self.depth as u64) << DEPTH_SHIFT
+            | self.bound.as_code() << BOUND_SHIFT
+            | ((self.generation & GENERATION_BITS) as u64) << GENERATION_SHIFT
+    }
+
+    fn unpack(key: u64, data: u64) -> Option<Entry> {
+        let best_move = match data as u32 {
+            0 => None,
+            bits => Some(ChessMove::from_bits(bits)),
+        };
+        Some(Entry {
+            key,
+            depth: (data >> DEPTH_SHIFT) as u8,
+            bound: Bound::from_code(data >> BOUND_SHIFT & 0x3)?,
+            value: (data >> VALUE_SHIFT) as u16 as i16 as i32,
+            best_move,
+            generation: (data >> GENERATION_SHIFT) as u8,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TableStats {
+    pub probes: u64,
+    pub hits: u64,
+    pub stores: u64,
+    /// Stores that replaced an entry of a different position.
+    pub overwrites: u64,
+}
+
+/// One entry. The key is stored xor-ed with the data, so an entry that another thread
+/// wrote halfway through does not match its key and is ignored.
+#[derive(Default)]
+struct Slot {
+    checked_key: AtomicU64,
+    data: AtomicU64,
+}
+
+#[derive(Default)]
+struct AtomicStats {
+    probes: AtomicU64,
+    hits: AtomicU64,
+    stores: AtomicU64,
+    overwrites: AtomicU64,
+}
+
+/// A fixed-size table of search results indexed by Zobrist key. It is meant to live
+/// as long as a game, so later searches profit from earlier ones. Probing and storing
+/// need no lock, so the threads of one search share the table.
+pub struct TranspositionTable {
+    slots: Vec<Slot>,
+    generation: u8,
+    stats: AtomicStats,
+}
+
+impl TranspositionTable {
+    pub fn new(size_mb: usize) -> Self {
+        let slot_count = (size_mb * BYTES_PER_MB / mem::size_of::<Slot>()).max(1);
+        TranspositionTable {
+            slots: (0..slot_count).map(|_| Slot::default()).collect(),
+            generation: 0,
+            stats: AtomicStats::default(),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        for slot in &mut self.slots {
+            *slot = Slot::default();
+        }
+        self.generation = 0;
+        self.stats = AtomicStats::default();
+    }
+
+    /// Marks the entries stored so far as old, so they are replaced first.
+    pub fn new_search(&mut self) {
+        self.generation = self.generation.wrapping_add(1) & GENERATION_BITS;
+    }
+
+    pub fn probe(&self, key: u64) -> Option<Entry> {
+        self.stats.probes.fetch_add(1, Ordering::Relaxed);
+        let entry = self.load(key)?;
+        self.stats.hits.fetch_add(1, Ordering::Relaxed);
+        Some(entry)
+    }
+
+    /// Stores a result unless the slot holds a deeper result of the current search.
+    pub fn store(
+        &self,
+        key: u64,
+        depth: u8,
+        bound: Bound,
+        value: i32,
+        best_move: Option<ChessMove>,
+    ) {
+        let slot = &self.slots[self.index(key)];
+        let data = slot.data.load(Ordering::Relaxed);
+        let old_key = slot.checked_key.load(Ordering::Relaxed) ^ data;
+        if let Some(entry) = Entry::unpack(old_key, data) {
+            if entry.key != key && entry.generation == self.generation && entry.depth > depth {
+                return;
+            }
+            if entry.key != key {
+                self.stats.overwrites.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        self.stats.stores.fetch_add(1, Ordering::Relaxed);
+        let data = Entry {
+            key,
+            depth,
+            bound,
+            value,
+            best_move,
+            generation: self.generation,
+        }
+        .pack();
+        slot.checked_key.store(key ^ data, Ordering::Relaxed);
+        slot.data.store(data, Ordering::Relaxed);
+    }
+
+    pub fn get_stats(&self) -> TableStats {
+        TableStats {
+            probes: self.stats.probes.load(Ordering::Relaxed),
+            hits: self.stats.hits.load(Ordering::Relaxed),
+            stores: self.stats.stores.load(Ordering::Relaxed),
+            overwrites: self.stats.overwrites.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Share of used entries in permille.
+    pub fn get_fill_permille(&self) -> u32 {
+        let sample = FILL_SAMPLE.min(self.slots.len());
+        let used = self.slots[..sample]
+            .iter()
+            .filter(|slot| slot.data.load(Ordering::Relaxed) != 0)
+            .count();
+        (used * 1000 / sample) as u32
+    }
+
+    fn load(&self, key: u64) -> Option<Entry> {
+        let slot = &self.slots[self.index(key)];
+        let data = slot.data.load(Ordering::Relaxed);
+        if slot.checked_key.load(Ordering::Relaxed) ^ data != key {
+            return None;
+        }
+        Entry::unpack(key, data)
+    }
+
+    fn index(&self, key: u64) -> usize {
+        (key % self.slots.len() as u64) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests;
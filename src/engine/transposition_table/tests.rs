@@ -0,0 +1,118 @@
+use crate::engine::{
+    chess_moves::{ChessMove, MoveType},
+    directions::squares::*,
+    piece::Piece,
+    transposition_table::{Bound, TableStats, TranspositionTable},
+};
+
+fn e2e4() -> ChessMove {
+    ChessMove::new(MoveType::Quiet, Piece::WhitePawn, E2, E4, None, None)
+}
+
+#[test]
+fn test_store_and_probe() {
+    let table = TranspositionTable::new(1);
+    assert_eq!(table.probe(42), None);
+    table.store(42, 3, Bound::Exact, 15, Some(e2e4()));
+    let entry = table.probe(42).unwrap();
+    assert_eq!(entry.depth, 3);
+    assert_eq!(entry.bound, Bound::Exact);
+    assert_eq!(entry.value, 15);
+    assert_eq!(entry.best_move, Some(e2e4()));
+    assert_eq!(
+        table.get_stats(),
+        TableStats {
+            probes: 2,
+            hits: 1,
+            stores: 1,
+            overwrites: 0,
+        }
+    );
+}
+
+#[test]
+fn test_deeper_entries_of_the_current_search_are_kept() {
+    let mut table = TranspositionTable::new(1);
+    let size = 1024 * 1024 / std::mem::size_of::<super::Slot>() as u64;
+    // Both keys share a slot.
+    table.store(7, 5, Bound::Lower, 10, None);
+    table.store(7 + size, 2, Bound::Upper, 20, None);
+    assert!(table.probe(7).is_some());
+    assert_eq!(table.probe(7 + size), None);
+
+    table.new_search();
+    table.store(7 + size, 2, Bound::Upper, 20, None);
+    assert_eq!(table.probe(7), None);
+    assert!(table.probe(7 + size).is_some());
+    assert_eq!(table.get_stats().overwrites, 1);
+}
+
+#[test]
+fn test_same_position_is_always_replaced() {
+    let table = TranspositionTable::new(1);
+    table.store(7, 5, Bound::Lower, 10, None);
+    table.store(7, 2, Bound::Exact, 20, Some(e2e4()));
+    assert_eq!(table.probe(7).unwrap().value, 20);
+}
+
+#[test]
+fn test_clear_and_fill() {
+    let mut table = TranspositionTable::new(1);
+    assert_eq!(table.get_fill_permille(), 0);
+    for key in 0..500 {
+        table.store(key, 1, Bound::Exact, 0, None);
+    }
+    assert_eq!(table.get_fill_permille(), 500);
+    table.clear();
+    assert_eq!(table.get_fill_permille(), 0);
+    assert_eq!(table.probe(1), None);
+}
+
+#[test]
+fn test_hash_move_matches_promotion() {
+    let promotion = |piece| {
+        ChessMove::new(
+            MoveType::Promotion,
+            Piece::WhitePawn,
+            E7,
+            E8,
+            None,
+            Some(piece),
+        )
+    };
+    let table = TranspositionTable::new(1);
+    table.store(7, 1, Bound::Exact, 90, Some(promotion(Piece::WhiteQueen)));
+    let best_move = table.probe(7).unwrap().best_move;
+    assert_eq!(best_move, Some(promotion(Piece::WhiteQueen)));
+    assert_ne!(best_move, Some(promotion(Piece::WhiteKnight)));
+}
+
+#[test]
+fn test_threads_share_the_table() {
+    let table = TranspositionTable::new(1);
+    std::thread::scope(|scope| {
+        for thread in 0..4u64 {
+            let table = &table;
+            scope.spawn(move || {
+                for key in (thread * 1000)..(thread * 1000 + 1000) {
+                    table.store(key, 1, Bound::Lower, key as i32, None);
+                    let entry = table.probe(key).unwrap();
+                    assert_eq!(entry.value, key as i32);
+                }
+            });
+        }
+    });
+    assert_eq!(table.probe(3999).unwrap().value, 3999);
+    assert_eq!(table.get_stats().stores, 4000);
+}
+
+#[test]
+fn test_negative_values_and_depth_survive_packing() {
+    let table = TranspositionTable::new(1);
+    table.store(9, 64, Bound::Upper, -30_950, Some(e2e4()));
+    let entry = table.probe(9).unwrap();
+    assert_eq!(
+        (entry.depth, entry.bound, entry.value, entry.best_move),
+        (64, Bound::Upper, -30_950, Some(e2e4()))
+    );
+}
@@ -1,6 +1,7 @@
 mod configuration;
 pub mod game;
 mod icon;
+mod segment_text;
 mod state_machine;
 mod ui_board;
 mod ui_button;
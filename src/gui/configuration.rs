@@ -9,3 +9,4 @@ pub const SELECTED_FIELD_COLOR: Color = Color::Rgba(1.0, 1.0, 1.0, 5.0);
 pub const CHECK_COLOR: Color = Color::Rgba(1.0, 0.0, 0.0, 5.0);
 pub const FIELD_SIZE: f32 = 100.0;
 pub const MENU_HEIGHT: f32 = 50.0;
+pub const TEXT_COLOR: Color = Color::Rgba(60.0 / 255.0, 60.0 / 255.0, 60.0 / 255.0, 1.0);
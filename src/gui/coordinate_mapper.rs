@@ -1,7 +1,6 @@
-use crate::{
-    engine::directions::squares::Square,
-    gui::{configuration::FIELD_SIZE, ui_element::CanvasCoordinate},
-};
+use luma::engine::directions::squares::Square;
+
+use crate::gui::{configuration::FIELD_SIZE, ui_element::CanvasCoordinate};
 
 /*
 fn get_field_from_canvas(canvas: f64) -> Option<u32> {
@@ -1,37 +1,94 @@
+use std::{
+    fs, mem,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
 use flo_canvas::{Draw, DrawingTarget};
+use futures::channel::mpsc::UnboundedSender;
+use luma::engine::{
+    self,
+    chess_moves::{ChessMove, MoveType},
+    directions::squares::Square,
+    game_history::GameHistory,
+    pgn::{self, GameRecord, GameResult, RecordedMove},
+    piece::Color,
+    position::Position,
+    transposition_table::{self, TranspositionTable},
+};
 
-use crate::{
-    engine::{
-        self,
-        chess_moves::{ChessMove, MoveType},
-        directions::squares::Square,
-        position::Position,
-    },
-    gui::{
-        state_machine::{
-            self, FromSquareSelectedData, GameState, NoSquareSelectedData, PromoteFunctionData,
-            PromotionSquareSelectedData, SelectFromSquareFunctionData, SelectToSquareFunctionData,
-            SquareSelected, StateFunction,
-        },
-        ui_board::Orientation,
-        ui_element::{CanvasCoordinate, UIElement},
-        ui_game::UIGame,
+use crate::gui::{
+    state_machine::{
+        self, FromSquareSelectedData, GameState, NoSquareSelectedData, PromoteFunctionData,
+        PromotionSquareSelectedData, SelectFromSquareFunctionData, SelectToSquareFunctionData,
+        SquareSelected, StateFunction, ViewingData,
     },
+    ui_board::Orientation,
+    ui_element::{CanvasCoordinate, Navigation, UIElement},
+    ui_game::UIGame,
 };
 
+const PLAYER_NAME: &str = "Player";
+const ENGINE_NAME: &str = "luma";
+
 pub struct Game {
     canvas: DrawingTarget,
     ui: UIGame,
     state: GameState,
     position: Position,
+    history: GameHistory,
+    record: GameRecord,
+    /// Taken back moves, the next one to redo last.
+    undone: Vec<RecordedMove>,
+    player: Color,
+    table: Arc<Mutex<TranspositionTable>>,
+    /// The next search clears the table first. A stopped search may still hold the
+    /// table, so it isn't cleared on the UI thread.
+    clear_table: bool,
+    /// Search threads of the computer player.
+    threads: usize,
+    search_results: UnboundedSender<SearchResult>,
+    /// Identifies the running search, results of other searches are ignored.
+    search_id: u64,
+    search_stop: Arc<AtomicBool>,
+}
+
+/// The outcome of a computer search, sent from the search thread to the event loop.
+#[derive(Debug)]
+pub struct SearchResult {
+    search_id: u64,
+    move_or_end: engine::MoveOrEnd,
 }
+
 impl Game {
-    pub fn new(canvas: DrawingTarget) -> Self {
+    pub fn new(
+        canvas: DrawingTarget,
+        search_results: UnboundedSender<SearchResult>,
+        threads: usize,
+    ) -> Self {
+        let mut ui = UIGame::new();
+        ui.set_threads(threads);
         Game {
             canvas,
-            ui: UIGame::new(),
+            ui,
             state: GameState::NoGame,
             position: Position::default(),
+            history: GameHistory::new(Position::default()),
+            record: GameRecord::new(Position::default(), "?", "?", "????.??.??"),
+            undone: Vec::new(),
+            player: Color::White,
+            table: Arc::new(Mutex::new(TranspositionTable::new(
+                transposition_table::DEFAULT_SIZE_MB,
+            ))),
+            clear_table: false,
+            threads,
+            search_results,
+            search_id: 0,
+            search_stop: Arc::new(AtomicBool::new(false)),
         }
     }
     pub fn draw(&mut self) {
@@ -55,6 +112,10 @@ impl Game {
                     StateFunction::SelectToSquare(data) => self.select_to_square(data),
                     StateFunction::Promote(data) => self.promote(data),
                     StateFunction::TurnBoard => self.turn_board(),
+                    StateFunction::ChangeThreads => self.change_threads(),
+                    StateFunction::Undo => self.undo(),
+                    StateFunction::Redo => self.redo(),
+                    StateFunction::Navigate(navigation) => self.navigate(navigation),
                 }
             }
         }
@@ -62,37 +123,55 @@ impl Game {
 
     fn new_game_as(&mut self, color: engine::piece::Color) {
         self.position = Position::new_starting_position();
+        self.history = GameHistory::new(self.position);
+        let (white, black) = match color {
+            engine::piece::Color::Black => (ENGINE_NAME, PLAYER_NAME),
+            engine::piece::Color::White => (PLAYER_NAME, ENGINE_NAME),
+        };
+        self.record = GameRecord::new(self.position, white, black, &pgn::today());
+        self.undone.clear();
+        self.player = color;
+        self.stop_search();
+        self.clear_table = true;
 
         let orientation = match color {
             engine::piece::Color::Black => Orientation::WhiteDown,
             engine::piece::Color::White => Orientation::WhiteUp,
         };
         self.ui.set_orientation(orientation);
+        self.ui.set_score(None);
         match color {
             engine::piece::Color::Black => {
-                self.state = GameState::Computer;
-                self.position = Position::new_starting_position();
                 self.execute_computer_move();
             }
-            engine::piece::Color::White => {
-                self.position = Position::new_starting_position();
-                match engine::get_possible_moves(&self.position) {
-                    Ok(possible_moves) => {
-                        self.state = GameState::Player(SquareSelected::No(NoSquareSelectedData {
-                            possible_moves,
-                            last_move: None,
-                        }));
-                        self.update_ui();
-                    }
-                    Err(_) => self.state = GameState::NoGame,
+            engine::piece::Color::White => match engine::get_possible_moves(&self.history) {
+                Ok(possible_moves) => {
+                    self.state = GameState::Player(SquareSelected::No(NoSquareSelectedData {
+                        possible_moves,
+                        last_move: None,
+                    }));
+                    self.update_ui();
                 }
-            }
+                Err(_) => self.state = GameState::NoGame,
+            },
         }
     }
     fn turn_board(&mut self) {
         self.ui.turn_board();
         self.update_ui();
     }
+    /// Doubles the search threads up to the available cores, then starts over at one.
+    /// A running search keeps its threads, the next one uses the new number.
+    fn change_threads(&mut self) {
+        let cores = thread::available_parallelism().map_or(1, |cores| cores.get());
+        self.threads = if self.threads >= cores {
+            1
+        } else {
+            (self.threads * 2).min(cores)
+        };
+        self.ui.set_threads(self.threads);
+        self.update_ui();
+    }
     fn select_from_square(&mut self, data: SelectFromSquareFunctionData) {
         let possible_moves_from = get_selected_moves_from(data.possible_moves.clone(), data.from);
         if possible_moves_from.is_empty() {
@@ -113,7 +192,7 @@ impl Game {
     fn select_to_square(&mut self, data: SelectToSquareFunctionData) {
         match get_selected_moves_to(data.possible_moves_from, data.to) {
             Some(SimpleMoveOrPromotions::SimpleMove(ui_move)) => {
-                self.execute_player_move(ui_move.position);
+                self.execute_player_move(ui_move);
             }
             Some(SimpleMoveOrPromotions::Promotions(possible_promotion_moves)) => {
                 self.enable_promotion_buttons(possible_promotion_moves);
@@ -131,12 +210,12 @@ impl Game {
     fn promote(&mut self, data: PromoteFunctionData) {
         self.ui.disabled_promotion_buttons();
         if let Some(promotion_move) = data.possible_promotion_moves.iter().find(|promotion_move| {
-            (promotion_move.move_type == MoveType::Promotion
-                || promotion_move.move_type == MoveType::PromotionCapture)
-                && promotion_move.pormotion == Some(data.piece)
+            (promotion_move.get_move_type() == MoveType::Promotion
+                || promotion_move.get_move_type() == MoveType::PromotionCapture)
+                && promotion_move.get_promotion() == Some(data.piece)
         }) {
-            self.position = promotion_move.position;
-            self.state = GameState::Computer;
+            self.undone.clear();
+            self.play(*promotion_move, None);
             self.execute_computer_move();
         }
     }
@@ -148,34 +227,177 @@ impl Game {
         self.update_ui();
     }
 
+    /// Starts the search on a background thread, the result arrives through the event
+    /// loop in `handle_search_result`.
     fn execute_computer_move(&mut self) {
-        match engine::get_next_move(&self.position) {
-            engine::MoveOrEnd::Move(chess_move) => {
-                self.position = chess_move.position;
-                match engine::get_possible_moves(&self.position) {
+        self.state = GameState::Computer;
+        self.update_ui();
+        self.stop_search();
+        let stop = Arc::new(AtomicBool::new(false));
+        self.search_stop = Arc::clone(&stop);
+        let search_id = self.search_id;
+        let history = self.history.clone();
+        let table = Arc::clone(&self.table);
+        let clear_table = mem::take(&mut self.clear_table);
+        let threads = self.threads;
+        let search_results = self.search_results.clone();
+        thread::spawn(move || {
+            let mut table = table.lock().unwrap_or_else(|error| error.into_inner());
+            if clear_table {
+                table.clear();
+            }
+            let move_or_end = engine::get_next_move(&history, &mut table, threads, &stop);
+            drop(table);
+            // The receiver is gone when the window was closed.
+            let _ = search_results.unbounded_send(SearchResult {
+                search_id,
+                move_or_end,
+            });
+        });
+    }
+
+    /// Aborts the running search, its result will be ignored.
+    fn stop_search(&mut self) {
+        self.search_stop.store(true, Ordering::Relaxed);
+        self.search_id += 1;
+    }
+
+    pub fn handle_search_result(&mut self, result: SearchResult) {
+        if result.search_id != self.search_id || !matches!(self.state, GameState::Computer) {
+            return;
+        }
+        match result.move_or_end {
+            engine::MoveOrEnd::Move(chess_move, depth, score) => {
+                self.ui.set_score(Some(score));
+                self.play(chess_move, Some(depth));
+                match engine::get_possible_moves(&self.history) {
                     Ok(possible_moves) => {
                         self.state = GameState::Player(SquareSelected::No(NoSquareSelectedData {
                             possible_moves,
                             last_move: Some(chess_move),
                         }));
                     }
-                    Err(_) => {
-                        self.state = GameState::GameOver;
-                    }
+                    Err(game_end) => self.end_game(game_end),
                 }
             }
-            engine::MoveOrEnd::GameEnd(game_end) => {
-                println!("{:?}", game_end);
+            engine::MoveOrEnd::GameEnd(game_end) => self.end_game(game_end),
+        }
+        self.update_ui();
+    }
+
+    fn play(&mut self, chess_move: ChessMove, depth: Option<u8>) {
+        self.history.push_move(chess_move);
+        self.position = self.history.get_current();
+        self.record.push(chess_move, depth);
+    }
+
+    fn end_game(&mut self, game_end: engine::GameEnd) {
+        println!("{:?}", game_end);
+        self.state = GameState::GameOver;
+        self.record.result = GameResult::from_game_end(game_end, self.position.get_player());
+        self.save_record();
+    }
+
+    /// Writes the game as PGN file into the working directory.
+    fn save_record(&self) {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or(0);
+        let file_name = format!("luma-{}.pgn", seconds);
+        match fs::write(&file_name, self.record.to_pgn()) {
+            Ok(()) => println!("Game saved to {}", file_name),
+            Err(error) => println!("Could not save game to {}: {}", file_name, error),
+        }
+    }
+
+    /// Takes back moves up to the previous position with the player to move, so
+    /// undoing a computer reply takes back the player's move as well.
+    fn undo(&mut self) {
+        let positions = self.record.get_positions();
+        if let Some(ply) = (0..positions.len() - 1)
+            .rev()
+            .find(|ply| positions[*ply].get_player() == self.player)
+        {
+            while self.record.get_moves().len() > ply {
+                if let Some(recorded_move) = self.record.pop() {
+                    self.history.pop();
+                    self.undone.push(recorded_move);
+                }
+            }
+        }
+        self.continue_game();
+    }
+
+    /// Replays taken back moves up to the next position with the player to move.
+    fn redo(&mut self) {
+        while let Some(recorded_move) = self.undone.pop() {
+            self.play(recorded_move.chess_move, recorded_move.depth);
+            if self.position.get_player() == self.player {
+                break;
+            }
+        }
+        self.continue_game();
+    }
+
+    fn navigate(&mut self, navigation: Navigation) {
+        let last = self.record.get_moves().len();
+        let current = match &self.state {
+            GameState::Viewing(data) => data.ply,
+            _ => last,
+        };
+        let ply = match navigation {
+            Navigation::First => 0,
+            Navigation::Previous => current.saturating_sub(1),
+            Navigation::Next => (current + 1).min(last),
+            Navigation::Last => last,
+        };
+        if ply == last {
+            self.continue_game();
+        } else {
+            self.ui.disabled_promotion_buttons();
+            self.position = self.record.get_positions()[ply];
+            self.state = GameState::Viewing(ViewingData {
+                ply,
+                last_move: ply
+                    .checked_sub(1)
+                    .map(|index| self.record.get_moves()[index].chess_move),
+            });
+            self.update_ui();
+        }
+    }
+
+    /// Shows the current position of the game and lets the side to move play.
+    fn continue_game(&mut self) {
+        self.position = self.history.get_current();
+        self.ui.disabled_promotion_buttons();
+        match engine::get_possible_moves(&self.history) {
+            Ok(possible_moves) if self.position.get_player() == self.player => {
+                self.state = GameState::Player(SquareSelected::No(NoSquareSelectedData {
+                    possible_moves,
+                    last_move: self
+                        .record
+                        .get_moves()
+                        .last()
+                        .map(|recorded_move| recorded_move.chess_move),
+                }));
+                self.update_ui();
+            }
+            Ok(_) => {
+                self.execute_computer_move();
+            }
+            Err(game_end) => {
                 self.state = GameState::GameOver;
+                self.record.result =
+                    GameResult::from_game_end(game_end, self.position.get_player());
+                self.update_ui();
             }
         }
-        self.update_ui();
     }
 
-    fn execute_player_move(&mut self, position: Position) {
-        self.position = position;
-        self.state = GameState::Computer;
-        self.update_ui();
+    fn execute_player_move(&mut self, chess_move: ChessMove) {
+        self.undone.clear();
+        self.play(chess_move, None);
         self.execute_computer_move();
     }
 }
@@ -183,7 +405,7 @@ impl Game {
 fn get_selected_moves_from(possible_moves: Vec<ChessMove>, from: Square) -> Vec<ChessMove> {
     possible_moves
         .into_iter()
-        .filter(|ui_move| from == ui_move.from)
+        .filter(|ui_move| from == ui_move.get_from())
         .collect()
 }
 
@@ -193,7 +415,7 @@ fn get_selected_moves_to(
 ) -> Option<SimpleMoveOrPromotions> {
     let mut moves_from_to: Vec<ChessMove> = possible_moves
         .into_iter()
-        .filter(|ui_move| to == ui_move.to)
+        .filter(|ui_move| to == ui_move.get_to())
         .collect();
     if moves_from_to.len() == 4 {
         Some(SimpleMoveOrPromotions::Promotions(moves_from_to))
@@ -66,4 +66,32 @@ impl Icon {
         texture_id: TextureId(14),
         bytes: include_bytes!["icons/turn-board.png"],
     };
+    pub const FIRST: Icon = Icon {
+        texture_id: TextureId(15),
+        bytes: include_bytes!["icons/first.png"],
+    };
+    pub const PREVIOUS: Icon = Icon {
+        texture_id: TextureId(16),
+        bytes: include_bytes!["icons/previous.png"],
+    };
+    pub const NEXT: Icon = Icon {
+        texture_id: TextureId(17),
+        bytes: include_bytes!["icons/next.png"],
+    };
+    pub const LAST: Icon = Icon {
+        texture_id: TextureId(18),
+        bytes: include_bytes!["icons/last.png"],
+    };
+    pub const UNDO: Icon = Icon {
+        texture_id: TextureId(19),
+        bytes: include_bytes!["icons/undo.png"],
+    };
+    pub const REDO: Icon = Icon {
+        texture_id: TextureId(20),
+        bytes: include_bytes!["icons/redo.png"],
+    };
+    pub const THINKING: Icon = Icon {
+        texture_id: TextureId(21),
+        bytes: include_bytes!["icons/thinking.png"],
+    };
 }
@@ -0,0 +1,79 @@
+use flo_canvas::{Draw, GraphicsContext};
+
+use crate::gui::{configuration::TEXT_COLOR, ui_container::Container};
+
+/// A stroke of a glyph from one point to another, in a unit box with y pointing up.
+type Stroke = ((f32, f32), (f32, f32));
+
+const TOP: Stroke = ((0.0, 1.0), (1.0, 1.0));
+const TOP_RIGHT: Stroke = ((1.0, 1.0), (1.0, 0.5));
+const BOTTOM_RIGHT: Stroke = ((1.0, 0.5), (1.0, 0.0));
+const BOTTOM: Stroke = ((0.0, 0.0), (1.0, 0.0));
+const BOTTOM_LEFT: Stroke = ((0.0, 0.0), (0.0, 0.5));
+const TOP_LEFT: Stroke = ((0.0, 0.5), (0.0, 1.0));
+const MIDDLE: Stroke = ((0.0, 0.5), (1.0, 0.5));
+
+/// Glyph height relative to the container height, and glyph width and advance relative
+/// to the glyph height.
+const HEIGHT: f32 = 0.4;
+const WIDTH: f32 = 0.5;
+const ADVANCE: f32 = 0.8;
+
+/// The strokes of the characters of a score like `+1.23` or `#-2`, drawn like a seven
+/// segment display. Other characters are left blank.
+fn get_strokes(character: char) -> &'static [Stroke] {
+    match character {
+        '0' => &[TOP, TOP_RIGHT, BOTTOM_RIGHT, BOTTOM, BOTTOM_LEFT, TOP_LEFT],
+        '1' => &[TOP_RIGHT, BOTTOM_RIGHT],
+        '2' => &[TOP, TOP_RIGHT, MIDDLE, BOTTOM_LEFT, BOTTOM],
+        '3' => &[TOP, TOP_RIGHT, MIDDLE, BOTTOM_RIGHT, BOTTOM],
+        '4' => &[TOP_LEFT, MIDDLE, TOP_RIGHT, BOTTOM_RIGHT],
+        '5' => &[TOP, TOP_LEFT, MIDDLE, BOTTOM_RIGHT, BOTTOM],
+        '6' => &[TOP, TOP_LEFT, MIDDLE, BOTTOM_LEFT, BOTTOM_RIGHT, BOTTOM],
+        '7' => &[TOP, TOP_RIGHT, BOTTOM_RIGHT],
+        '8' => &[
+            TOP,
+            TOP_RIGHT,
+            BOTTOM_RIGHT,
+            BOTTOM,
+            BOTTOM_LEFT,
+            TOP_LEFT,
+            MIDDLE,
+        ],
+        '9' => &[TOP, TOP_RIGHT, BOTTOM_RIGHT, BOTTOM, TOP_LEFT, MIDDLE],
+        '-' => &[MIDDLE],
+        '+' => &[MIDDLE, ((0.5, 0.25), (0.5, 0.75))],
+        '.' => &[((0.4, 0.0), (0.6, 0.0))],
+        '#' => &[
+            ((0.3, 0.1), (0.3, 0.9)),
+            ((0.7, 0.1), (0.7, 0.9)),
+            ((0.0, 0.35), (1.0, 0.35)),
+            ((0.0, 0.65), (1.0, 0.65)),
+        ],
+        _ => &[],
+    }
+}
+
+/// Draws the text centered in the container. The menu has no font, so the characters
+/// are stroked as lines.
+pub fn draw_text(gc: &mut Vec<Draw>, container: Container, text: &str) {
+    let height = container.get_height() * HEIGHT;
+    let width = height * WIDTH;
+    let advance = height * ADVANCE;
+    let characters = text.chars().count() as f32;
+    let text_width = (characters * advance - (advance - width)).max(0.0);
+    let x_min = container.get_center_x() - text_width / 2.0;
+    let y_min = container.get_center_y() - height / 2.0;
+
+    gc.new_path();
+    for (index, character) in text.chars().enumerate() {
+        let x = x_min + index as f32 * advance;
+        for ((x_from, y_from), (x_to, y_to)) in get_strokes(character) {
+            gc.move_to(x + x_from * width, y_min + y_from * height);
+            gc.line_to(x + x_to * width, y_min + y_to * height);
+        }
+    }
+    gc.line_width(2.0);
+    gc.stroke_color(TEXT_COLOR);
+    gc.stroke();
+}
@@ -1,11 +1,10 @@
-use crate::{
-    engine::{
-        chess_moves::ChessMove,
-        directions::squares::Square,
-        piece::{Color, Piece},
-    },
-    gui::ui_element::UIEvent,
+use luma::engine::{
+    chess_moves::ChessMove,
+    directions::squares::Square,
+    piece::{Color, Piece},
 };
+
+use crate::gui::ui_element::{Navigation, UIEvent};
 #[derive(Debug)]
 
 pub enum GameState {
@@ -13,6 +12,7 @@ pub enum GameState {
     Computer,
     Player(SquareSelected),
     GameOver,
+    Viewing(ViewingData),
 }
 #[derive(Debug)]
 pub enum SquareSelected {
@@ -25,6 +25,12 @@ pub struct NoSquareSelectedData {
     pub possible_moves: Vec<ChessMove>,
     pub last_move: Option<ChessMove>,
 }
+/// An earlier position of the game is shown, `ply` moves after the start.
+#[derive(Debug)]
+pub struct ViewingData {
+    pub ply: usize,
+    pub last_move: Option<ChessMove>,
+}
 #[derive(Debug)]
 pub struct FromSquareSelectedData {
     pub from: Square,
@@ -79,6 +85,10 @@ pub enum StateFunction {
     SelectToSquare(SelectToSquareFunctionData),
     Promote(PromoteFunctionData),
     TurnBoard,
+    ChangeThreads,
+    Undo,
+    Redo,
+    Navigate(Navigation),
 }
 
 pub struct SelectFromSquareFunctionData {
@@ -97,7 +107,14 @@ impl SelectFromSquareFunctionData {
 pub fn get_function(event: UIEvent, state: &GameState) -> Option<StateFunction> {
     match event {
         UIEvent::TurnBoard => Some(StateFunction::TurnBoard),
+        UIEvent::ChangeThreads => Some(StateFunction::ChangeThreads),
         UIEvent::NewGameAs(color) => Some(StateFunction::NewGameAs(color)),
+        UIEvent::Undo if is_game_started(state) => Some(StateFunction::Undo),
+        UIEvent::Redo if is_game_started(state) => Some(StateFunction::Redo),
+        UIEvent::Navigate(navigation) if is_game_started(state) => {
+            Some(StateFunction::Navigate(navigation))
+        }
+        UIEvent::Undo | UIEvent::Redo | UIEvent::Navigate(_) => None,
         UIEvent::PromoteTo(piece) => match state {
             GameState::Player(SquareSelected::Promotion(data)) => Some(StateFunction::Promote(
                 PromoteFunctionData::from(data, piece),
@@ -118,3 +135,12 @@ pub fn get_function(event: UIEvent, state: &GameState) -> Option<StateFunction>
         },
     }
 }
+
+/// The moves of a game can be taken back and viewed while the player is to move
+/// or after the game is over, not while the computer is thinking.
+fn is_game_started(state: &GameState) -> bool {
+    matches!(
+        state,
+        GameState::Player(_) | GameState::GameOver | GameState::Viewing(_)
+    )
+}
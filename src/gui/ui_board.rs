@@ -1,17 +1,15 @@
 use std::collections::HashMap;
 
 use flo_canvas::Draw;
+use luma::engine::{directions::squares::Square, piece::Piece};
 
-use crate::{
-    engine::{directions::squares::Square, piece::Piece},
-    gui::{
-        configuration::{BLACK_FIELD_COLOR, WHITE_FIELD_COLOR},
-        ui_container::Container,
-        ui_element::{CanvasCoordinate, UIElement, UIEvent},
-        ui_layout::BoardLayout,
-        ui_piece::get_icon,
-        ui_square::UISquare,
-    },
+use crate::gui::{
+    configuration::{BLACK_FIELD_COLOR, WHITE_FIELD_COLOR},
+    ui_container::Container,
+    ui_element::{CanvasCoordinate, UIElement, UIEvent},
+    ui_layout::BoardLayout,
+    ui_piece::get_icon,
+    ui_square::UISquare,
 };
 #[derive(Debug)]
 pub enum Orientation {
@@ -41,6 +41,9 @@ impl UIButton {
     pub fn set_disabled(&mut self, disabled: bool) {
         self.disabled = disabled;
     }
+    pub fn is_disabled(&self) -> bool {
+        self.disabled
+    }
 
     pub fn group(mut self, group: Group) -> Self {
         self.group = group;
@@ -70,21 +73,25 @@ impl UIElement for UIButton {
         if self.disabled {
             return;
         }
-        gc.new_path();
-        gc.rect(
-            self.container.x_horizontal_min,
-            self.container.y_vertical_min,
-            self.container.x_horizontal_max,
-            self.container.y_vertical_max,
-        );
-        gc.load_texture(self.icon.texture_id, Cursor::new(self.icon.bytes));
-        gc.fill_texture(
-            self.icon.texture_id,
-            self.container.x_horizontal_min,
-            self.container.y_vertical_max,
-            self.container.x_horizontal_max,
-            self.container.y_vertical_min,
-        );
-        gc.fill();
+        draw_icon(gc, self.container, self.icon);
     }
 }
+
+pub fn draw_icon(gc: &mut Vec<Draw>, container: Container, icon: Icon) {
+    gc.new_path();
+    gc.rect(
+        container.x_horizontal_min,
+        container.y_vertical_min,
+        container.x_horizontal_max,
+        container.y_vertical_max,
+    );
+    gc.load_texture(icon.texture_id, Cursor::new(icon.bytes));
+    gc.fill_texture(
+        icon.texture_id,
+        container.x_horizontal_min,
+        container.y_vertical_max,
+        container.x_horizontal_max,
+        container.y_vertical_min,
+    );
+    gc.fill();
+}
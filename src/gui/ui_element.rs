@@ -1,6 +1,5 @@
 use flo_canvas::Draw;
-
-use crate::engine::{
+use luma::engine::{
     directions::squares::Square,
     piece::{Color, Piece},
 };
@@ -17,6 +16,19 @@ pub enum UIEvent {
     PromoteTo(Piece),
     Square(Square),
     TurnBoard,
+    ChangeThreads,
+    Undo,
+    Redo,
+    Navigate(Navigation),
+}
+
+/// Moves through the positions of the game without changing it.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Navigation {
+    First,
+    Previous,
+    Next,
+    Last,
 }
 
 pub trait UIElement {
@@ -1,18 +1,17 @@
 use flo_canvas::{Draw, GraphicsContext};
+use luma::engine::{
+    self, directions::squares::Square, piece::Piece, position::Position,
+    search_algorithms::score::Score,
+};
 
-use crate::{
-    engine::{
-        self, chess_moves::ChessMove, directions::squares::Square, piece::Piece, position::Position,
-    },
-    gui::{
-        configuration::{BACKGROUND_COLOR, FIELD_SIZE, MENU_HEIGHT},
-        state_machine::{GameState, SquareSelected},
-        ui_board::{Orientation, UIBoard},
-        ui_container::Container,
-        ui_element::{CanvasCoordinate, UIElement, UIEvent},
-        ui_layout::GameLayout,
-        ui_menu::UIMenu,
-    },
+use crate::gui::{
+    configuration::{BACKGROUND_COLOR, FIELD_SIZE, MENU_HEIGHT},
+    state_machine::{GameState, SquareSelected},
+    ui_board::{Orientation, UIBoard},
+    ui_container::Container,
+    ui_element::{CanvasCoordinate, UIElement, UIEvent},
+    ui_layout::GameLayout,
+    ui_menu::UIMenu,
 };
 
 pub struct UIGame {
@@ -38,6 +37,8 @@ impl UIGame {
 
     pub fn update(&mut self, position: &Position, state: &GameState) {
         self.reset_squares();
+        self.ui_menu
+            .set_thinking(matches!(state, GameState::Computer));
         for (square, piece) in position.get_all_pieces() {
             self.set_piece(square, piece);
         }
@@ -48,19 +49,19 @@ impl UIGame {
             match square_selected {
                 SquareSelected::From(data) => {
                     for chess_move in data.possible_moves_from.iter() {
-                        self.set_drop_target_square(chess_move.to);
+                        self.set_drop_target_square(chess_move.get_to());
                     }
                     self.set_selected_square(data.from);
                 }
                 SquareSelected::Promotion(data) => {
                     if let Some(chess_move) = data.possible_promotion_moves.first() {
-                        self.set_selected_square(chess_move.from);
-                        self.set_drop_target_square(chess_move.to);
+                        self.set_selected_square(chess_move.get_from());
+                        self.set_drop_target_square(chess_move.get_to());
                         match position.get_player() {
-                            crate::engine::piece::Color::Black => {
+                            engine::piece::Color::Black => {
                                 self.set_black_promotion_buttons_disabled(false)
                             }
-                            crate::engine::piece::Color::White => {
+                            engine::piece::Color::White => {
                                 self.set_white_promotion_buttons_disabled(false)
                             }
                         }
@@ -69,12 +70,18 @@ impl UIGame {
                 SquareSelected::No(data) => {
                     if let Some(chess_move) = data.last_move {
                         println!("Set last move");
-                        self.set_last_move_square(chess_move.from);
-                        self.set_last_move_square(chess_move.to);
+                        self.set_last_move_square(chess_move.get_from());
+                        self.set_last_move_square(chess_move.get_to());
                     }
                 }
             }
         }
+        if let GameState::Viewing(data) = state {
+            if let Some(chess_move) = data.last_move {
+                self.set_last_move_square(chess_move.get_from());
+                self.set_last_move_square(chess_move.get_to());
+            }
+        }
     }
 
     pub fn reset_squares(&mut self) {
@@ -100,6 +107,12 @@ impl UIGame {
     pub fn set_last_move_square(&mut self, square: Square) {
         self.ui_board.set_last_move_square(square);
     }
+    pub fn set_threads(&mut self, threads: usize) {
+        self.ui_menu.set_threads(threads);
+    }
+    pub fn set_score(&mut self, score: Option<Score>) {
+        self.ui_menu.set_score(score);
+    }
     pub fn disabled_promotion_buttons(&mut self) {
         self.set_black_promotion_buttons_disabled(true);
         self.set_white_promotion_buttons_disabled(true);
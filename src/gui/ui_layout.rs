@@ -44,6 +44,17 @@ impl MenuLayout {
     pub fn get(&self, column: Column) -> Container {
         self.layout.cell(1, column.as_index()).unwrap()
     }
+    /// The columns from `from` to `to` as one container.
+    pub fn span(&self, from: Column, to: Column) -> Container {
+        let from = self.get(from);
+        let to = self.get(to);
+        Container {
+            x_horizontal_min: from.x_horizontal_min,
+            x_horizontal_max: to.x_horizontal_max,
+            y_vertical_min: from.y_vertical_min,
+            y_vertical_max: to.y_vertical_max,
+        }
+    }
     pub fn new(container: Container) -> Self {
         MenuLayout {
             layout: Layout::new(container, 1, 16),
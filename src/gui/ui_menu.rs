@@ -1,20 +1,28 @@
 use flo_canvas::{Draw, GraphicsContext, GraphicsPrimitives};
+use luma::engine::{
+    piece::{Color, Piece},
+    search_algorithms::score::Score,
+};
 
-use crate::{
-    engine::piece::{Color, Piece},
-    gui::{
-        configuration::*,
-        icon::*,
-        ui_button::{Group, UIButton},
-        ui_container::Container,
-        ui_element::{CanvasCoordinate, UIElement, UIEvent},
-        ui_layout::*,
-    },
+use crate::gui::{
+    configuration::*,
+    icon::*,
+    segment_text::draw_text,
+    ui_button::{draw_icon, Group, UIButton},
+    ui_container::Container,
+    ui_element::{CanvasCoordinate, Navigation, UIElement, UIEvent},
+    ui_layout::*,
 };
 
 pub struct UIMenu {
     container: Container,
     buttons: Vec<UIButton>,
+    thinking_container: Container,
+    thinking: bool,
+    score_container: Container,
+    score: Option<Score>,
+    threads_container: Container,
+    threads: usize,
 }
 
 impl UIMenu {
@@ -22,6 +30,12 @@ impl UIMenu {
         let layout = MenuLayout::new(container);
         UIMenu {
             container,
+            thinking_container: layout.get(Column::Id12),
+            thinking: false,
+            score_container: layout.span(Column::Id13, Column::Id16),
+            score: None,
+            threads_container: layout.get(Column::Id4),
+            threads: 1,
             buttons: vec![
                 UIButton::new(
                     layout.get(Column::Id1),
@@ -38,6 +52,28 @@ impl UIMenu {
                     Icon::TURN_BOARD,
                     UIEvent::TurnBoard,
                 ),
+                UIButton::new(
+                    layout.get(Column::Id5),
+                    Icon::FIRST,
+                    UIEvent::Navigate(Navigation::First),
+                ),
+                UIButton::new(
+                    layout.get(Column::Id6),
+                    Icon::PREVIOUS,
+                    UIEvent::Navigate(Navigation::Previous),
+                ),
+                UIButton::new(
+                    layout.get(Column::Id7),
+                    Icon::NEXT,
+                    UIEvent::Navigate(Navigation::Next),
+                ),
+                UIButton::new(
+                    layout.get(Column::Id8),
+                    Icon::LAST,
+                    UIEvent::Navigate(Navigation::Last),
+                ),
+                UIButton::new(layout.get(Column::Id10), Icon::UNDO, UIEvent::Undo),
+                UIButton::new(layout.get(Column::Id11), Icon::REDO, UIEvent::Redo),
                 UIButton::new(
                     layout.get(Column::Id13),
                     Icon::WHITE_BISHOP,
@@ -107,6 +143,18 @@ impl UIMenu {
             button.set_disabled(disabled);
         }
     }
+    /// Shows that the computer is searching its move.
+    pub fn set_thinking(&mut self, thinking: bool) {
+        self.thinking = thinking;
+    }
+    /// Shows the number of search threads the computer uses, clicking it changes them.
+    pub fn set_threads(&mut self, threads: usize) {
+        self.threads = threads;
+    }
+    /// Shows the score of the computer's last move, from the computer's point of view.
+    pub fn set_score(&mut self, score: Option<Score>) {
+        self.score = score;
+    }
     pub fn set_black_promotion_buttons_disabled(&mut self, disabled: bool) {
         self.set_group_disabled(disabled, Group::BlackPromotionButtons);
     }
@@ -123,6 +171,13 @@ impl UIElement for UIMenu {
                 return Some(event);
             }
         }
+        if canvas_coordinate.x >= self.threads_container.x_horizontal_min
+            && canvas_coordinate.x <= self.threads_container.x_horizontal_max
+            && canvas_coordinate.y >= self.threads_container.y_vertical_min
+            && canvas_coordinate.y <= self.threads_container.y_vertical_max
+        {
+            return Some(UIEvent::ChangeThreads);
+        }
         None
     }
 
@@ -139,5 +194,17 @@ impl UIElement for UIMenu {
         for button in self.buttons.iter().clone() {
             button.draw(gc);
         }
+        draw_text(gc, self.threads_container, &self.threads.to_string());
+        if self.thinking {
+            draw_icon(gc, self.thinking_container, Icon::THINKING);
+        }
+        // The score shares its place with the promotion buttons.
+        let promoting = self
+            .buttons
+            .iter()
+            .any(|button| button.get_group() != Group::Default && !button.is_disabled());
+        if let (Some(score), false) = (self.score, promoting) {
+            draw_text(gc, self.score_container, &score.to_string());
+        }
     }
 }
@@ -1,4 +1,6 @@
-use crate::{engine::piece::Piece, gui::icon::Icon};
+use luma::engine::piece::Piece;
+
+use crate::gui::icon::Icon;
 
 pub fn get_icon(piece: Piece) -> Icon {
     match piece {
@@ -1,15 +1,13 @@
 use std::io::Cursor;
 
 use flo_canvas::{Color, Draw, GraphicsContext, GraphicsPrimitives};
+use luma::engine::directions::squares::Square;
 
-use crate::{
-    engine::directions::squares::Square,
-    gui::{
-        configuration::{CHECK_COLOR, DROP_TARGET_COLOR, FROM_TO_COLOR, SELECTED_FIELD_COLOR},
-        icon::Icon,
-        ui_container::Container,
-        ui_element::{CanvasCoordinate, UIElement, UIEvent},
-    },
+use crate::gui::{
+    configuration::{CHECK_COLOR, DROP_TARGET_COLOR, FROM_TO_COLOR, SELECTED_FIELD_COLOR},
+    icon::Icon,
+    ui_container::Container,
+    ui_element::{CanvasCoordinate, UIElement, UIEvent},
 };
 
 #[derive(Clone)]
@@ -0,0 +1,46 @@
+//! The luma chess engine: positions, move generation, evaluation and search, plus the
+//! UCI protocol. The GUI is a separate binary behind the `gui` feature, so this library
+//! has no graphics dependencies.
+//!
+//! ```
+//! use luma::{get_best_move, GameHistory, Position, SearchLimits, TranspositionTable};
+//!
+//! let history = GameHistory::new(Position::new_starting_position());
+//! let limits = SearchLimits {
+//!     max_depth: Some(2),
+//!     ..SearchLimits::default()
+//! };
+//! let mut table = TranspositionTable::new(1);
+//! let best_move = get_best_move(&history, &limits, &mut table).unwrap();
+//! println!("{}", best_move.to_uci());
+//! ```
+
+pub mod engine;
+pub mod tune;
+pub mod uci;
+
+pub use engine::{
+    attacks::attacks_from,
+    check::{is_check, is_under_attack},
+    chess_moves::{
+        get_current_player_moves,
+        notation::MoveParseError,
+        perft::{divide, perft},
+        ChessMove, MoveType,
+    },
+    directions::squares::Square,
+    evaluation::{
+        params::{EvalParams, ParamsError},
+        Evaluation,
+    },
+    game_history::GameHistory,
+    get_game_end, get_next_move, get_possible_moves,
+    piece::{Color, Piece},
+    position::{bitboard::Bitboard, fen::FenError, make_move::UndoInfo, Position},
+    search_algorithms::{
+        config::SearchConfig, get_best_move, iterative_deepening::iterative_deepening,
+        limits::SearchLimits, score::Score, search_info::SearchInfo,
+    },
+    transposition_table::TranspositionTable,
+    DrawReason, GameEnd, MoveOrEnd,
+};
@@ -0,0 +1,82 @@
+//! Tunes the evaluation parameters on labelled positions, see `luma::tune`.
+//!
+//! `luma-tune POSITIONS [--params FILE] [--output FILE] [--iterations N]`
+//!
+//! Starts from the parameters in `--params`, the compiled-in ones without it, and writes
+//! the tuned parameters to `--output` after every pass, so a long run can be stopped at
+//! any time. The result is loaded by the engine with the UCI option EvalFile.
+
+use std::{env, path::PathBuf, process};
+
+use luma::{tune, EvalParams};
+
+const DEFAULT_OUTPUT: &str = "tuned.txt";
+const DEFAULT_ITERATIONS: usize = 100;
+const USAGE: &str = "usage: luma-tune POSITIONS [--params FILE] [--output FILE] [--iterations N]";
+
+struct Arguments {
+    positions: PathBuf,
+    params: Option<PathBuf>,
+    output: PathBuf,
+    iterations: usize,
+}
+
+fn parse_arguments(mut args: impl Iterator<Item = String>) -> Option<Arguments> {
+    let mut positions = None;
+    let mut params = None;
+    let mut output = PathBuf::from(DEFAULT_OUTPUT);
+    let mut iterations = DEFAULT_ITERATIONS;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--params" => params = Some(PathBuf::from(args.next()?)),
+            "--output" => output = PathBuf::from(args.next()?),
+            "--iterations" => iterations = args.next()?.parse().ok()?,
+            _ if positions.is_none() && !arg.starts_with("--") => {
+                positions = Some(PathBuf::from(arg))
+            }
+            _ => return None,
+        }
+    }
+    Some(Arguments {
+        positions: positions?,
+        params,
+        output,
+        iterations,
+    })
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("{}", message);
+    process::exit(1);
+}
+
+pub fn main() {
+    let arguments = parse_arguments(env::args().skip(1)).unwrap_or_else(|| fail(USAGE));
+    let params = match &arguments.params {
+        Some(path) => EvalParams::load(path)
+            .unwrap_or_else(|error| fail(&format!("{}: {}", path.display(), error))),
+        None => EvalParams::default(),
+    };
+    let positions = tune::load_positions(&arguments.positions)
+        .unwrap_or_else(|error| fail(&format!("{}: {}", arguments.positions.display(), error)));
+    println!("{} positions", positions.len());
+    let scaling = tune::find_scaling(&positions, &params);
+    println!(
+        "scaling {:.4} error {:.6}",
+        scaling,
+        tune::get_error(&positions, &params, scaling)
+    );
+    tune::tune(
+        &positions,
+        &params,
+        scaling,
+        arguments.iterations,
+        |iteration, params, error| {
+            println!("iteration {} error {:.6}", iteration, error);
+            if let Err(error) = params.save(&arguments.output) {
+                fail(&format!("{}: {}", arguments.output.display(), error));
+            }
+        },
+    );
+    println!("saved {}", arguments.output.display());
+}
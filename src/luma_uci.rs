@@ -0,0 +1,7 @@
+use std::io;
+
+use luma::uci;
+
+pub fn main() {
+    uci::run(io::stdin().lock(), io::stdout());
+}
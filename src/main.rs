@@ -1,32 +1,68 @@
+use std::env;
+
 use flo_draw::{
     binding::{bind, BindRef},
     create_drawing_window_with_events, with_2d_graphics, DrawEvent, MousePointer, PointerAction,
     WindowProperties,
 };
-use futures::{executor, StreamExt};
+use futures::{channel::mpsc, executor, stream, StreamExt};
+use luma::engine::search_algorithms::config::SearchConfig;
+
+use crate::gui::{
+    game::{Game, SearchResult},
+    ui_element::CanvasCoordinate,
+};
 
-use crate::gui::{game::Game, ui_element::CanvasCoordinate};
+/// The window events and the results of background searches, handled in one loop.
+enum Event {
+    Draw(DrawEvent),
+    Search(SearchResult),
+}
+
+/// The value of `--threads N`, one thread at least. Without it the computer searches
+/// with the engine's default, as the UCI `Threads` option does. The menu changes the
+/// number during the game.
+fn parse_threads(mut args: impl Iterator<Item = String>) -> usize {
+    let mut threads = SearchConfig::default().threads;
+    while let Some(arg) = args.next() {
+        if arg == "--threads" {
+            if let Some(value) = args.next().and_then(|value| value.parse::<usize>().ok()) {
+                threads = value.max(1);
+            }
+        }
+    }
+    threads
+}
 
 pub fn main() {
+    let threads = parse_threads(env::args().skip(1));
     let mut window_properties = WindowProperties::from(&"luma Chess");
     window_properties.mouse_pointer = BindRef::from(bind(MousePointer::SystemDefault));
     with_2d_graphics(move || {
-        let (canvas, mut events) = create_drawing_window_with_events(window_properties);
-        let mut game: Game = Game::new(canvas);
+        let (canvas, draw_events) = create_drawing_window_with_events(window_properties);
+        let (search_sender, search_results) = mpsc::unbounded();
+        let mut game: Game = Game::new(canvas, search_sender, threads);
         game.draw();
+        let mut events = stream::select(
+            draw_events.map(Event::Draw),
+            search_results.map(Event::Search),
+        );
         executor::block_on(async move {
-            while let Some(evt) = events.next().await {
-                if let DrawEvent::Pointer(PointerAction::ButtonDown, _, state) = evt {
-                    if let Some(coord) = state.location_in_canvas {
-                        game.handle_click_event(CanvasCoordinate {
-                            x: coord.0 as f32,
-                            y: coord.1 as f32,
-                        });
+            while let Some(event) = events.next().await {
+                match event {
+                    Event::Draw(DrawEvent::Pointer(PointerAction::ButtonDown, _, state)) => {
+                        if let Some(coord) = state.location_in_canvas {
+                            game.handle_click_event(CanvasCoordinate {
+                                x: coord.0 as f32,
+                                y: coord.1 as f32,
+                            });
+                        }
                     }
+                    Event::Draw(_) => {}
+                    Event::Search(result) => game.handle_search_result(result),
                 }
             }
         });
     });
 }
-mod engine;
 mod gui;
@@ -0,0 +1,240 @@
+//! Texel tuning of the evaluation parameters. The evaluation of labelled positions is
+//! mapped to an expected result with a sigmoid, and the parameters are changed one step
+//! at a time as long as the mean squared error against the game results drops. The
+//! positions should be quiet, since the static evaluation can't see captures.
+
+use std::{
+    fmt, fs, io,
+    path::Path,
+    sync::{mpsc, Arc},
+    thread,
+};
+
+use crate::engine::{evaluation::params::EvalParams, evaluation::Evaluation, position::Position};
+
+/// Bounds of the search for the sigmoid's scaling constant.
+const MAX_SCALING: f64 = 10.0;
+const SCALING_STEPS: usize = 60;
+
+/// A position and the result of the game it was taken from, for white: 1 for a win,
+/// 0.5 for a draw and 0 for a loss.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LabelledPosition {
+    pub position: Position,
+    pub result: f64,
+}
+
+#[derive(Debug)]
+pub enum TuneError {
+    Io(io::Error),
+    InvalidLine { line: usize, reason: String },
+    NoPositions,
+}
+
+impl fmt::Display for TuneError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TuneError::Io(error) => write!(f, "{}", error),
+            TuneError::InvalidLine { line, reason } => write!(f, "line {}: {}", line, reason),
+            TuneError::NoPositions => write!(f, "no labelled positions"),
+        }
+    }
+}
+
+impl std::error::Error for TuneError {}
+
+impl From<io::Error> for TuneError {
+    fn from(error: io::Error) -> Self {
+        TuneError::Io(error)
+    }
+}
+
+pub fn load_positions(path: &Path) -> Result<Vec<LabelledPosition>, TuneError> {
+    parse_positions(&fs::read_to_string(path)?)
+}
+
+/// Reads one position per line: a FEN followed by the result as `1-0`, `1/2-1/2`, `0-1`
+/// or a number, optionally in quotes or brackets, as in `... w - - 0 1 [1/2-1/2]` or
+/// EPD's `... w - - c9 "1-0";`. Empty lines are skipped.
+pub fn parse_positions(text: &str) -> Result<Vec<LabelledPosition>, TuneError> {
+    let mut positions = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let position = parse_line(line).map_err(|reason| TuneError::InvalidLine {
+            line: index + 1,
+            reason,
+        })?;
+        positions.push(position);
+    }
+    if positions.is_empty() {
+        return Err(TuneError::NoPositions);
+    }
+    Ok(positions)
+}
+
+fn parse_line(line: &str) -> Result<LabelledPosition, String> {
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    let result_token = tokens.last().ok_or("empty line")?;
+    let result = parse_result(result_token.trim_matches(|c| "\"[];,|".contains(c)))
+        .ok_or_else(|| format!("invalid result '{}'", result_token))?;
+    // The four required FEN fields, followed by the move counters if they are given.
+    let counters = tokens
+        .iter()
+        .skip(4)
+        .take(2)
+        .take_while(|token| token.parse::<u32>().is_ok())
+        .count();
+    let fen = tokens[..(4 + counters).min(tokens.len())].join(" ");
+    let position = Position::from_fen(&fen).map_err(|error| error.to_string())?;
+    Ok(LabelledPosition { position, result })
+}
+
+fn parse_result(result: &str) -> Option<f64> {
+    match result {
+        "1-0" => Some(1.0),
+        "0-1" => Some(0.0),
+        "1/2-1/2" | "1/2" => Some(0.5),
+        number => number
+            .parse::<f64>()
+            .ok()
+            .filter(|result| (0.0..=1.0).contains(result)),
+    }
+}
+
+/// The expected result for white of a position evaluated at `score` centipawns.
+pub fn get_expected_result(score: i32, scaling: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf(-scaling * score as f64 / 400.0))
+}
+
+/// The mean squared error of the expected results against the game results, computed on
+/// all available cores.
+pub fn get_error(positions: &[LabelledPosition], params: &EvalParams, scaling: f64) -> f64 {
+    thread::scope(|scope| ErrorWorkers::new(scope, positions).get_error(params, scaling))
+}
+
+/// The parameters and the scaling to compute an error for.
+type Request = (Arc<EvalParams>, f64);
+
+/// Threads that each hold a share of the positions and compute its squared error on
+/// request. They are started once for many errors, and stop when this is dropped.
+struct ErrorWorkers {
+    workers: Vec<(mpsc::Sender<Request>, mpsc::Receiver<f64>)>,
+    positions: usize,
+}
+
+impl ErrorWorkers {
+    fn new<'scope>(
+        scope: &'scope thread::Scope<'scope, '_>,
+        positions: &'scope [LabelledPosition],
+    ) -> Self {
+        let threads = thread::available_parallelism().map_or(1, |threads| threads.get());
+        let chunk_size = positions.len().div_ceil(threads).max(1);
+        let workers = positions
+            .chunks(chunk_size)
+            .map(|chunk| {
+                let (request_sender, requests) = mpsc::channel::<Request>();
+                let (error_sender, errors) = mpsc::channel();
+                scope.spawn(move || {
+                    for (params, scaling) in requests {
+                        let _ = error_sender.send(get_squared_error(chunk, &params, scaling));
+                    }
+                });
+                (request_sender, errors)
+            })
+            .collect();
+        ErrorWorkers {
+            workers,
+            positions: positions.len(),
+        }
+    }
+
+    fn get_error(&self, params: &EvalParams, scaling: f64) -> f64 {
+        let params = Arc::new(params.clone());
+        for (requests, _) in &self.workers {
+            let _ = requests.send((Arc::clone(&params), scaling));
+        }
+        // A worker that panicked has dropped its sender, its share then counts as an
+        // infinite error instead of blocking.
+        let total: f64 = self
+            .workers
+            .iter()
+            .map(|(_, errors)| errors.recv().unwrap_or(f64::INFINITY))
+            .sum();
+        total / self.positions.max(1) as f64
+    }
+}
+
+fn get_squared_error(positions: &[LabelledPosition], params: &EvalParams, scaling: f64) -> f64 {
+    positions
+        .iter()
+        .map(|labelled| {
+            let score = Evaluation::with_params(&labelled.position, params).score;
+            (labelled.result - get_expected_result(score, scaling)).powi(2)
+        })
+        .sum()
+}
+
+/// The scaling constant of the sigmoid that fits `params` best, so the tuning changes the
+/// parameters rather than their scale.
+pub fn find_scaling(positions: &[LabelledPosition], params: &EvalParams) -> f64 {
+    thread::scope(|scope| {
+        let workers = ErrorWorkers::new(scope, positions);
+        // The error is unimodal in the scaling, so a ternary search finds its minimum.
+        let (mut low, mut high) = (0.0, MAX_SCALING);
+        for _ in 0..SCALING_STEPS {
+            let lower_third = low + (high - low) / 3.0;
+            let upper_third = high - (high - low) / 3.0;
+            if workers.get_error(params, lower_third) < workers.get_error(params, upper_third) {
+                high = upper_third;
+            } else {
+                low = lower_third;
+            }
+        }
+        (low + high) / 2.0
+    })
+}
+
+/// Local search: every parameter is moved up or down by one while that lowers the error,
+/// until a pass changes nothing or `max_iterations` passes are done. `on_iteration`
+/// receives the pass number, the parameters and their error after every pass.
+pub fn tune<F: FnMut(usize, &EvalParams, f64)>(
+    positions: &[LabelledPosition],
+    params: &EvalParams,
+    scaling: f64,
+    max_iterations: usize,
+    mut on_iteration: F,
+) -> EvalParams {
+    thread::scope(|scope| {
+        let workers = ErrorWorkers::new(scope, positions);
+        let mut params = params.clone();
+        let mut values = params.get_values();
+        let mut best_error = workers.get_error(&params, scaling);
+        for iteration in 1..=max_iterations {
+            let mut improved = false;
+            for index in 0..values.len() {
+                for step in [1, -1] {
+                    values[index] += step;
+                    params.set_values(&values);
+                    let error = workers.get_error(&params, scaling);
+                    if error < best_error {
+                        best_error = error;
+                        improved = true;
+                        break;
+                    }
+                    values[index] -= step;
+                }
+            }
+            params.set_values(&values);
+            on_iteration(iteration, &params, best_error);
+            if !improved {
+                break;
+            }
+        }
+        params
+    })
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,73 @@
+use crate::{
+    engine::{evaluation::params::DEFAULT_PARAMS, position::Position},
+    tune::{
+        find_scaling, get_error, get_expected_result, parse_positions, tune, LabelledPosition,
+        TuneError,
+    },
+};
+
+const POSITIONS: &str = "\
+4k3/8/8/8/8/8/PPP5/4K3 w - - 0 1 [1-0]
+4k3/ppp5/8/8/8/8/8/4K3 b - - 0 1 [0-1]
+4k3/p7/8/8/8/8/P7/4K3 w - - 0 1 [1/2-1/2]
+4k3/8/8/8/8/8/PP6/4K3 b - - 3 40 [1.0]
+";
+
+#[test]
+fn test_parse_positions() {
+    let text = "8/8/8/8/8/8/8/K1k5 w - - 0 1 [1/2-1/2]\n\n\
+        8/8/8/8/8/8/P7/K1k5 w - - c9 \"1-0\";\n\
+        8/8/8/8/8/8/p7/K1k5 b - - 12 60 0.0\n";
+    let positions = parse_positions(text).unwrap();
+    assert_eq!(
+        positions
+            .iter()
+            .map(|labelled| labelled.result)
+            .collect::<Vec<_>>(),
+        vec![0.5, 1.0, 0.0]
+    );
+    assert_eq!(
+        positions[2].position,
+        Position::from_fen("8/8/8/8/8/8/p7/K1k5 b - - 12 60").unwrap()
+    );
+}
+
+#[test]
+fn test_parse_errors() {
+    assert!(matches!(
+        parse_positions("8/8/8/8/8/8/8/K1k5 w - - 0 1 [1-0]\n8/8/8/8/8/8/8/K1k5 w - - 0 1 2-0"),
+        Err(TuneError::InvalidLine { line: 2, .. })
+    ));
+    assert!(matches!(
+        parse_positions("8/8/8/8/8/8/8/K1k5 x - - 0 1 1-0"),
+        Err(TuneError::InvalidLine { line: 1, .. })
+    ));
+    assert!(matches!(parse_positions("\n"), Err(TuneError::NoPositions)));
+}
+
+#[test]
+fn test_expected_result() {
+    assert_eq!(get_expected_result(0, 1.0), 0.5);
+    assert!((get_expected_result(400, 1.0) - 10.0 / 11.0).abs() < 1e-9);
+    assert!((get_expected_result(-400, 1.0) - 1.0 / 11.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_tuning_lowers_error() {
+    let positions: Vec<LabelledPosition> = parse_positions(POSITIONS).unwrap();
+    let scaling = find_scaling(&positions, &DEFAULT_PARAMS);
+    assert!(scaling > 0.0);
+    let error = get_error(&positions, &DEFAULT_PARAMS, scaling);
+    let mut reported = Vec::new();
+    let tuned = tune(
+        &positions,
+        &DEFAULT_PARAMS,
+        scaling,
+        2,
+        |iteration, _, error| reported.push((iteration, error)),
+    );
+    let tuned_error = get_error(&positions, &tuned, scaling);
+    assert!(tuned_error < error);
+    assert_eq!(reported.last().map(|(_, error)| *error), Some(tuned_error));
+    assert!(reported.len() <= 2);
+}
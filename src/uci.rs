@@ -0,0 +1,312 @@
+use std::{
+    io::{BufRead, Write},
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use crate::{
+    engine::{
+        chess_moves::ChessMove,
+        evaluation::params::EvalParams,
+        game_history::GameHistory,
+        position::Position,
+        search_algorithms::{
+            config::SearchConfig, iterative_deepening::iterative_deepening, search_info::SearchInfo,
+        },
+        transposition_table::{self, TranspositionTable},
+    },
+    uci::command::{Command, GoParameters},
+};
+
+pub mod command;
+
+const ENGINE_NAME: &str = "luma";
+const ENGINE_AUTHOR: &str = "the luma developers";
+const STOP_POLL_INTERVAL: Duration = Duration::from_millis(10);
+const MAX_HASH_MB: usize = 4096;
+const MAX_THREADS: usize = 256;
+
+type ConfigSwitch = fn(&mut SearchConfig) -> &mut bool;
+
+/// The techniques of `SearchConfig` as check options, so self-play can measure each one.
+const CONFIG_OPTIONS: [(&str, ConfigSwitch); 4] = [
+    ("NullMovePruning", |config| &mut config.null_move_pruning),
+    ("LateMoveReductions", |config| {
+        &mut config.late_move_reductions
+    }),
+    ("PrincipalVariationSearch", |config| {
+        &mut config.principal_variation_search
+    }),
+    ("CheckExtensions", |config| &mut config.check_extensions),
+];
+
+type Output<W> = Arc<Mutex<W>>;
+
+struct RunningSearch {
+    stop: Arc<AtomicBool>,
+    thread: JoinHandle<()>,
+    infinite: bool,
+}
+
+pub struct Uci<W: Write + Send + 'static> {
+    history: GameHistory,
+    output: Output<W>,
+    table: Arc<Mutex<TranspositionTable>>,
+    config: SearchConfig,
+    /// Loaded with the EvalFile option, the compiled-in parameters if `None`.
+    params: Option<Arc<EvalParams>>,
+    search: Option<RunningSearch>,
+}
+
+/// Reads UCI commands from `input` until `quit` or end of input and answers on `output`.
+/// At the end of input a running search is finished, so scripted transcripts can be piped in.
+pub fn run<R: BufRead, W: Write + Send + 'static>(input: R, output: W) {
+    let mut uci = Uci::new(output);
+    for line in input.lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        match command::parse(&line) {
+            Some(Command::Quit) => {
+                uci.stop_search();
+                return;
+            }
+            Some(command) => uci.handle(command),
+            None => {}
+        }
+    }
+    uci.finish_search();
+}
+
+impl<W: Write + Send + 'static> Uci<W> {
+    pub fn new(output: W) -> Self {
+        Uci {
+            history: GameHistory::new(Position::new_starting_position()),
+            output: Arc::new(Mutex::new(output)),
+            table: Arc::new(Mutex::new(TranspositionTable::new(
+                transposition_table::DEFAULT_SIZE_MB,
+            ))),
+            config: SearchConfig::default(),
+            params: None,
+            search: None,
+        }
+    }
+
+    pub fn handle(&mut self, command: Command) {
+        match command {
+            Command::Uci => {
+                write_line(&self.output, &format!("id name {}", ENGINE_NAME));
+                write_line(&self.output, &format!("id author {}", ENGINE_AUTHOR));
+                write_line(
+                    &self.output,
+                    &format!(
+                        "option name Hash type spin default {} min 1 max {}",
+                        transposition_table::DEFAULT_SIZE_MB,
+                        MAX_HASH_MB
+                    ),
+                );
+                write_line(
+                    &self.output,
+                    &format!(
+                        "option name Threads type spin default {} min 1 max {}",
+                        SearchConfig::default().threads,
+                        MAX_THREADS
+                    ),
+                );
+                write_line(
+                    &self.output,
+                    "option name EvalFile type string default <empty>",
+                );
+                for (name, switch) in CONFIG_OPTIONS {
+                    let default = *switch(&mut SearchConfig::default());
+                    write_line(
+                        &self.output,
+                        &format!("option name {} type check default {}", name, default),
+                    );
+                }
+                write_line(&self.output, "uciok");
+            }
+            Command::IsReady => write_line(&self.output, "readyok"),
+            Command::UciNewGame => {
+                self.stop_search();
+                self.history = GameHistory::new(Position::new_starting_position());
+                if let Ok(mut table) = self.table.lock() {
+                    table.clear();
+                }
+            }
+            Command::SetOption { name, value } => self.set_option(&name, value),
+            Command::Position { fen, moves } => self.set_position(fen, moves),
+            Command::Go(parameters) => self.go(parameters),
+            Command::Stop => self.stop_search(),
+            Command::Quit => self.stop_search(),
+        }
+    }
+
+    fn set_option(&mut self, name: &str, value: Option<String>) {
+        if name.eq_ignore_ascii_case("Hash") {
+            match value.and_then(|value| value.parse::<usize>().ok()) {
+                Some(size_mb) => {
+                    self.stop_search();
+                    self.table = Arc::new(Mutex::new(TranspositionTable::new(
+                        size_mb.clamp(1, MAX_HASH_MB),
+                    )));
+                }
+                None => write_line(&self.output, "info string invalid Hash value"),
+            }
+        } else if name.eq_ignore_ascii_case("Threads") {
+            match value.and_then(|value| value.parse::<usize>().ok()) {
+                Some(threads) => self.config.threads = threads.clamp(1, MAX_THREADS),
+                None => write_line(&self.output, "info string invalid Threads value"),
+            }
+        } else if name.eq_ignore_ascii_case("EvalFile") {
+            // An empty value restores the compiled-in parameters.
+            match value.filter(|path| path != "<empty>") {
+                Some(path) => match EvalParams::load(Path::new(&path)) {
+                    Ok(params) => self.params = Some(Arc::new(params)),
+                    Err(error) => write_line(
+                        &self.output,
+                        &format!("info string invalid EvalFile: {}", error),
+                    ),
+                },
+                None => self.params = None,
+            }
+        } else if let Some((name, switch)) = CONFIG_OPTIONS
+            .into_iter()
+            .find(|(option, _)| name.eq_ignore_ascii_case(option))
+        {
+            match value.and_then(|value| value.parse::<bool>().ok()) {
+                Some(enabled) => *switch(&mut self.config) = enabled,
+                None => write_line(&self.output, &format!("info string invalid {} value", name)),
+            }
+        }
+    }
+
+    fn set_position(&mut self, fen: Option<String>, moves: Vec<String>) {
+        let position = match fen {
+            None => Position::new_starting_position(),
+            Some(fen) => match Position::from_fen(&fen) {
+                Ok(position) => position,
+                Err(error) => {
+                    write_line(&self.output, &format!("info string invalid fen: {}", error));
+                    return;
+                }
+            },
+        };
+        let mut history = GameHistory::new(position);
+        for uci_move in moves {
+            match ChessMove::parse_uci(&history.get_current(), &uci_move) {
+                Ok(chess_move) => history.push_move(chess_move),
+                Err(error) => {
+                    write_line(&self.output, &format!("info string {}", error));
+                    break;
+                }
+            }
+        }
+        self.history = history;
+    }
+
+    fn go(&mut self, parameters: GoParameters) {
+        self.stop_search();
+        let history = self.history.clone();
+        let player = history.get_current().get_player();
+        let output = Arc::clone(&self.output);
+        let table = Arc::clone(&self.table);
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = Arc::clone(&stop);
+        let infinite = parameters.infinite;
+        let mut limits = parameters.to_search_limits(player);
+        limits.config = self.config;
+        limits.params = self.params.clone();
+        let thread = thread::spawn(move || {
+            // Only one search runs at a time, so the lock is never contended.
+            let mut table = table.lock().unwrap_or_else(|error| error.into_inner());
+            // A panic must not keep the GUI waiting for the bestmove.
+            let last_iteration = panic::catch_unwind(AssertUnwindSafe(|| {
+                iterative_deepening(&history, &limits, &thread_stop, &mut table, |info| {
+                    for line in info_lines(info) {
+                        write_line(&output, &line);
+                    }
+                })
+            }))
+            .unwrap_or_else(|_| {
+                write_line(&output, "info string search failed");
+                None
+            });
+            drop(table);
+            // In infinite mode the bestmove may only be sent after the GUI said stop.
+            while parameters.infinite && !thread_stop.load(Ordering::Relaxed) {
+                thread::sleep(STOP_POLL_INTERVAL);
+            }
+            let best_move = match last_iteration.and_then(|iteration| iteration.best_move()) {
+                Some(chess_move) => chess_move.to_uci(),
+                None => String::from("0000"),
+            };
+            write_line(&output, &format!("bestmove {}", best_move));
+        });
+        self.search = Some(RunningSearch {
+            stop,
+            thread,
+            infinite,
+        });
+    }
+
+    /// Stops a running search and waits until it has sent its bestmove.
+    fn stop_search(&mut self) {
+        if let Some(search) = self.search.take() {
+            search.stop.store(true, Ordering::Relaxed);
+            let _ = search.thread.join();
+        }
+    }
+
+    /// Waits for a running search to reach its limits. Infinite searches have none and are stopped.
+    fn finish_search(&mut self) {
+        match &self.search {
+            Some(search) if !search.infinite => {
+                if let Some(search) = self.search.take() {
+                    let _ = search.thread.join();
+                }
+            }
+            _ => self.stop_search(),
+        }
+    }
+}
+
+/// The standard info line of an iteration, followed by the pawn table's hit rate in
+/// permille, which UCI has no field for.
+fn info_lines(info: &SearchInfo) -> [String; 2] {
+    let pv: Vec<String> = info
+        .pv
+        .iter()
+        .map(|chess_move| chess_move.to_uci())
+        .collect();
+    [
+        format!(
+            "info depth {} seldepth {} score {} nodes {} nps {} time {} hashfull {} pv {}",
+            info.depth,
+            info.seldepth,
+            info.score().to_uci(),
+            info.nodes,
+            info.get_nodes_per_second(),
+            info.elapsed.as_millis(),
+            info.hash_fill,
+            pv.join(" ")
+        ),
+        format!("info string pawnhits {}", info.pawn_hits),
+    ]
+}
+
+fn write_line<W: Write>(output: &Output<W>, line: &str) {
+    if let Ok(mut output) = output.lock() {
+        let _ = writeln!(output, "{}", line);
+        let _ = output.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,150 @@
+use std::time::Duration;
+
+use crate::engine::{
+    piece::Color,
+    search_algorithms::limits::{Clock, SearchLimits},
+};
+
+/// Depth of a `go` without any limit.
+pub const DEFAULT_DEPTH: u8 = 4;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Command {
+    Uci,
+    IsReady,
+    UciNewGame,
+    Position {
+        fen: Option<String>,
+        moves: Vec<String>,
+    },
+    SetOption {
+        name: String,
+        value: Option<String>,
+    },
+    Go(GoParameters),
+    Stop,
+    Quit,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GoParameters {
+    pub depth: Option<u8>,
+    pub nodes: Option<u64>,
+    pub movetime: Option<u64>,
+    pub wtime: Option<u64>,
+    pub btime: Option<u64>,
+    pub winc: Option<u64>,
+    pub binc: Option<u64>,
+    pub movestogo: Option<u32>,
+    pub infinite: bool,
+}
+
+impl GoParameters {
+    /// The limits for a search by `color`. Without any limit the search stops at the default depth,
+    /// an infinite search only ends with `stop`.
+    pub fn to_search_limits(&self, color: Color) -> SearchLimits {
+        let clock = match color {
+            Color::White => self.wtime.map(|time| (time, self.winc.unwrap_or(0))),
+            Color::Black => self.btime.map(|time| (time, self.binc.unwrap_or(0))),
+        };
+        if self.infinite {
+            return SearchLimits::default();
+        }
+        let mut limits = SearchLimits {
+            max_depth: self.depth,
+            max_nodes: self.nodes,
+            move_time: self.movetime.map(Duration::from_millis),
+            clock: clock.map(|(time, increment)| Clock {
+                remaining: Duration::from_millis(time),
+                increment: Duration::from_millis(increment),
+                moves_to_go: self.movestogo,
+            }),
+            ..Default::default()
+        };
+        if limits == SearchLimits::default() {
+            limits.max_depth = Some(DEFAULT_DEPTH);
+        }
+        limits
+    }
+}
+
+/// Parses one line sent by the GUI. Unknown commands are ignored as the protocol demands.
+pub fn parse(line: &str) -> Option<Command> {
+    let mut tokens = line.split_whitespace();
+    match tokens.next()? {
+        "uci" => Some(Command::Uci),
+        "isready" => Some(Command::IsReady),
+        "ucinewgame" => Some(Command::UciNewGame),
+        "setoption" => parse_setoption(tokens.collect()),
+        "position" => parse_position(tokens.collect()),
+        "go" => Some(Command::Go(parse_go(tokens.collect()))),
+        "stop" => Some(Command::Stop),
+        "quit" => Some(Command::Quit),
+        _ => None,
+    }
+}
+
+fn parse_position(tokens: Vec<&str>) -> Option<Command> {
+    let moves_index = tokens
+        .iter()
+        .position(|token| *token == "moves")
+        .unwrap_or(tokens.len());
+    let moves = tokens
+        .iter()
+        .skip(moves_index + 1)
+        .map(|token| token.to_string())
+        .collect();
+    match tokens.first() {
+        Some(&"startpos") => Some(Command::Position { fen: None, moves }),
+        Some(&"fen") => Some(Command::Position {
+            fen: Some(tokens[1..moves_index].join(" ")),
+            moves,
+        }),
+        _ => None,
+    }
+}
+
+// Option names and values may contain spaces.
+fn parse_setoption(tokens: Vec<&str>) -> Option<Command> {
+    if tokens.first() != Some(&"name") {
+        return None;
+    }
+    let value_index = tokens
+        .iter()
+        .position(|token| *token == "value")
+        .unwrap_or(tokens.len());
+    let name = tokens[1..value_index].join(" ");
+    let value = tokens
+        .get(value_index + 1..)
+        .map(|value| value.join(" "))
+        .filter(|value| !value.is_empty());
+    Some(Command::SetOption { name, value })
+}
+
+fn parse_go(tokens: Vec<&str>) -> GoParameters {
+    let mut parameters = GoParameters::default();
+    let mut iter = tokens.into_iter();
+    while let Some(token) = iter.next() {
+        match token {
+            "infinite" => parameters.infinite = true,
+            "depth" => parameters.depth = iter.next().and_then(|value| value.parse().ok()),
+            "nodes" => parameters.nodes = iter.next().and_then(|value| value.parse().ok()),
+            "movetime" => parameters.movetime = iter.next().and_then(|value| value.parse().ok()),
+            "wtime" => parameters.wtime = iter.next().and_then(parse_time),
+            "btime" => parameters.btime = iter.next().and_then(parse_time),
+            "winc" => parameters.winc = iter.next().and_then(parse_time),
+            "binc" => parameters.binc = iter.next().and_then(parse_time),
+            "movestogo" => parameters.movestogo = iter.next().and_then(|value| value.parse().ok()),
+            _ => {}
+        }
+    }
+    parameters
+}
+
+// Some GUIs send negative clock values when a player is in time trouble.
+fn parse_time(value: &str) -> Option<u64> {
+    value.parse::<i64>().ok().map(|time| time.max(0) as u64)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,159 @@
+use std::time::Duration;
+
+use crate::{
+    engine::{
+        piece::Color,
+        search_algorithms::limits::{Clock, SearchLimits},
+    },
+    uci::command::{parse, Command, GoParameters, DEFAULT_DEPTH},
+};
+
+#[test]
+fn test_parse_simple_commands() {
+    assert_eq!(parse("uci"), Some(Command::Uci));
+    assert_eq!(parse("isready"), Some(Command::IsReady));
+    assert_eq!(parse("ucinewgame"), Some(Command::UciNewGame));
+    assert_eq!(parse("  stop  "), Some(Command::Stop));
+    assert_eq!(parse("quit"), Some(Command::Quit));
+    assert_eq!(parse("register later"), None);
+    assert_eq!(parse(""), None);
+}
+
+#[test]
+fn test_parse_position_startpos() {
+    assert_eq!(
+        parse("position startpos moves e2e4 e7e5"),
+        Some(Command::Position {
+            fen: None,
+            moves: vec![String::from("e2e4"), String::from("e7e5")],
+        })
+    );
+    assert_eq!(
+        parse("position startpos"),
+        Some(Command::Position {
+            fen: None,
+            moves: vec![],
+        })
+    );
+}
+
+#[test]
+fn test_parse_position_fen() {
+    assert_eq!(
+        parse("position fen 4k3/8/8/8/8/8/8/4K2R w K - 0 1 moves e1g1"),
+        Some(Command::Position {
+            fen: Some(String::from("4k3/8/8/8/8/8/8/4K2R w K - 0 1")),
+            moves: vec![String::from("e1g1")],
+        })
+    );
+    assert_eq!(parse("position"), None);
+}
+
+#[test]
+fn test_parse_setoption() {
+    assert_eq!(
+        parse("setoption name Hash value 64"),
+        Some(Command::SetOption {
+            name: String::from("Hash"),
+            value: Some(String::from("64")),
+        })
+    );
+    assert_eq!(
+        parse("setoption name Clear Hash"),
+        Some(Command::SetOption {
+            name: String::from("Clear Hash"),
+            value: None,
+        })
+    );
+    assert_eq!(parse("setoption Hash 64"), None);
+}
+
+#[test]
+fn test_parse_go() {
+    assert_eq!(
+        parse("go depth 3"),
+        Some(Command::Go(GoParameters {
+            depth: Some(3),
+            ..Default::default()
+        }))
+    );
+    assert_eq!(
+        parse("go nodes 5000"),
+        Some(Command::Go(GoParameters {
+            nodes: Some(5000),
+            ..Default::default()
+        }))
+    );
+    assert_eq!(
+        parse("go wtime 60000 btime -20 winc 1000 binc 1000 movestogo 20"),
+        Some(Command::Go(GoParameters {
+            wtime: Some(60000),
+            btime: Some(0),
+            winc: Some(1000),
+            binc: Some(1000),
+            movestogo: Some(20),
+            ..Default::default()
+        }))
+    );
+    assert_eq!(
+        parse("go infinite"),
+        Some(Command::Go(GoParameters {
+            infinite: true,
+            ..Default::default()
+        }))
+    );
+}
+
+#[test]
+fn test_to_search_limits() {
+    let movetime = GoParameters {
+        movetime: Some(500),
+        ..Default::default()
+    };
+    assert_eq!(
+        movetime.to_search_limits(Color::Black),
+        SearchLimits::move_time(Duration::from_millis(500))
+    );
+
+    let clock = GoParameters {
+        wtime: Some(60000),
+        btime: Some(30000),
+        binc: Some(1000),
+        movestogo: Some(20),
+        ..Default::default()
+    };
+    assert_eq!(
+        clock.to_search_limits(Color::Black).clock,
+        Some(Clock {
+            remaining: Duration::from_millis(30000),
+            increment: Duration::from_millis(1000),
+            moves_to_go: Some(20),
+        })
+    );
+    assert_eq!(
+        clock.to_search_limits(Color::White).time_budget(),
+        Some(Duration::from_millis(3000))
+    );
+
+    let nodes = GoParameters {
+        nodes: Some(1000),
+        depth: Some(3),
+        ..Default::default()
+    };
+    assert_eq!(nodes.to_search_limits(Color::White).max_nodes, Some(1000));
+    assert_eq!(nodes.to_search_limits(Color::White).max_depth, Some(3));
+
+    assert_eq!(
+        GoParameters::default().to_search_limits(Color::White),
+        SearchLimits::depth(DEFAULT_DEPTH)
+    );
+    let infinite = GoParameters {
+        infinite: true,
+        depth: Some(3),
+        ..Default::default()
+    };
+    assert_eq!(
+        infinite.to_search_limits(Color::White),
+        SearchLimits::default()
+    );
+}
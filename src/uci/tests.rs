@@ -0,0 +1,172 @@
+use std::{
+    io::{Cursor, Write},
+    sync::{Arc, Mutex},
+};
+
+use crate::uci::run;
+
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn run_transcript(transcript: &str) -> Vec<String> {
+    let buffer = SharedBuffer::default();
+    run(Cursor::new(transcript.to_string()), buffer.clone());
+    let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+    output.lines().map(|line| line.to_string()).collect()
+}
+
+fn get_bestmove(lines: &[String]) -> Option<&str> {
+    lines.iter().find_map(|line| line.strip_prefix("bestmove "))
+}
+
+#[test]
+fn test_handshake() {
+    let lines = run_transcript("uci\nisready\nquit\n");
+    assert_eq!(lines[0], "id name luma");
+    assert!(lines[1].starts_with("id author"));
+    assert!(lines[2].starts_with("option name Hash type spin"));
+    assert_eq!(
+        lines[3],
+        "option name Threads type spin default 1 min 1 max 256"
+    );
+    assert_eq!(lines[4], "option name EvalFile type string default <empty>");
+    assert_eq!(
+        lines[5],
+        "option name NullMovePruning type check default true"
+    );
+    assert_eq!(lines[9], "uciok");
+    assert_eq!(lines[10], "readyok");
+}
+
+#[test]
+fn test_go_depth_reports_info_and_bestmove() {
+    let lines = run_transcript("position startpos moves e2e4 e7e5\ngo depth 2\n");
+    assert!(lines.iter().any(|line| line.starts_with("info depth 1 ")));
+    let index = lines
+        .iter()
+        .position(|line| line.starts_with("info depth 2 "))
+        .unwrap();
+    let info = &lines[index];
+    for field in [
+        " seldepth ",
+        " score cp ",
+        " nodes ",
+        " nps ",
+        " time ",
+        " hashfull ",
+        " pv ",
+    ] {
+        assert!(info.contains(field), "{info}");
+    }
+    let pawn_hits = lines[index + 1]
+        .strip_prefix("info string pawnhits ")
+        .and_then(|permille| permille.parse::<u32>().ok())
+        .unwrap();
+    assert!(pawn_hits <= 1000);
+    assert!(get_bestmove(&lines).is_some());
+}
+
+#[test]
+fn test_finds_mate_in_one() {
+    let lines = run_transcript("position fen 6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1\ngo depth 2\n");
+    assert!(lines
+        .iter()
+        .any(|line| line.starts_with("info depth 2 ") && line.contains(" score mate 1 ")));
+    assert_eq!(get_bestmove(&lines), Some("a1a8"));
+}
+
+#[test]
+fn test_position_moves_are_applied() {
+    // The pawn walks up to e7, so the promotion is only found if all moves were applied.
+    let lines = run_transcript(
+        "position fen 4k3/8/8/8/8/8/4P3/4K3 w - - 0 1 moves e2e4 e8d8 e4e5 d8c8 e5e6 c8b8 e6e7 b8a8\ngo depth 1\n",
+    );
+    assert_eq!(get_bestmove(&lines), Some("e7e8q"));
+}
+
+#[test]
+fn test_promotion_move_format() {
+    let lines = run_transcript("position fen 8/k3P3/8/8/8/8/8/K7 w - - 0 1\ngo depth 1\n");
+    assert_eq!(get_bestmove(&lines), Some("e7e8q"));
+}
+
+#[test]
+fn test_no_legal_moves() {
+    let lines = run_transcript("position fen 7k/5Q2/6K1/8/8/8/8/8 b - - 0 1\ngo depth 3\n");
+    assert_eq!(get_bestmove(&lines), Some("0000"));
+}
+
+#[test]
+fn test_invalid_input_is_reported() {
+    let lines = run_transcript("position fen 8/8 w - - 0 1\nposition startpos moves e2e5\n");
+    assert!(lines[0].starts_with("info string invalid fen"));
+    assert_eq!(lines[1], "info string 'e2e5' is not a legal move");
+}
+
+#[test]
+fn test_illegal_fen_keeps_previous_position() {
+    let lines = run_transcript(
+        "position fen 6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1\nposition fen 8/8/8/8/8/8/8/8 w - - 0 1\ngo depth 2\n",
+    );
+    assert_eq!(
+        lines[0],
+        "info string invalid fen: White has 0 kings instead of one"
+    );
+    assert_eq!(get_bestmove(&lines), Some("a1a8"));
+}
+
+#[test]
+fn test_infinite_waits_for_stop() {
+    let lines = run_transcript("position fen 7k/5Q2/6K1/8/8/8/8/8 b - - 0 1\ngo infinite\nstop\n");
+    assert_eq!(get_bestmove(&lines), Some("0000"));
+}
+
+#[test]
+fn test_setoption_hash() {
+    let lines = run_transcript(
+        "setoption name Hash value 1\nsetoption name Hash value x\nucinewgame\nposition startpos\ngo depth 2\n",
+    );
+    assert_eq!(lines[0], "info string invalid Hash value");
+    assert!(get_bestmove(&lines).is_some());
+}
+
+#[test]
+fn test_setoption_search_config() {
+    let lines = run_transcript(
+        "setoption name NullMovePruning value false\nsetoption name CheckExtensions value x\nsetoption name latemovereductions value false\nposition startpos\ngo depth 3\n",
+    );
+    assert_eq!(lines[0], "info string invalid CheckExtensions value");
+    assert!(get_bestmove(&lines).is_some());
+}
+
+#[test]
+fn test_setoption_threads() {
+    let lines = run_transcript(
+        "setoption name Threads value x\nsetoption name Threads value 3\nposition startpos\ngo depth 3\n",
+    );
+    assert_eq!(lines[0], "info string invalid Threads value");
+    assert!(get_bestmove(&lines).is_some());
+}
+
+#[test]
+fn test_setoption_eval_file() {
+    let path = std::env::temp_dir().join(format!("luma-eval-{}.txt", std::process::id()));
+    std::fs::write(&path, "material.queen.middlegame 950\n").unwrap();
+    let lines = run_transcript(&format!(
+        "setoption name EvalFile value {}\nsetoption name EvalFile value missing.txt\nposition startpos\ngo depth 2\n",
+        path.display()
+    ));
+    std::fs::remove_file(&path).unwrap();
+    assert!(lines[0].starts_with("info string invalid EvalFile"));
+    assert!(get_bestmove(&lines).is_some());
+}